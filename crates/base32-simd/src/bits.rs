@@ -0,0 +1,79 @@
+use crate::decode::{BASE32HEX_TABLE, BASE32_TABLE};
+use crate::{Error, Kind};
+use crate::{BASE32HEX_CHARSET, BASE32_CHARSET};
+
+use vsimd::tools::{read, write};
+
+/// Encodes the first `nbits` bits of `src` (MSB first) into `nbits.div_ceil(5)` characters.
+///
+/// Only the first `nbits.div_ceil(8)` bytes of `src` are read.
+/// The unused low bits of the last character are zero.
+#[inline]
+pub(crate) unsafe fn encode_bits(src: *const u8, nbits: usize, dst: *mut u8, kind: Kind) {
+    let charset = match kind {
+        Kind::Base32 => BASE32_CHARSET.as_ptr(),
+        Kind::Base32Hex => BASE32HEX_CHARSET.as_ptr(),
+    };
+
+    // The bytes which hold the first `nbits` bits. Zeros are shifted in after them.
+    let len = nbits / 8 + (nbits % 8 != 0) as usize;
+
+    let mut acc: u32 = 0;
+    let mut acc_bits: usize = 0;
+    let mut consumed: usize = 0;
+    let mut i = 0;
+
+    while consumed < nbits {
+        if acc_bits < 5 {
+            acc <<= 8;
+            if i < len {
+                acc |= u32::from(read(src, i));
+            }
+            acc_bits += 8;
+            i += 1;
+        }
+        acc_bits -= 5;
+        let mut bits = (acc >> acc_bits) & 0x1f;
+        let remaining = nbits - consumed;
+        if remaining < 5 {
+            bits &= (0x1f << (5 - remaining)) & 0x1f;
+        }
+        write(dst, consumed / 5, read(charset, bits as usize));
+        consumed += 5;
+    }
+}
+
+/// Decodes `n` characters into `(5 * n).div_ceil(8)` bytes (MSB first).
+///
+/// The unused low bits of the last byte are zero.
+#[inline]
+pub(crate) unsafe fn decode_bits(src: *const u8, n: usize, dst: *mut u8, kind: Kind) -> Result<(), Error> {
+    let table = match kind {
+        Kind::Base32 => BASE32_TABLE.as_ptr(),
+        Kind::Base32Hex => BASE32HEX_TABLE.as_ptr(),
+    };
+
+    let mut acc: u32 = 0;
+    let mut acc_bits: usize = 0;
+    let mut j = 0;
+
+    let mut i = 0;
+    while i < n {
+        let bits = read(table, read(src, i) as usize);
+        ensure!(bits != 0xff);
+        acc = (acc << 5) | u32::from(bits);
+        acc_bits += 5;
+        if acc_bits >= 8 {
+            acc_bits -= 8;
+            write(dst, j, (acc >> acc_bits) as u8);
+            j += 1;
+        }
+        i += 1;
+    }
+
+    if acc_bits > 0 {
+        write(dst, j, (acc << (8 - acc_bits)) as u8);
+    }
+
+    Ok(())
+}
@@ -53,3 +53,38 @@ pub(crate) unsafe fn check_simd<S: SIMD256>(s: S, mut src: *const u8, mut len: u
 fn check_ascii32<S: SIMD256>(s: S, x: V256, check: AlswLut<V256>) -> bool {
     vsimd::alsw::check_ascii_xn(s, x, check)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::encode::{encode_fallback, encoded_length_unchecked};
+
+    use vsimd::isa::{Fallback, InstructionSet};
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn fallback_isa() {
+        let s = unsafe { Fallback::new() };
+        let src: Vec<u8> = (0..150u32).map(|i| (i * 37 + 11) as u8).collect();
+
+        for kind in [Kind::Base32, Kind::Base32Hex] {
+            for m in 0..src.len() {
+                let n = encoded_length_unchecked(m, false);
+                let mut data = vec![0u8; n];
+                unsafe { encode_fallback(src.as_ptr(), m, data.as_mut_ptr(), kind, None) };
+
+                let check = |data: &[u8]| unsafe {
+                    let expected = check_fallback(data.as_ptr(), n, kind);
+                    let ans = check_simd(s, data.as_ptr(), n, kind);
+                    assert_eq!(ans.is_ok(), expected.is_ok(), "n = {n}");
+                };
+                check(&data);
+                if n > 0 {
+                    data[n * 2 / 3] = b'=';
+                    check(&data);
+                }
+            }
+        }
+    }
+}
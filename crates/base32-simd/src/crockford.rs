@@ -0,0 +1,436 @@
+//! Crockford base32
+//!
+//! The encoding designed by Douglas Crockford for identifiers which are read and typed by humans.
+//! See <https://www.crockford.com/base32.html>.
+//!
+//! + the character set is `0123456789ABCDEFGHJKMNPQRSTVWXYZ`, which omits `I`, `L`, `O` and `U`.
+//! + decoding is case-insensitive, and accepts `O` for `0`, `I` and `L` for `1`.
+//! + the bits are packed like RFC 4648 base32 without padding.
+//!   A string of `n` bytes is encoded to `ceil(8n / 5)` characters.
+//!
+//! The optional check symbol is the value of the encoded string, read as a base32 number, modulo 37.
+//! The values 32 to 36 are represented by the extra symbols `*~$=U`.
+//!
+//! The encoding is implemented with scalar code.
+//! Only the hyphen removal of [`decode_inplace`] is vectorized.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use base32_simd::crockford;
+//!
+//! let encoded = crockford::encode_with_checksum_to_string(b"hello");
+//! assert_eq!(encoded, "D1JPRV3FJ");
+//! assert_eq!(crockford::decode_with_checksum_to_vec("d1jprv3fj").unwrap(), b"hello");
+//! assert!(crockford::decode_with_checksum_to_vec("D1JPRV3FA").is_err());
+//! # }
+//! ```
+
+use crate::Error;
+
+use vsimd::mask::mask8x16_all;
+use vsimd::tools::{read, slice_mut, write};
+use vsimd::vector::V128;
+use vsimd::SIMD256;
+
+use outref::Out;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+
+/// The Crockford base32 character set.
+pub const CHARSET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// The extra check symbols for the values 32 to 36.
+pub const CHECK_SYMBOLS: &[u8; 5] = b"*~$=U";
+
+const fn decoding_table() -> [u8; 256] {
+    let mut table = [0xff; 256];
+    let mut i = 0;
+    while i < 32 {
+        let c = CHARSET[i];
+        table[c as usize] = i as u8;
+        table[c.to_ascii_lowercase() as usize] = i as u8;
+        i += 1;
+    }
+    table[b'O' as usize] = 0;
+    table[b'o' as usize] = 0;
+    table[b'I' as usize] = 1;
+    table[b'i' as usize] = 1;
+    table[b'L' as usize] = 1;
+    table[b'l' as usize] = 1;
+    table
+}
+
+const TABLE: &[u8; 256] = &decoding_table();
+
+/// Calculates the encoded length, without the check symbol.
+///
+/// # Panics
+/// This function asserts that `n <= usize::MAX / 8`.
+#[inline]
+#[must_use]
+pub const fn encoded_length(n: usize) -> usize {
+    assert!(n <= usize::MAX / 8);
+    (n * 8 + 4) / 5
+}
+
+/// Calculates the decoded length of a Crockford base32 string with `n` characters,
+/// without the check symbol.
+///
+/// # Errors
+/// This function returns `Err` if `n` is not the encoded length of any byte string.
+#[inline]
+pub fn decoded_length(n: usize) -> Result<usize, Error> {
+    ensure!(n <= usize::MAX / 5);
+    let m = n * 5 / 8;
+    ensure!(encoded_length(m) == n);
+    Ok(m)
+}
+
+/// Encodes bytes to a Crockford base32 string.
+///
+/// # Errors
+/// This function returns `Err` if the length of `dst` is not enough.
+#[inline]
+pub fn encode<'d>(src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+    ensure!(src.len() <= usize::MAX / 8);
+    let len = encoded_length(src.len());
+    ensure!(dst.len() >= len);
+    unsafe {
+        let dst = dst.as_mut_ptr();
+        encode_raw(src, dst);
+        Ok(slice_mut(dst, len))
+    }
+}
+
+/// Encodes bytes to a Crockford base32 string and appends the check symbol.
+///
+/// # Errors
+/// This function returns `Err` if the length of `dst` is not enough.
+#[inline]
+pub fn encode_with_checksum<'d>(src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+    ensure!(src.len() <= usize::MAX / 8);
+    let len = encoded_length(src.len());
+    ensure!(dst.len() > len);
+    unsafe {
+        let dst = dst.as_mut_ptr();
+        let checksum = encode_raw(src, dst);
+        write(dst, len, check_symbol(checksum));
+        Ok(slice_mut(dst, len + 1))
+    }
+}
+
+/// Decodes a Crockford base32 string to bytes.
+///
+/// Non-canonical strings are rejected:
+/// the unused low bits of the last character must be zero.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + the length of `dst` is not enough.
+/// + the content of `src` is invalid.
+#[inline]
+pub fn decode<'d>(src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+    let m = decoded_length(src.len())?;
+    ensure!(dst.len() >= m);
+    unsafe {
+        let dst = dst.as_mut_ptr();
+        decode_raw(src.as_ptr(), src.len(), dst)?;
+        Ok(slice_mut(dst, m))
+    }
+}
+
+/// Decodes a Crockford base32 string with a trailing check symbol to bytes.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + the length of `dst` is not enough.
+/// + the content of `src` is invalid.
+/// + the check symbol does not match.
+#[inline]
+pub fn decode_with_checksum<'d>(src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+    let (body, check) = split_check_symbol(src)?;
+    let m = decoded_length(body.len())?;
+    ensure!(dst.len() >= m);
+    unsafe {
+        let dst = dst.as_mut_ptr();
+        let checksum = decode_raw(body.as_ptr(), body.len(), dst)?;
+        ensure!(checksum == check);
+        Ok(slice_mut(dst, m))
+    }
+}
+
+/// Decodes a Crockford base32 string inplace, ignoring the hyphens.
+///
+/// Crockford base32 allows `-` anywhere as a visual separator, such as `01AB-CDEF`.
+/// The hyphens are removed before decoding, so they do not affect the decoded value.
+/// They are removed by SIMD code, which also validates the characters,
+/// so that an error reports the index in `data` rather than in the compacted string.
+///
+/// # Examples
+///
+/// ```
+/// use base32_simd::crockford;
+///
+/// let mut id = *b"91JP-RV3F";
+/// assert_eq!(crockford::decode_inplace(&mut id).unwrap(), b"Hello");
+///
+/// let mut id = *b"91JP-RU3F";
+/// assert_eq!(crockford::decode_inplace(&mut id).unwrap_err().index(), Some(6));
+/// ```
+///
+/// # Errors
+/// This function returns `Err` if
+/// + the number of characters without the hyphens is not the encoded length of any byte string.
+/// + the content of `data` is invalid.
+///   If a character is neither a hyphen nor in the character set,
+///   [`Error::index`] returns its index in `data`.
+#[inline]
+pub fn decode_inplace(data: &mut [u8]) -> Result<&mut [u8], Error> {
+    let n = unsafe { crate::multiversion::crockford_remove_hyphens::auto(data.as_mut_ptr(), data.len())? };
+
+    let m = decoded_length(n)?;
+    unsafe {
+        // Each byte is written after the characters which it is decoded from are read.
+        let dst = data.as_mut_ptr();
+        decode_raw(dst, n, dst)?;
+        Ok(slice_mut(dst, m))
+    }
+}
+
+/// Encodes bytes to a Crockford base32 string.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn encode_to_string(data: impl AsRef<[u8]>) -> String {
+    let src = data.as_ref();
+    let mut buf = vec![0; encoded_length(src.len())];
+    unsafe {
+        encode_raw(src, buf.as_mut_ptr());
+        String::from_utf8_unchecked(buf)
+    }
+}
+
+/// Encodes bytes to a Crockford base32 string with a trailing check symbol.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn encode_with_checksum_to_string(data: impl AsRef<[u8]>) -> String {
+    let src = data.as_ref();
+    let len = encoded_length(src.len());
+    let mut buf = vec![0; len + 1];
+    unsafe {
+        let checksum = encode_raw(src, buf.as_mut_ptr());
+        buf[len] = check_symbol(checksum);
+        String::from_utf8_unchecked(buf)
+    }
+}
+
+/// Decodes a Crockford base32 string to bytes.
+///
+/// # Errors
+/// This function returns `Err` if the content of `data` is invalid.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn decode_to_vec(data: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
+    let src = data.as_ref();
+    let mut buf = vec![0; decoded_length(src.len())?];
+    unsafe { decode_raw(src.as_ptr(), src.len(), buf.as_mut_ptr())? };
+    Ok(buf)
+}
+
+/// Decodes a Crockford base32 string with a trailing check symbol to bytes.
+///
+/// # Errors
+/// This function returns `Err` if the content of `data` is invalid or the check symbol does not match.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn decode_with_checksum_to_vec(data: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
+    let (body, check) = split_check_symbol(data.as_ref())?;
+    let mut buf = vec![0; decoded_length(body.len())?];
+    let checksum = unsafe { decode_raw(body.as_ptr(), body.len(), buf.as_mut_ptr())? };
+    ensure!(checksum == check);
+    Ok(buf)
+}
+
+#[inline(always)]
+fn check_symbol(checksum: u8) -> u8 {
+    match checksum {
+        0..=31 => CHARSET[checksum as usize],
+        _ => CHECK_SYMBOLS[checksum as usize - 32],
+    }
+}
+
+/// Splits the check symbol from the end of `src` and returns its value.
+#[inline(always)]
+fn split_check_symbol(src: &[u8]) -> Result<(&[u8], u8), Error> {
+    let (&last, body) = match src.split_last() {
+        Some(x) => x,
+        None => return Err(Error::new()),
+    };
+    let check = match CHECK_SYMBOLS.iter().position(|&c| c == last.to_ascii_uppercase()) {
+        Some(i) => 32 + i as u8,
+        None => TABLE[last as usize],
+    };
+    ensure!(check != 0xff);
+    Ok((body, check))
+}
+
+/// Writes `encoded_length(src.len())` characters and returns the checksum.
+#[inline(always)]
+unsafe fn encode_raw(src: &[u8], dst: *mut u8) -> u8 {
+    let mut acc: u16 = 0;
+    let mut nbits: u32 = 0;
+    let mut checksum: u32 = 0;
+    let mut j = 0;
+
+    let mut push = |bits: u16| {
+        write(dst, j, read(CHARSET.as_ptr(), bits as usize));
+        checksum = (checksum * 32 + u32::from(bits)) % 37;
+        j += 1;
+    };
+
+    for &x in src {
+        acc = (acc << 8) | u16::from(x);
+        nbits += 8;
+        while nbits >= 5 {
+            nbits -= 5;
+            push((acc >> nbits) & 0x1f);
+        }
+        acc &= (1 << nbits) - 1;
+    }
+    if nbits > 0 {
+        push((acc << (5 - nbits)) & 0x1f);
+    }
+
+    checksum as u8
+}
+
+/// Writes `decoded_length(len)` bytes and returns the checksum.
+#[inline(always)]
+unsafe fn decode_raw(src: *const u8, len: usize, dst: *mut u8) -> Result<u8, Error> {
+    let mut acc: u16 = 0;
+    let mut nbits: u32 = 0;
+    let mut checksum: u32 = 0;
+    let mut j = 0;
+
+    for i in 0..len {
+        let x = read(src, i);
+        let bits = read(TABLE.as_ptr(), x as usize);
+        ensure!(bits != 0xff);
+        checksum = (checksum * 32 + u32::from(bits)) % 37;
+
+        acc = (acc << 5) | u16::from(bits);
+        nbits += 5;
+        if nbits >= 8 {
+            nbits -= 8;
+            write(dst, j, (acc >> nbits) as u8);
+            j += 1;
+        }
+        acc &= (1 << nbits) - 1;
+    }
+    ensure!(acc == 0);
+
+    Ok(checksum as u8)
+}
+
+/// Removes the hyphens of `data[i..len]` inplace, after `n` characters have been kept.
+///
+/// Returns the number of the kept characters, or the index of the first invalid character.
+#[inline(always)]
+unsafe fn remove_hyphens_short(data: *mut u8, mut i: usize, mut n: usize, len: usize) -> Result<usize, Error> {
+    while i < len {
+        let x = read(data, i);
+        if x != b'-' {
+            if read(TABLE.as_ptr(), x as usize) == 0xff {
+                return Err(Error::at(i));
+            }
+            write(data, n, x);
+            n += 1;
+        }
+        i += 1;
+    }
+    Ok(n)
+}
+
+#[inline(always)]
+pub(crate) unsafe fn remove_hyphens_fallback(data: *mut u8, len: usize) -> Result<usize, Error> {
+    remove_hyphens_short(data, 0, 0, len)
+}
+
+/// The indices of the lanes to keep for each 8-bit hyphen mask.
+const PACK_TABLE: &[[u8; 8]; 256] = &{
+    let mut table = [[0; 8]; 256];
+    let mut m = 0;
+    while m < 256 {
+        let mut j = 0;
+        let mut k = 0;
+        while k < 8 {
+            if m & (1 << k) == 0 {
+                table[m][j] = k as u8;
+                j += 1;
+            }
+            k += 1;
+        }
+        m += 1;
+    }
+    table
+};
+
+/// Offsets the indices of the high half of a block.
+const HALF_OFFSET: V128 = V128::from_bytes([0, 0, 0, 0, 0, 0, 0, 0, 8, 8, 8, 8, 8, 8, 8, 8]);
+
+/// Packs the high bits of 8 mask lanes into a byte.
+#[inline(always)]
+fn mask8(x: [u8; 8]) -> u8 {
+    ((u64::from_le_bytes(x) & 0x8040_2010_0804_0201).wrapping_mul(0x0101_0101_0101_0101) >> 56) as u8
+}
+
+#[inline(always)]
+pub(crate) unsafe fn remove_hyphens_simd<S: SIMD256>(s: S, data: *mut u8, len: usize) -> Result<usize, Error> {
+    let mut i = 0;
+    let mut n = 0;
+
+    while len - i >= 16 {
+        let x = s.v128_load_unaligned(data.add(i));
+
+        let is_hyphen = s.u8x16_eq(x, s.u8x16_splat(b'-'));
+        let is_digit = s.u8x16_lt(s.u8x16_sub(x, s.u8x16_splat(b'0')), s.u8x16_splat(10));
+        let lower = s.v128_or(x, s.u8x16_splat(0x20));
+        let is_letter = s.u8x16_lt(s.u8x16_sub(lower, s.u8x16_splat(b'a')), s.u8x16_splat(26));
+        let is_letter = s.v128_andnot(is_letter, s.u8x16_eq(lower, s.u8x16_splat(b'u')));
+        if !mask8x16_all(s, s.v128_or(s.v128_or(is_hyphen, is_digit), is_letter)) {
+            // Locates the invalid character.
+            break;
+        }
+
+        let (lo, hi) = is_hyphen.to_v64x2();
+        let (m1, m2) = (mask8(*lo.as_bytes()), mask8(*hi.as_bytes()));
+        if (m1 | m2) == 0 {
+            s.v128_store_unaligned(data.add(n), x);
+            n += 16;
+        } else {
+            let mut index = [0u8; 16];
+            index[..8].copy_from_slice(&PACK_TABLE[m1 as usize]);
+            index[8..].copy_from_slice(&PACK_TABLE[m2 as usize]);
+            let index = s.u8x16_add(s.v128_load_unaligned(index.as_ptr()), HALF_OFFSET);
+            let (lo, hi) = s.u8x16_swizzle(x, index).to_v64x2();
+
+            // `n <= i`, so the 8-byte stores do not reach the unread bytes after the block.
+            data.add(n).cast::<[u8; 8]>().write_unaligned(*lo.as_bytes());
+            n += m1.count_zeros() as usize;
+            data.add(n).cast::<[u8; 8]>().write_unaligned(*hi.as_bytes());
+            n += m2.count_zeros() as usize;
+        }
+        i += 16;
+    }
+
+    remove_hyphens_short(data, i, n, len)
+}
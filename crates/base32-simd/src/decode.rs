@@ -13,7 +13,7 @@ use vsimd::{matches_isa, simd256_vop, SIMD256};
 use core::ops::Not;
 
 #[inline]
-const fn decoding_table(charset: &[u8; 32]) -> [u8; 256] {
+pub(crate) const fn decoding_table(charset: &[u8; 32]) -> [u8; 256] {
     let mut table = [0xff; 256];
     let mut i = 0;
     while i < 32 {
@@ -27,16 +27,16 @@ pub const BASE32_TABLE: &[u8; 256] = &decoding_table(BASE32_CHARSET);
 pub const BASE32HEX_TABLE: &[u8; 256] = &decoding_table(BASE32HEX_CHARSET);
 
 #[inline]
-pub fn decoded_length(data: &[u8], padding: bool) -> Result<(usize, usize), Error> {
+pub fn decoded_length(data: &[u8], pad: Option<u8>) -> Result<(usize, usize), Error> {
     if data.is_empty() {
         return Ok((0, 0));
     }
 
     let len = data.len();
-    let n = if padding {
+    let n = if let Some(pad) = pad {
         ensure!(len % 8 == 0);
         let last = unsafe { data.get_unchecked(len - 6..) };
-        let count = last.iter().copied().filter(|&x| x == b'=').count();
+        let count = last.iter().copied().filter(|&x| x == pad).count();
         len - count
     } else {
         data.len()
@@ -101,38 +101,7 @@ pub unsafe fn decode_extra<const WRITE: bool>(
     dst: *mut u8,
     table: *const u8,
 ) -> Result<(), Error> {
-    match extra {
-        0 => {}
-        2 => {
-            let (u10, flag) = decode_bits::<2>(src, table);
-            ensure!(flag != 0xff && u10 & 0b11 == 0);
-            if WRITE {
-                write_be_bytes::<1>(dst, u10 >> 2);
-            }
-        }
-        4 => {
-            let (u20, flag) = decode_bits::<4>(src, table);
-            ensure!(flag != 0xff && u20 & 0b1111 == 0);
-            if WRITE {
-                write_be_bytes::<2>(dst, u20 >> 4);
-            }
-        }
-        5 => {
-            let (u25, flag) = decode_bits::<5>(src, table);
-            ensure!(flag != 0xff && u25 & 0b1 == 0);
-            if WRITE {
-                write_be_bytes::<3>(dst, u25 >> 1);
-            }
-        }
-        7 => {
-            let (u35, flag) = decode_bits::<7>(src, table);
-            ensure!(flag != 0xff && u35 & 0b111 == 0);
-            if WRITE {
-                write_be_bytes::<4>(dst, u35 >> 3);
-            }
-        }
-        _ => core::hint::unreachable_unchecked(),
-    }
+    ensure!(vsimd::reference::decode_bits::<5, WRITE>(src, extra, dst, table, false));
     Ok(())
 }
 
@@ -174,14 +143,12 @@ pub(crate) unsafe fn decode_simd<S: SIMD256>(
         Kind::Base32Hex => (BASE32HEX_ALSW_CHECK_X2, BASE32HEX_ALSW_DECODE_X2),
     };
 
-    // n*5/8 >= 10+10+6
-    while n >= 42 {
+    // n*5/8 >= 20+12
+    while n >= 52 {
         let x = s.v256_load_unaligned(src);
         let y = try_!(decode_ascii32(s, x, check_lut, decode_lut));
 
-        let (y1, y2) = y.to_v128x2();
-        s.v128_store_unaligned(dst, y1);
-        s.v128_store_unaligned(dst.add(10), y2);
+        store_bytes20(s, dst, y);
 
         src = src.add(32);
         dst = dst.add(20);
@@ -264,6 +231,27 @@ fn merge_bits<S: SIMD256>(s: S, x: V256) -> V256 {
     unreachable!()
 }
 
+/// Stores the 20 bytes from `merge_bits`, which are the low 10 bytes of each lane.
+///
+/// It writes 32 bytes to `dst` on AVX2 and 26 bytes on the others.
+#[inline(always)]
+unsafe fn store_bytes20<S: SIMD256>(s: S, dst: *mut u8, y: V256) {
+    if matches_isa!(S, AVX2) {
+        const PACK: V256 = V256::from_bytes([
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, //
+            0x08, 0x09, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, //
+            0x16, 0x17, 0x18, 0x19, 0x80, 0x80, 0x80, 0x80, //
+            0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, //
+        ]);
+        let y = s.shuffle_bytes_x2(y, PACK);
+        s.v256_store_unaligned(dst, y);
+    } else {
+        let (y1, y2) = y.to_v128x2();
+        s.v128_store_unaligned(dst, y1);
+        s.v128_store_unaligned(dst.add(10), y2);
+    }
+}
+
 #[inline(always)]
 fn decode_ascii32<S: SIMD256>(s: S, x: V256, check: AlswLut<V256>, decode: AlswLut<V256>) -> Result<V256, Error> {
     let (c1, c2) = vsimd::alsw::decode_ascii_xn(s, x, check, decode);
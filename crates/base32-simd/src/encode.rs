@@ -1,10 +1,10 @@
 use crate::{u16x4_to_u64, Kind};
 use crate::{BASE32HEX_CHARSET, BASE32_CHARSET};
 
-use vsimd::isa::{NEON, SSE41, SSSE3, WASM128};
+use vsimd::isa::{Fallback, NEON, SSE41, SSSE3, WASM128};
 use vsimd::tools::{read, write};
 use vsimd::vector::V256;
-use vsimd::{matches_isa, SIMD256};
+use vsimd::{is_isa_type, matches_isa, SIMD256};
 
 pub const fn encoded_length_unchecked(len: usize, padding: bool) -> usize {
     let l = len / 5 * 8;
@@ -18,6 +18,22 @@ pub const fn encoded_length_unchecked(len: usize, padding: bool) -> usize {
     l + EXTRA[len % 5] as usize
 }
 
+/// Returns `None` if the encoded length is greater than `isize::MAX`,
+/// which is the size limit of any slice or allocation.
+///
+/// On 32-bit targets, an input of about 1.25 GiB reaches the limit.
+#[inline(always)]
+pub(crate) const fn checked_encoded_length(len: usize, padding: bool) -> Option<usize> {
+    if len > usize::MAX / 2 {
+        return None;
+    }
+    let m = encoded_length_unchecked(len, padding);
+    if m > isize::MAX as usize {
+        return None;
+    }
+    Some(m)
+}
+
 #[inline(always)]
 pub unsafe fn encode_bits<const N: usize>(dst: *mut u8, charset: *const u8, x: u64) {
     debug_assert!(matches!(N, 2 | 4 | 5 | 7 | 8));
@@ -63,55 +79,27 @@ pub unsafe fn read_be_bytes<const N: usize>(src: *const u8) -> u64 {
 }
 
 #[inline(always)]
-unsafe fn encode_extra(src: *const u8, extra: usize, dst: *mut u8, charset: *const u8, padding: bool) {
-    match extra {
-        0 => {}
-        1 => {
-            let u10 = read_be_bytes::<1>(src) << 2;
-            encode_bits::<2>(dst, charset, u10);
-            if padding {
-                let mut i = 2;
-                while i < 8 {
-                    write(dst, i, b'=');
-                    i += 1;
-                }
-            }
-        }
-        2 => {
-            let u20 = read_be_bytes::<2>(src) << 4;
-            encode_bits::<4>(dst, charset, u20);
-            if padding {
-                let mut i = 4;
-                while i < 8 {
-                    write(dst, i, b'=');
-                    i += 1;
-                }
-            }
-        }
-        3 => {
-            let u25 = read_be_bytes::<3>(src) << 1;
-            encode_bits::<5>(dst, charset, u25);
-            if padding {
-                let mut i = 5;
-                while i < 8 {
-                    write(dst, i, b'=');
-                    i += 1;
-                }
-            }
-        }
-        4 => {
-            let u35 = read_be_bytes::<4>(src) << 3;
-            encode_bits::<7>(dst, charset, u35);
-            if padding {
-                write(dst, 7, b'=');
+unsafe fn encode_extra(src: *const u8, extra: usize, dst: *mut u8, charset: *const u8, pad: Option<u8>) {
+    let k = vsimd::reference::encode_bits::<5>(src, extra, dst, charset);
+    if let Some(pad) = pad {
+        if extra != 0 {
+            let mut i = k;
+            while i < 8 {
+                write(dst, i, pad);
+                i += 1;
             }
         }
-        _ => core::hint::unreachable_unchecked(),
     }
 }
 
 #[inline(always)]
-pub(crate) unsafe fn encode_fallback(mut src: *const u8, mut len: usize, mut dst: *mut u8, kind: Kind, padding: bool) {
+pub(crate) unsafe fn encode_fallback(
+    mut src: *const u8,
+    mut len: usize,
+    mut dst: *mut u8,
+    kind: Kind,
+    pad: Option<u8>,
+) {
     let charset: *const u8 = match kind {
         Kind::Base32 => BASE32_CHARSET.as_ptr(),
         Kind::Base32Hex => BASE32HEX_CHARSET.as_ptr(),
@@ -126,7 +114,7 @@ pub(crate) unsafe fn encode_fallback(mut src: *const u8, mut len: usize, mut dst
     }
     len %= 5;
 
-    encode_extra(src, len, dst, charset, padding);
+    encode_extra(src, len, dst, charset, pad);
 }
 
 #[inline(always)]
@@ -136,7 +124,7 @@ pub(crate) unsafe fn encode_simd<S: SIMD256>(
     mut len: usize,
     mut dst: *mut u8,
     kind: Kind,
-    padding: bool,
+    pad: Option<u8>,
 ) {
     let (charset, encoding_lut) = match kind {
         Kind::Base32 => (BASE32_CHARSET.as_ptr(), BASE32_ENCODING_LUT),
@@ -168,7 +156,7 @@ pub(crate) unsafe fn encode_simd<S: SIMD256>(
         }
     }
 
-    encode_fallback(src, len, dst, kind, padding);
+    encode_fallback(src, len, dst, kind, pad);
 }
 
 #[inline(always)]
@@ -192,7 +180,7 @@ fn split_bits<S: SIMD256>(s: S, x: V256) -> V256 {
         return s.v256_or(x4, x5);
     }
 
-    if matches_isa!(S, NEON | WASM128) {
+    if matches_isa!(S, NEON | WASM128) || is_isa_type!(S, Fallback) {
         const SPLIT_M1: u64 = u16x4_to_u64([1 << 1, 1 << 3, 1 << 5, 1 << 7]);
         const SPLIT_M2: u64 = u16x4_to_u64([1 << 2, 1 << 4, 1 << 6, 1 << 8]);
         const SPLIT_M3: u16 = u16::from_le_bytes([0x00, 0x1f]);
@@ -242,7 +230,7 @@ fn encode_values<S: SIMD256>(s: S, x: V256, lut: EncodingLutX2) -> V256 {
     if matches_isa!(S, NEON) && cfg!(target_arch = "aarch64") {
         return s.u8x32_swizzle(lut.full, x);
     }
-    if matches_isa!(S, NEON | WASM128) {
+    if matches_isa!(S, NEON | WASM128) || is_isa_type!(S, Fallback) {
         let m = s.u8x32_splat(0x0f);
         let x1 = s.v256_and(x, m);
         let x2 = s.u8x16x2_swizzle(lut.low, x1);
@@ -263,3 +251,30 @@ fn encode_bytes20<S: SIMD256>(s: S, x: V256, lut: EncodingLutX2) -> V256 {
     encode_values(s, values, lut)
     // {{ascii}}x32
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use vsimd::isa::InstructionSet;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn fallback_isa() {
+        let s = unsafe { Fallback::new() };
+        let src: Vec<u8> = (0..200u32).map(|i| (i * 37 + 11) as u8).collect();
+
+        for (kind, pad) in [(Kind::Base32, Some(b'=')), (Kind::Base32Hex, None)] {
+            for n in 0..src.len() {
+                let len = encoded_length_unchecked(n, pad.is_some());
+                let mut expected = vec![0u8; len];
+                let mut ans = vec![0u8; len];
+                unsafe {
+                    encode_fallback(src.as_ptr(), n, expected.as_mut_ptr(), kind, pad);
+                    encode_simd(s, src.as_ptr(), n, ans.as_mut_ptr(), kind, pad);
+                }
+                assert_eq!(ans, expected, "n = {n}");
+            }
+        }
+    }
+}
@@ -1,12 +1,28 @@
 use core::fmt;
 
 /// Base32 Error
-pub struct Error(());
+pub struct Error {
+    index: Option<usize>,
+}
 
 impl Error {
     #[inline(always)]
     pub(crate) const fn new() -> Self {
-        Error(())
+        Error { index: None }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn at(index: usize) -> Self {
+        Error { index: Some(index) }
+    }
+
+    /// Returns the index of the first offending character in the input.
+    ///
+    /// Only the functions which document it report the index. The others return `None`.
+    #[inline]
+    #[must_use]
+    pub const fn index(&self) -> Option<usize> {
+        self.index
     }
 }
 
@@ -0,0 +1,179 @@
+use crate::decode::decoded_length;
+use crate::{Base32, Error, Out};
+
+use vsimd::tools::{read, slice_mut, write};
+
+use core::ops::Not;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec::Vec};
+
+/// Removes ASCII whitespace, converts lowercase letters to uppercase
+/// and strips the trailing padding characters.
+///
+/// Writes the result to `dst` and returns its length, which is not greater than `len`.
+/// `src` and `dst` may be the same pointer.
+#[inline(always)]
+unsafe fn normalize(src: *const u8, len: usize, dst: *mut u8, pad_char: u8) -> usize {
+    let mut j = 0;
+    let mut i = 0;
+    while i < len {
+        let x = read(src, i);
+        if x.is_ascii_whitespace().not() {
+            write(dst, j, x.to_ascii_uppercase());
+            j += 1;
+        }
+        i += 1;
+    }
+    while j > 0 && read(dst, j - 1) == pad_char {
+        j -= 1;
+    }
+    j
+}
+
+impl Base32 {
+    /// Forgiving decodes a base32 string to bytes and writes inplace.
+    ///
+    /// The input is normalized before a strict decoding:
+    /// + ASCII whitespace is removed.
+    /// + lowercase letters are converted to uppercase.
+    /// + padding is optional, regardless of whether `self` uses padding.
+    ///
+    /// This is useful for handling secrets copied by hand, such as `"jbsw y3dp ehpk 3pxp"`.
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid after normalization.
+    #[inline]
+    pub fn forgiving_decode_inplace<'d>(&'_ self, data: &'d mut [u8]) -> Result<&'d mut [u8], Error> {
+        unsafe {
+            let dst = data.as_mut_ptr();
+            let len = normalize(dst, data.len(), dst, self.pad_char);
+            self.decode_normalized(slice_mut(dst, len))
+        }
+    }
+
+    /// Forgiving decodes a base32 string to bytes.
+    ///
+    /// See [`forgiving_decode_inplace`](Self::forgiving_decode_inplace) for the normalization.
+    /// `dst` is also used as a scratch buffer for the normalized input.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + `src.len() > dst.len()`
+    /// + the content of `src` is invalid after normalization.
+    #[inline]
+    pub fn forgiving_decode<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        ensure!(src.len() <= dst.len());
+        unsafe {
+            let dst = dst.as_mut_ptr();
+            let len = normalize(src.as_ptr(), src.len(), dst, self.pad_char);
+            self.decode_normalized(slice_mut(dst, len))
+        }
+    }
+
+    /// Forgiving decodes a base32 string to bytes and returns a new [`Vec<u8>`](Vec).
+    ///
+    /// See [`forgiving_decode_inplace`](Self::forgiving_decode_inplace) for the normalization.
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid after normalization.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn forgiving_decode_to_vec(&self, data: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
+        let mut buf = data.as_ref().to_vec();
+        let len = self.forgiving_decode_inplace(&mut buf)?.len();
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    /// Checks whether `data` is accepted by the forgiving decoding functions.
+    ///
+    /// See [`forgiving_decode_inplace`](Self::forgiving_decode_inplace) for the normalization.
+    /// Nothing is written and nothing is allocated, so a user interface can validate a secret as the user types.
+    /// This function returns `Ok` if and only if [`forgiving_decode`](Self::forgiving_decode) succeeds.
+    ///
+    /// An input without whitespace and lowercase letters is checked inplace.
+    /// Otherwise it is normalized in chunks on the stack.
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid after normalization.
+    #[inline]
+    pub fn check_relaxed(&self, data: &[u8]) -> Result<(), Error> {
+        const CHUNK: usize = 256;
+
+        let is_trailing = |x: u8| x.is_ascii_whitespace() || x.to_ascii_uppercase() == self.pad_char;
+        let mut end = data.len();
+        while end > 0 && is_trailing(data[end - 1]) {
+            end -= 1;
+        }
+        let data = &data[..end];
+
+        if data
+            .iter()
+            .any(|&x| x.is_ascii_whitespace() || x.is_ascii_lowercase())
+            .not()
+        {
+            return self.check_normalized(data);
+        }
+
+        let mut buf = [0u8; CHUNK];
+        let mut len = 0;
+        for &x in data {
+            if x.is_ascii_whitespace() {
+                continue;
+            }
+            if len == CHUNK {
+                unsafe { crate::multiversion::check::auto(buf.as_ptr(), CHUNK, self.kind)? };
+                len = 0;
+            }
+            buf[len] = x.to_ascii_uppercase();
+            len += 1;
+        }
+        self.check_normalized(&buf[..len])
+    }
+
+    /// Checks a normalized base32 string without padding.
+    #[inline(always)]
+    fn check_normalized(&self, data: &[u8]) -> Result<(), Error> {
+        let (n, _) = decoded_length(data, None)?;
+        unsafe { crate::multiversion::check::auto(data.as_ptr(), n, self.kind) }
+    }
+
+    /// Decodes a normalized base32 string without padding inplace.
+    #[inline(always)]
+    fn decode_normalized<'d>(&'_ self, data: &'d mut [u8]) -> Result<&'d mut [u8], Error> {
+        unsafe {
+            let (n, m) = decoded_length(data, None)?;
+
+            let dst: *mut u8 = data.as_mut_ptr();
+            let src: *const u8 = dst;
+            crate::multiversion::decode::auto(src, n, dst, self.kind)?;
+
+            Ok(slice_mut(dst, m))
+        }
+    }
+}
+
+/// Decodes a TOTP secret, as shown by authenticator apps, to bytes.
+///
+/// TOTP secrets are RFC 4648 base32 strings which are usually displayed in lowercase,
+/// split into groups by spaces and without padding, such as `"jbsw y3dp ehpk 3pxp"`.
+/// This function is [`BASE32.forgiving_decode_to_vec`](Base32::forgiving_decode_to_vec)
+/// returning a boxed slice.
+///
+/// # Examples
+///
+/// ```
+/// let key = base32_simd::decode_totp_secret("jbsw y3dp ehpk 3pxp").unwrap();
+/// assert_eq!(&*key, b"Hello!\xde\xad\xbe\xef");
+/// ```
+///
+/// # Errors
+/// This function returns `Err` if the content of `s` is invalid after normalization.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn decode_totp_secret(s: &str) -> Result<Box<[u8]>, Error> {
+    crate::BASE32.forgiving_decode_to_vec(s).map(Vec::into_boxed_slice)
+}
@@ -0,0 +1,210 @@
+use crate::encode::{checked_encoded_length, encoded_length_unchecked};
+use crate::{Base32, Error, Out};
+
+use vsimd::tools::{read, slice_mut, slice_parts, write};
+
+use core::ptr::{copy, copy_nonoverlapping};
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+/// Removes ASCII whitespace.
+///
+/// Writes the result to `dst` and returns its length, which is not greater than `len`.
+/// `src` and `dst` may be the same pointer.
+#[inline(always)]
+unsafe fn remove_whitespace(src: *const u8, len: usize, dst: *mut u8) -> usize {
+    let mut j = 0;
+    let mut i = 0;
+    while i < len {
+        let x = read(src, i);
+        if !x.is_ascii_whitespace() {
+            write(dst, j, x);
+            j += 1;
+        }
+        i += 1;
+    }
+    j
+}
+
+impl Base32 {
+    /// Calculates the encoded length with a separator of `sep_len` bytes after every `group` characters.
+    ///
+    /// There is no separator after the last group.
+    ///
+    /// Returns `None` if the length overflows.
+    ///
+    /// # Panics
+    /// This function asserts that `group > 0`.
+    #[inline]
+    #[must_use]
+    pub const fn grouped_encoded_length(&self, n: usize, group: usize, sep_len: usize) -> Option<usize> {
+        assert!(group > 0);
+        let m = match checked_encoded_length(n, self.padding) {
+            Some(m) => m,
+            None => return None,
+        };
+        if m == 0 {
+            return Some(0);
+        }
+        match ((m - 1) / group).checked_mul(sep_len) {
+            Some(k) => m.checked_add(k),
+            None => None,
+        }
+    }
+
+    /// Encodes bytes to a base32 string, inserting `sep` after every `group` characters.
+    ///
+    /// There is no separator after the last group.
+    /// This covers the presentation format of DNS zone files, which wraps long fields into lines,
+    /// such as `group = 32`, `sep = b"\n"` or space-separated groups.
+    ///
+    /// The output length is [`grouped_encoded_length`](Self::grouped_encoded_length).
+    /// [`decode_wrapped`](Self::decode_wrapped) decodes the output if `sep` is whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base32_simd::{AsOut, BASE32HEX_NO_PAD};
+    ///
+    /// let mut buf = [0u8; 64];
+    /// let ans = BASE32HEX_NO_PAD.encode_grouped(b"hello world", buf.as_mut_slice().as_out(), 8, b" ").unwrap();
+    /// assert_eq!(ans, b"D1IMOR3F 41RMUSJC CG");
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns `Err` if the length of `dst` is not enough.
+    ///
+    /// # Panics
+    /// This function asserts that `group > 0`.
+    #[inline]
+    pub fn encode_grouped<'d>(
+        &self,
+        src: &[u8],
+        mut dst: Out<'d, [u8]>,
+        group: usize,
+        sep: &[u8],
+    ) -> Result<&'d mut [u8], Error> {
+        let total = self.grouped_encoded_length(src.len(), group, sep.len());
+        let total = match total {
+            Some(total) => total,
+            None => return Err(Error::new()),
+        };
+        ensure!(dst.len() >= total);
+
+        unsafe {
+            let m = encoded_length_unchecked(src.len(), self.padding);
+            let dst = dst.as_mut_ptr();
+
+            // Encode to the end of the output, then move the groups forward to their places.
+            // The destination of each group never passes its source,
+            // and the separators after it only overwrite the characters which have been moved.
+            let base = dst.add(total - m);
+            let (src, len) = slice_parts(src);
+            crate::multiversion::encode::auto(src, len, base, self.kind, self.pad());
+
+            if !sep.is_empty() {
+                let mut i = 0;
+                let mut cur = dst;
+                while i < m {
+                    let k = group.min(m - i);
+                    copy(base.add(i), cur, k);
+                    cur = cur.add(k);
+                    i += k;
+                    if i < m {
+                        copy_nonoverlapping(sep.as_ptr(), cur, sep.len());
+                        cur = cur.add(sep.len());
+                    }
+                }
+                debug_assert_eq!(cur, dst.add(total));
+            }
+
+            Ok(slice_mut(dst, total))
+        }
+    }
+
+    /// Encodes bytes to a base32 string, inserting `sep` after every `group` characters,
+    /// and returns a new [`String`].
+    ///
+    /// See [`encode_grouped`](Self::encode_grouped).
+    ///
+    /// # Panics
+    /// This function panics if `group == 0` or the length of the output overflows.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn encode_grouped_to_string(&self, data: impl AsRef<[u8]>, group: usize, sep: &str) -> String {
+        let data = data.as_ref();
+        let total = self.grouped_encoded_length(data.len(), group, sep.len()).unwrap();
+
+        let mut buf: Vec<u8> = Vec::with_capacity(total);
+        let dst = Out::from_uninit_slice(&mut buf.spare_capacity_mut()[..total]);
+        let len = self.encode_grouped(data, dst, group, sep.as_bytes()).unwrap().len();
+        unsafe {
+            buf.set_len(len);
+            String::from_utf8_unchecked(buf)
+        }
+    }
+
+    /// Decodes a wrapped base32 string to bytes and writes inplace.
+    ///
+    /// ASCII whitespace is removed before a strict decoding.
+    /// Unlike [`forgiving_decode_inplace`](Self::forgiving_decode_inplace),
+    /// the case and the padding are not relaxed.
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid after removing whitespace.
+    #[inline]
+    pub fn decode_wrapped_inplace<'d>(&'_ self, data: &'d mut [u8]) -> Result<&'d mut [u8], Error> {
+        let pos = match data.iter().position(u8::is_ascii_whitespace) {
+            Some(pos) => pos,
+            None => return self.decode_inplace(data),
+        };
+
+        unsafe {
+            let len = data.len();
+            let dst = data.as_mut_ptr();
+            let rem = remove_whitespace(dst.add(pos), len - pos, dst.add(pos));
+            self.decode_inplace(slice_mut(dst, pos + rem))
+        }
+    }
+
+    /// Decodes a wrapped base32 string to bytes.
+    ///
+    /// See [`decode_wrapped_inplace`](Self::decode_wrapped_inplace).
+    /// If `src` contains whitespace, `dst` is also used as a scratch buffer for the filtered input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base32_simd::{AsOut, BASE32HEX_NO_PAD};
+    ///
+    /// let mut buf = [0u8; 64];
+    /// let ans = BASE32HEX_NO_PAD.decode_wrapped(b"D1IMOR3F\n41RMUSJC\nCG", buf.as_mut_slice().as_out()).unwrap();
+    /// assert_eq!(ans, b"hello world");
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid after removing whitespace.
+    #[inline]
+    pub fn decode_wrapped<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        let pos = match src.iter().position(u8::is_ascii_whitespace) {
+            Some(pos) => pos,
+            None => return self.decode(src, dst),
+        };
+        ensure!(dst.len() >= src.len());
+
+        unsafe {
+            let len = src.len();
+            let src = src.as_ptr();
+            let dst = dst.as_mut_ptr();
+
+            copy_nonoverlapping(src, dst, pos);
+            let rem = remove_whitespace(src.add(pos), len - pos, dst.add(pos));
+            self.decode_inplace(slice_mut(dst, pos + rem))
+        }
+    }
+}
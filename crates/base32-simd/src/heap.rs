@@ -1,5 +1,4 @@
 use crate::decode::decoded_length;
-use crate::encode::encoded_length_unchecked;
 use crate::{AppendBase32Decode, AppendBase32Encode, Base32, Error, FromBase32Decode, FromBase32Encode};
 
 use vsimd::tools::{alloc_uninit_bytes, assume_init, boxed_str, slice_parts};
@@ -15,7 +14,7 @@ fn decode_to_boxed_bytes(base32: &Base32, src: &[u8]) -> Result<Box<[u8]>, Error
     }
 
     unsafe {
-        let (n, m) = decoded_length(src, base32.padding)?;
+        let (n, m) = decoded_length(src, base32.pad())?;
 
         // safety: 0 < m < isize::MAX
         let mut buf = alloc_uninit_bytes(m);
@@ -36,7 +35,7 @@ fn decode_append_vec(base32: &Base32, src: &[u8], buf: &mut Vec<u8>) -> Result<(
         return Ok(());
     }
 
-    let (n, m) = decoded_length(src, base32.padding)?;
+    let (n, m) = decoded_length(src, base32.pad())?;
 
     buf.reserve_exact(m);
     let prev_len = buf.len();
@@ -58,15 +57,14 @@ fn encode_to_boxed_str(base32: &Base32, src: &[u8]) -> Box<str> {
     }
 
     unsafe {
-        let m = encoded_length_unchecked(src.len(), base32.padding);
-        assert!(m <= usize::MAX / 2);
+        let m = base32.encoded_length(src.len());
 
         let mut buf = alloc_uninit_bytes(m);
 
         {
             let (src, len) = slice_parts(src);
             let dst: *mut u8 = buf.as_mut_ptr().cast();
-            crate::multiversion::encode::auto(src, len, dst, base32.kind, base32.padding);
+            crate::multiversion::encode::auto(src, len, dst, base32.kind, base32.pad());
         }
 
         boxed_str(assume_init(buf))
@@ -79,8 +77,7 @@ fn encode_append_vec(base32: &Base32, src: &[u8], buf: &mut Vec<u8>) {
         return;
     }
 
-    let m = encoded_length_unchecked(src.len(), base32.padding);
-    assert!(m <= usize::MAX / 2);
+    let m = base32.encoded_length(src.len());
 
     buf.reserve_exact(m);
     let prev_len = buf.len();
@@ -88,7 +85,7 @@ fn encode_append_vec(base32: &Base32, src: &[u8], buf: &mut Vec<u8>) {
     unsafe {
         let (src, len) = slice_parts(src);
         let dst = buf.as_mut_ptr().add(prev_len);
-        crate::multiversion::encode::auto(src, len, dst, base32.kind, base32.padding);
+        crate::multiversion::encode::auto(src, len, dst, base32.kind, base32.pad());
 
         buf.set_len(prev_len + m);
     }
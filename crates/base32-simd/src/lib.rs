@@ -52,21 +52,35 @@ mod error;
 pub use self::error::Error;
 
 mod alsw;
+mod bits;
 mod check;
 mod decode;
 mod encode;
+mod forgiving;
+mod grouped;
+mod transcode;
+
+#[cfg(feature = "alloc")]
+pub use self::forgiving::decode_totp_secret;
 
 mod multiversion;
 
 #[cfg(feature = "alloc")]
 mod heap;
 
+pub mod crockford;
+pub mod nix;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+pub mod multibase;
+
 pub use outref::{AsOut, Out};
 
 // -----------------------------------------------------------------------------
 
-use crate::decode::decoded_length;
-use crate::encode::encoded_length_unchecked;
+use crate::decode::{decoded_length, BASE32HEX_TABLE, BASE32_TABLE};
+use crate::encode::{checked_encoded_length, encoded_length_unchecked};
 
 use vsimd::tools::{slice_mut, slice_parts};
 
@@ -86,6 +100,7 @@ const fn u16x4_to_u64(x: [u16; 4]) -> u64 {
 pub struct Base32 {
     kind: Kind,
     padding: bool,
+    pad_char: u8,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -98,27 +113,79 @@ pub(crate) enum Kind {
 pub const BASE32: Base32 = Base32 {
     kind: Kind::Base32,
     padding: true,
+    pad_char: b'=',
 };
 
 /// `Base32Hex` charset with padding.
 pub const BASE32HEX: Base32 = Base32 {
     kind: Kind::Base32Hex,
     padding: true,
+    pad_char: b'=',
 };
 
 /// `Base32` charset withnot padding.
 pub const BASE32_NO_PAD: Base32 = Base32 {
     kind: Kind::Base32,
     padding: false,
+    pad_char: b'=',
 };
 
 /// `Base32Hex` charset withnot padding.
 pub const BASE32HEX_NO_PAD: Base32 = Base32 {
     kind: Kind::Base32Hex,
     padding: false,
+    pad_char: b'=',
 };
 
 impl Base32 {
+    /// Returns a variant of `self` with or without padding.
+    ///
+    /// A padded base32 string has a length which is a multiple of 8.
+    /// An unpadded base32 string has a length whose remainder modulo 8 is 0, 2, 4, 5 or 7.
+    #[inline]
+    #[must_use]
+    pub const fn with_padding(self, padding: bool) -> Self {
+        Self { padding, ..self }
+    }
+
+    /// Returns a variant of `self` with a custom padding character.
+    ///
+    /// The padding character is only used when padding is enabled.
+    ///
+    /// # Panics
+    /// This function panics if `pad_char` is not an ASCII character or it is in the character set.
+    #[inline]
+    #[must_use]
+    pub const fn with_pad_char(self, pad_char: u8) -> Self {
+        assert!(pad_char.is_ascii());
+        let charset = match self.kind {
+            Kind::Base32 => BASE32_CHARSET,
+            Kind::Base32Hex => BASE32HEX_CHARSET,
+        };
+        let mut i = 0;
+        while i < charset.len() {
+            assert!(charset[i] != pad_char);
+            i += 1;
+        }
+        Self { pad_char, ..self }
+    }
+
+    /// Returns the padding character, or `None` if padding is disabled.
+    #[inline]
+    #[must_use]
+    pub const fn pad_char(&self) -> Option<u8> {
+        self.pad()
+    }
+
+    #[inline(always)]
+    const fn pad(&self) -> Option<u8> {
+        if self.padding {
+            Some(self.pad_char)
+        } else {
+            None
+        }
+    }
+
     /// Returns the character set.
     #[inline]
     #[must_use]
@@ -129,15 +196,71 @@ impl Base32 {
         }
     }
 
+    /// Returns the value of `c` in the character set, or `None` if `c` is not in it.
+    ///
+    /// The value is read from the table used by the decoder, so it classifies the bytes
+    /// exactly as decoding does. The padding character is not in the character set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base32_simd::{BASE32, BASE32HEX};
+    ///
+    /// assert_eq!(BASE32.char_value(b'C'), Some(2));
+    /// assert_eq!(BASE32HEX.char_value(b'C'), Some(12));
+    /// assert_eq!(BASE32.char_value(b'c'), None);
+    /// assert_eq!(BASE32.char_value(b'='), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn char_value(&self, c: u8) -> Option<u8> {
+        let table = match self.kind {
+            Kind::Base32 => BASE32_TABLE,
+            Kind::Base32Hex => BASE32HEX_TABLE,
+        };
+        let value = table[c as usize];
+        if value == 0xff {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Returns `true` if `c` is in the character set.
+    ///
+    /// See [`char_value`](Self::char_value).
+    #[inline]
+    #[must_use]
+    pub const fn contains_char(&self, c: u8) -> bool {
+        self.char_value(c).is_some()
+    }
+
     /// Calculates the encoded length.
     ///
+    /// Every group of 5 bytes is encoded to 8 characters.
+    /// The last partial group of 1, 2, 3 or 4 bytes is encoded to 2, 4, 5 or 7 characters,
+    /// which are padded to 8 characters if padding is enabled.
+    ///
     /// # Panics
-    /// This function asserts that `n <= usize::MAX / 2`.
+    /// This function asserts that the encoded length is not greater than `isize::MAX`.
+    /// On 32-bit targets, the limit is reached by an input of about 1.25 GiB.
     #[inline]
     #[must_use]
     pub const fn encoded_length(&self, n: usize) -> usize {
-        assert!(n <= usize::MAX / 2);
-        encoded_length_unchecked(n, self.padding)
+        match checked_encoded_length(n, self.padding) {
+            Some(m) => m,
+            None => panic!("encoded length overflow"),
+        }
+    }
+
+    /// Calculates the encoded length.
+    ///
+    /// Returns `None` if the encoded length is greater than `isize::MAX`,
+    /// where [`encoded_length`](Self::encoded_length) would panic.
+    #[inline]
+    #[must_use]
+    pub const fn checked_encoded_length(&self, n: usize) -> Option<usize> {
+        checked_encoded_length(n, self.padding)
     }
 
     /// Estimates the decoded length.
@@ -161,17 +284,20 @@ impl Base32 {
     /// This function returns `Err` if the content of `data` is partially invalid.
     #[inline]
     pub fn decoded_length(&self, data: &[u8]) -> Result<usize, Error> {
-        let (_, m) = decoded_length(data, self.padding)?;
+        let (_, m) = decoded_length(data, self.pad())?;
         Ok(m)
     }
 
     /// Checks whether `data` is a base32 string.
     ///
+    /// Nothing is written, so the input can be validated before allocating a buffer for it.
+    /// This function returns `Ok` if and only if [`decode`](Self::decode) succeeds with a large enough buffer.
+    ///
     /// # Errors
     /// This function returns `Err` if the content of `data` is invalid.
     #[inline]
     pub fn check(&self, data: &[u8]) -> Result<(), Error> {
-        let (n, _) = decoded_length(data, self.padding)?;
+        let (n, _) = decoded_length(data, self.pad())?;
         let src = data.as_ptr();
         unsafe { crate::multiversion::check::auto(src, n, self.kind) }
     }
@@ -188,7 +314,7 @@ impl Base32 {
 
             let (src, len) = slice_parts(src);
             let dst = dst.as_mut_ptr();
-            self::multiversion::encode::auto(src, len, dst, self.kind, self.padding);
+            self::multiversion::encode::auto(src, len, dst, self.kind, self.pad());
 
             Ok(slice_mut(dst, m))
         }
@@ -206,6 +332,9 @@ impl Base32 {
 
     /// Decodes a base32 string to bytes.
     ///
+    /// The input is validated and decoded in a single pass.
+    /// It is not necessary to call [`check`](Self::check) before.
+    ///
     /// # Errors
     /// This function returns `Err` if
     /// + the length of `dst` is not enough.
@@ -213,7 +342,7 @@ impl Base32 {
     #[inline]
     pub fn decode<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
         unsafe {
-            let (n, m) = decoded_length(src, self.padding)?;
+            let (n, m) = decoded_length(src, self.pad())?;
             ensure!(dst.len() >= m);
 
             let src = src.as_ptr();
@@ -231,7 +360,7 @@ impl Base32 {
     #[inline]
     pub fn decode_inplace<'d>(&'_ self, data: &'d mut [u8]) -> Result<&'d mut [u8], Error> {
         unsafe {
-            let (n, m) = decoded_length(data, self.padding)?;
+            let (n, m) = decoded_length(data, self.pad())?;
 
             let dst: *mut u8 = data.as_mut_ptr();
             let src: *const u8 = dst;
@@ -241,6 +370,120 @@ impl Base32 {
         }
     }
 
+    /// Converts a base32 string from the charset of `from` to the charset of `to`.
+    ///
+    /// `Base32` and `Base32Hex` share the bit grouping, so every character is substituted
+    /// without decoding the string to bytes.
+    /// The padding of the output follows `to`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base32_simd::{AsOut, Base32, BASE32, BASE32HEX_NO_PAD};
+    ///
+    /// let mut buf = [0u8; 16];
+    /// let ans = Base32::transcode(b"NBSWY3DP", &BASE32, &BASE32HEX_NO_PAD, buf.as_mut_slice().as_out()).unwrap();
+    /// assert_eq!(ans, b"D1IMOR3F");
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the content of `src` is invalid for `from`.
+    /// + the length of `dst` is not enough.
+    #[inline]
+    pub fn transcode<'d>(
+        src: &[u8],
+        from: &Base32,
+        to: &Base32,
+        mut dst: Out<'d, [u8]>,
+    ) -> Result<&'d mut [u8], Error> {
+        from.check(src)?;
+        let (n, m) = decoded_length(src, from.pad())?;
+
+        let out_len = encoded_length_unchecked(m, to.padding);
+        ensure!(dst.len() >= out_len);
+
+        unsafe {
+            let src = src.as_ptr();
+            let dst = dst.as_mut_ptr();
+            match (from.kind, to.kind) {
+                (Kind::Base32, Kind::Base32) | (Kind::Base32Hex, Kind::Base32Hex) => {
+                    core::ptr::copy_nonoverlapping(src, dst, n);
+                }
+                _ => self::multiversion::transcode::auto(src, n, dst, from.kind),
+            }
+            core::ptr::write_bytes(dst.add(n), to.pad_char, out_len - n);
+            Ok(slice_mut(dst, out_len))
+        }
+    }
+
+    /// Decodes a base32 string to a byte array of length `N`.
+    ///
+    /// This is useful for fixed-size data, such as a 20-byte TOTP key encoded in 32 characters.
+    /// The input is fully validated, including padding and the unused bits of the last character.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the decoded length is not `N`.
+    /// + the content of `src` is invalid.
+    #[inline]
+    pub fn decode_exact<const N: usize>(&self, src: &[u8]) -> Result<[u8; N], Error> {
+        let (n, m) = decoded_length(src, self.pad())?;
+        ensure!(m == N);
+
+        let mut buf = [0u8; N];
+        unsafe { crate::multiversion::decode::auto(src.as_ptr(), n, buf.as_mut_ptr(), self.kind)? };
+        Ok(buf)
+    }
+
+    /// Encodes the first `nbits` bits of `src` to a base32 string.
+    ///
+    /// Bits are taken from the most significant bit of each byte.
+    /// The output contains `nbits.div_ceil(5)` characters and never contains padding.
+    /// The unused low bits of the last character are set to zero.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + `nbits` is greater than `src.len() * 8`.
+    /// + the length of `dst` is not enough.
+    #[inline]
+    pub fn encode_bits<'d>(&self, src: &[u8], nbits: usize, mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        ensure!(nbits <= src.len().saturating_mul(8));
+        let m = nbits / 5 + (nbits % 5 != 0) as usize;
+        ensure!(dst.len() >= m);
+        unsafe {
+            let dst = dst.as_mut_ptr();
+            crate::bits::encode_bits(src.as_ptr(), nbits, dst, self.kind);
+            Ok(slice_mut(dst, m))
+        }
+    }
+
+    /// Decodes a base32 string to a bit string.
+    ///
+    /// Every character contributes 5 bits, so the result has `5 * src.len()` bits,
+    /// stored from the most significant bit of each byte.
+    /// The output contains `(5 * src.len()).div_ceil(8)` bytes.
+    /// The unused low bits of the last byte are set to zero.
+    ///
+    /// Padding characters are not allowed.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid.
+    #[inline]
+    pub fn decode_bits<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        ensure!(src.len() <= usize::MAX / 5);
+        let nbits = src.len() * 5;
+        let m = nbits / 8 + (nbits % 8 != 0) as usize;
+        ensure!(dst.len() >= m);
+        unsafe {
+            let dst = dst.as_mut_ptr();
+            crate::bits::decode_bits(src.as_ptr(), src.len(), dst, self.kind)?;
+            Ok(slice_mut(dst, m))
+        }
+    }
+
     /// Encodes bytes to a base32 string and returns a specified type.
     #[inline]
     #[must_use]
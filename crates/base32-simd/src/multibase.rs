@@ -0,0 +1,170 @@
+//! Multibase strings with base32 payloads.
+//!
+//! A multibase string starts with a single character which identifies its base.
+//! The base32 variants are
+//!
+//! | prefix | name                | charset     | case  | padding |
+//! | ------ | ------------------- | ----------- | ----- | ------- |
+//! | `b`    | `base32`            | `Base32`    | lower | no      |
+//! | `B`    | `base32upper`       | `Base32`    | upper | no      |
+//! | `c`    | `base32pad`         | `Base32`    | lower | yes     |
+//! | `C`    | `base32padupper`    | `Base32`    | upper | yes     |
+//! | `v`    | `base32hex`         | `Base32Hex` | lower | no      |
+//! | `V`    | `base32hexupper`    | `Base32Hex` | upper | no      |
+//! | `t`    | `base32hexpad`      | `Base32Hex` | lower | yes     |
+//! | `T`    | `base32hexpadupper` | `Base32Hex` | upper | yes     |
+//!
+//! The payload must be in the case of its prefix.
+//! The lowercase payloads are converted from or to uppercase around the SIMD kernels.
+//!
+//! See <https://github.com/multiformats/multibase>
+//!
+//! # Examples
+//!
+//! ```
+//! use base32_simd::multibase::{self, Variant};
+//!
+//! let s = multibase::encode(Variant::Base32, b"hello");
+//! assert_eq!(s, "bnbswy3dp");
+//!
+//! let (variant, data) = multibase::decode(&s).unwrap();
+//! assert_eq!(variant, Variant::Base32);
+//! assert_eq!(&*data, b"hello");
+//! ```
+
+use crate::{Base32, Error, BASE32, BASE32HEX, BASE32HEX_NO_PAD, BASE32_NO_PAD};
+
+use alloc::boxed::Box;
+use alloc::string::String;
+
+/// The base32 variants of multibase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variant {
+    /// `b`: `Base32` charset in lowercase without padding.
+    Base32,
+    /// `B`: `Base32` charset in uppercase without padding.
+    Base32Upper,
+    /// `c`: `Base32` charset in lowercase with padding.
+    Base32Pad,
+    /// `C`: `Base32` charset in uppercase with padding.
+    Base32PadUpper,
+    /// `v`: `Base32Hex` charset in lowercase without padding.
+    Base32Hex,
+    /// `V`: `Base32Hex` charset in uppercase without padding.
+    Base32HexUpper,
+    /// `t`: `Base32Hex` charset in lowercase with padding.
+    Base32HexPad,
+    /// `T`: `Base32Hex` charset in uppercase with padding.
+    Base32HexPadUpper,
+}
+
+impl Variant {
+    /// Returns the prefix character of the variant.
+    #[inline]
+    #[must_use]
+    pub const fn prefix(self) -> char {
+        match self {
+            Self::Base32 => 'b',
+            Self::Base32Upper => 'B',
+            Self::Base32Pad => 'c',
+            Self::Base32PadUpper => 'C',
+            Self::Base32Hex => 'v',
+            Self::Base32HexUpper => 'V',
+            Self::Base32HexPad => 't',
+            Self::Base32HexPadUpper => 'T',
+        }
+    }
+
+    /// Returns the variant identified by a prefix character.
+    ///
+    /// Returns `None` if `prefix` does not identify a base32 variant.
+    #[inline]
+    #[must_use]
+    pub const fn from_prefix(prefix: u8) -> Option<Self> {
+        match prefix {
+            b'b' => Some(Self::Base32),
+            b'B' => Some(Self::Base32Upper),
+            b'c' => Some(Self::Base32Pad),
+            b'C' => Some(Self::Base32PadUpper),
+            b'v' => Some(Self::Base32Hex),
+            b'V' => Some(Self::Base32HexUpper),
+            b't' => Some(Self::Base32HexPad),
+            b'T' => Some(Self::Base32HexPadUpper),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the payload of the variant is in lowercase.
+    #[inline]
+    #[must_use]
+    pub const fn is_lowercase(self) -> bool {
+        (self.prefix() as u8).is_ascii_lowercase()
+    }
+
+    /// Returns the base32 variant used by the payload, in uppercase.
+    #[inline]
+    #[must_use]
+    pub const fn base32(self) -> Base32 {
+        match self {
+            Self::Base32 | Self::Base32Upper => BASE32_NO_PAD,
+            Self::Base32Pad | Self::Base32PadUpper => BASE32,
+            Self::Base32Hex | Self::Base32HexUpper => BASE32HEX_NO_PAD,
+            Self::Base32HexPad | Self::Base32HexPadUpper => BASE32HEX,
+        }
+    }
+}
+
+/// Encodes `data` to a multibase string of `variant`.
+///
+/// The allocation is sized exactly for the prefix and the encoded payload.
+#[inline]
+#[must_use]
+pub fn encode(variant: Variant, data: &[u8]) -> String {
+    let base32 = variant.base32();
+    let len = 1 + base32.encoded_length(data.len());
+    let mut s = String::with_capacity(len);
+    s.push(variant.prefix());
+    base32.encode_append(data, &mut s);
+    if variant.is_lowercase() {
+        // The prefix is already lowercase.
+        s.make_ascii_lowercase();
+    }
+    debug_assert_eq!(s.len(), len);
+    s
+}
+
+/// Decodes a multibase string with a base32 payload.
+///
+/// Returns the variant identified by the prefix and the decoded payload.
+/// The payload must match the case and the padding of the variant exactly.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + `s` is empty, or its prefix does not identify a base32 variant.
+///   The error reports the index `0`.
+/// + the payload of a lowercase variant contains an uppercase letter.
+///   The error reports its index in `s`.
+/// + the payload is invalid.
+#[inline]
+pub fn decode(s: &str) -> Result<(Variant, Box<[u8]>), Error> {
+    let s = s.as_bytes();
+    let variant = match s.first().copied().and_then(Variant::from_prefix) {
+        Some(variant) => variant,
+        None => return Err(Error::at(0)),
+    };
+    let payload = &s[1..];
+    let base32 = variant.base32();
+
+    if !variant.is_lowercase() {
+        let data = base32.decode_type::<Box<[u8]>>(payload)?;
+        return Ok((variant, data));
+    }
+
+    if let Some(pos) = payload.iter().position(u8::is_ascii_uppercase) {
+        return Err(Error::at(1 + pos));
+    }
+    let mut buf = payload.to_ascii_uppercase();
+    let m = base32.decode_inplace(&mut buf)?.len();
+    buf.truncate(m);
+    Ok((variant, buf.into_boxed_slice()))
+}
@@ -20,9 +20,27 @@ vsimd::dispatch!(
 
 vsimd::dispatch!(
     name        = {encode},
-    signature   = {pub(crate) unsafe fn(src: *const u8, len: usize, dst: *mut u8, kind: Kind, padding: bool) -> ()},
+    signature   = {pub(crate) unsafe fn(src: *const u8, len: usize, dst: *mut u8, kind: Kind, pad: Option<u8>) -> ()},
     fallback    = {crate::encode::encode_fallback},
     simd        = {crate::encode::encode_simd},
     targets     = {"avx2", "sse4.1", "neon", "simd128"},
     fastest     = {"avx2", "neon", "simd128"},
 );
+
+vsimd::dispatch!(
+    name        = {transcode},
+    signature   = {pub(crate) unsafe fn(src: *const u8, len: usize, dst: *mut u8, from: Kind) -> ()},
+    fallback    = {crate::transcode::transcode_fallback},
+    simd        = {crate::transcode::transcode_simd},
+    targets     = {"avx2", "sse2", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+);
+
+vsimd::dispatch!(
+    name        = {crockford_remove_hyphens},
+    signature   = {pub(crate) unsafe fn(data: *mut u8, len: usize) -> Result<usize, Error>},
+    fallback    = {crate::crockford::remove_hyphens_fallback},
+    simd        = {crate::crockford::remove_hyphens_simd},
+    targets     = {"avx2", "ssse3", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+);
@@ -0,0 +1,186 @@
+//! Nix base32
+//!
+//! The encoding used by the Nix package manager for store paths and hashes.
+//!
+//! It differs from RFC 4648 base32 in three ways:
+//! + the character set is `0123456789abcdfghijklmnpqrsvwxyz`, which omits `e`, `o`, `t` and `u`.
+//! + the bits are packed from the least significant bit of the last byte,
+//!   so the first character encodes the most significant bits of the whole input.
+//! + there is no padding. A string of `n` bytes is encoded to `ceil(8n / 5)` characters.
+//!
+//! The whole input is one group, so this encoding does not fit the 5-byte quanta
+//! of [`Base32`](crate::Base32) and is implemented with scalar code.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use base32_simd::nix;
+//!
+//! let hash = b"\x01";
+//! let encoded = nix::encode_to_string(hash);
+//! assert_eq!(encoded, "01");
+//! assert_eq!(nix::decode_to_vec(encoded).unwrap(), hash);
+//! # }
+//! ```
+
+use crate::decode::decoding_table;
+use crate::Error;
+
+use vsimd::tools::{read, slice_mut, write};
+
+use outref::Out;
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+
+/// The Nix base32 character set.
+pub const CHARSET: &[u8; 32] = b"0123456789abcdfghijklmnpqrsvwxyz";
+
+const TABLE: &[u8; 256] = &decoding_table(CHARSET);
+
+/// Calculates the encoded length.
+///
+/// # Panics
+/// This function asserts that `n <= usize::MAX / 8`.
+#[inline]
+#[must_use]
+pub const fn encoded_length(n: usize) -> usize {
+    assert!(n <= usize::MAX / 8);
+    (n * 8 + 4) / 5
+}
+
+/// Calculates the decoded length of a Nix base32 string with `n` characters.
+///
+/// # Errors
+/// This function returns `Err` if `n` is not the encoded length of any byte string.
+#[inline]
+pub fn decoded_length(n: usize) -> Result<usize, Error> {
+    ensure!(n <= usize::MAX / 5);
+    let m = n * 5 / 8;
+    ensure!(encoded_length(m) == n);
+    Ok(m)
+}
+
+/// Encodes bytes to a Nix base32 string.
+///
+/// # Errors
+/// This function returns `Err` if the length of `dst` is not enough.
+#[inline]
+pub fn encode<'d>(src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+    ensure!(src.len() <= usize::MAX / 8);
+    let len = encoded_length(src.len());
+    ensure!(dst.len() >= len);
+    unsafe {
+        let dst = dst.as_mut_ptr();
+        encode_raw(src, len, dst);
+        Ok(slice_mut(dst, len))
+    }
+}
+
+/// Encodes bytes to a Nix base32 string and returns [`&mut str`](str).
+///
+/// # Errors
+/// This function returns `Err` if the length of `dst` is not enough.
+#[inline]
+pub fn encode_as_str<'d>(src: &[u8], dst: Out<'d, [u8]>) -> Result<&'d mut str, Error> {
+    let ans = encode(src, dst)?;
+    Ok(unsafe { core::str::from_utf8_unchecked_mut(ans) })
+}
+
+/// Decodes a Nix base32 string to bytes.
+///
+/// Non-canonical strings are rejected:
+/// the unused high bits of the first character must be zero.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + the length of `dst` is not enough.
+/// + the content of `src` is invalid.
+#[inline]
+pub fn decode<'d>(src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+    let m = decoded_length(src.len())?;
+    ensure!(dst.len() >= m);
+    unsafe {
+        let dst = dst.as_mut_ptr();
+        decode_raw(src, m, dst)?;
+        Ok(slice_mut(dst, m))
+    }
+}
+
+/// Encodes bytes to a Nix base32 string.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn encode_to_string(data: impl AsRef<[u8]>) -> String {
+    let src = data.as_ref();
+    let mut buf = vec![0; encoded_length(src.len())];
+    unsafe {
+        encode_raw(src, buf.len(), buf.as_mut_ptr());
+        String::from_utf8_unchecked(buf)
+    }
+}
+
+/// Decodes a Nix base32 string to bytes.
+///
+/// # Errors
+/// This function returns `Err` if the content of `data` is invalid.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn decode_to_vec(data: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
+    let src = data.as_ref();
+    let mut buf = vec![0; decoded_length(src.len())?];
+    unsafe { decode_raw(src, buf.len(), buf.as_mut_ptr())? };
+    Ok(buf)
+}
+
+/// Writes `len` characters. The `k`-th character from the end encodes bits `5k..5k+5`,
+/// counted from the least significant bit of the first byte.
+#[inline(always)]
+unsafe fn encode_raw(src: &[u8], len: usize, dst: *mut u8) {
+    let src_len = src.len();
+    let src = src.as_ptr();
+
+    let mut k = 0;
+    while k < len {
+        let (i, j) = (k * 5 / 8, k * 5 % 8);
+        let mut x = u16::from(read(src, i));
+        if i + 1 < src_len {
+            x |= u16::from(read(src, i + 1)) << 8;
+        }
+        let bits = (x >> j) & 0x1f;
+        write(dst, len - 1 - k, read(CHARSET.as_ptr(), bits as usize));
+        k += 1;
+    }
+}
+
+/// Writes `dst_len` bytes decoded from `src`, which has `encoded_length(dst_len)` characters.
+#[inline(always)]
+unsafe fn decode_raw(src: &[u8], dst_len: usize, dst: *mut u8) -> Result<(), Error> {
+    let len = src.len();
+    let src = src.as_ptr();
+
+    core::ptr::write_bytes(dst, 0, dst_len);
+
+    let mut k = 0;
+    while k < len {
+        let bits = read(TABLE.as_ptr(), read(src, len - 1 - k) as usize);
+        ensure!(bits != 0xff);
+
+        let (i, j) = (k * 5 / 8, k * 5 % 8);
+        let x = u16::from(bits) << j;
+        write(dst, i, read(dst, i) | (x as u8));
+
+        let carry = (x >> 8) as u8;
+        if i + 1 < dst_len {
+            write(dst, i + 1, read(dst, i + 1) | carry);
+        } else {
+            ensure!(carry == 0);
+        }
+        k += 1;
+    }
+    Ok(())
+}
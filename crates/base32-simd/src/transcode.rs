@@ -0,0 +1,81 @@
+use crate::Kind;
+
+use vsimd::isa::AVX2;
+use vsimd::tools::{read, write};
+use vsimd::{matches_isa, Scalable, POD, SIMD256};
+
+// Base32 to Base32Hex:
+// 'A'..='J' -> '0'..='9': -17
+// 'K'..='Z' -> 'A'..='P': -10
+// '2'..='7' -> 'Q'..='V': +31
+//
+// Base32Hex to Base32:
+// '0'..='9' -> 'A'..='J': +17
+// 'A'..='P' -> 'K'..='Z': +10
+// 'Q'..='V' -> '2'..='7': -31
+
+/// Returns `(c1, c2, d0, d1, d2)`.
+///
+/// The delta of `x` is `d0`, plus `d1` if `x < c1`, plus `d2` if `x < c2`.
+#[inline(always)]
+const fn deltas(from: Kind) -> (u8, u8, u8, u8, u8) {
+    match from {
+        Kind::Base32 => (b'K', b'A', (-10i8) as u8, (-7i8) as u8, 48),
+        Kind::Base32Hex => (b'Q', b'A', (-31i8) as u8, 41, 7),
+    }
+}
+
+#[inline(always)]
+fn remap<S: Scalable<V>, V: POD>(s: S, x: V, from: Kind) -> V {
+    let (c1, c2, d0, d1, d2) = deltas(from);
+    let lt1 = s.i8xn_lt(x, s.u8xn_splat(c1));
+    let lt2 = s.i8xn_lt(x, s.u8xn_splat(c2));
+    let delta = s.u8xn_add(s.and(lt1, s.u8xn_splat(d1)), s.and(lt2, s.u8xn_splat(d2)));
+    s.u8xn_add(x, s.u8xn_add(delta, s.u8xn_splat(d0)))
+}
+
+/// Maps every character of the `from` charset to the same value in the other charset.
+///
+/// The characters must be valid for `from`.
+#[inline(always)]
+pub unsafe fn transcode_fallback(src: *const u8, len: usize, dst: *mut u8, from: Kind) {
+    let (c1, c2, d0, d1, d2) = deltas(from);
+    let mut i = 0;
+    while i < len {
+        let x = read(src, i);
+        let mut delta = d0;
+        if x < c1 {
+            delta = delta.wrapping_add(d1);
+        }
+        if x < c2 {
+            delta = delta.wrapping_add(d2);
+        }
+        write(dst, i, x.wrapping_add(delta));
+        i += 1;
+    }
+}
+
+#[inline(always)]
+pub unsafe fn transcode_simd<S: SIMD256>(s: S, mut src: *const u8, mut len: usize, mut dst: *mut u8, from: Kind) {
+    if matches_isa!(S, AVX2) {
+        while len >= 32 {
+            let x = s.v256_load_unaligned(src);
+            let y = remap(s, x, from);
+            s.v256_store_unaligned(dst, y);
+            src = src.add(32);
+            dst = dst.add(32);
+            len -= 32;
+        }
+    }
+
+    while len >= 16 {
+        let x = s.v128_load_unaligned(src);
+        let y = remap(s, x, from);
+        s.v128_store_unaligned(dst, y);
+        src = src.add(16);
+        dst = dst.add(16);
+        len -= 16;
+    }
+
+    transcode_fallback(src, len, dst, from);
+}
@@ -107,6 +107,18 @@ fn allocation() {
     BASE32.decode_append(src, &mut decode_buf).unwrap();
 
     assert_eq!(decode_buf, b"123helloworld");
+
+    let encoded: Box<str> = BASE32.encode_type("helloworld");
+    assert_eq!(&*encoded, "NBSWY3DPO5XXE3DE");
+    let encoded: Box<[u8]> = BASE32.encode_type("helloworld");
+    assert_eq!(&*encoded, b"NBSWY3DPO5XXE3DE");
+
+    let decoded: Box<[u8]> = BASE32.decode_type(&*encoded).unwrap();
+    assert_eq!(&*decoded, b"helloworld");
+
+    let mut inplace = encoded.into_vec();
+    let decoded = BASE32.decode_inplace(&mut inplace).unwrap();
+    assert_eq!(decoded, b"helloworld");
 }
 
 #[cfg_attr(not(target_arch = "wasm32"), test)]
@@ -141,3 +153,692 @@ fn random() {
         }
     }
 }
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn bits() {
+    // geohash-like: 25 bits -> 5 chars
+    let src = [0b1010_1010, 0b0101_0101, 0b1111_0000, 0b1000_0000];
+    let mut buf = [0u8; 16];
+    let ans = BASE32.encode_bits(&src, 25, buf.as_mut_slice().as_out()).unwrap();
+    assert_eq!(ans, b"VJK7B");
+
+    let mut out = [0u8; 16];
+    let ans = BASE32.decode_bits(b"VJK7B", out.as_mut_slice().as_out()).unwrap();
+    assert_eq!(ans, &src);
+
+    // unused bits are cleared
+    let ans = BASE32.encode_bits(&[0xff], 3, buf.as_mut_slice().as_out()).unwrap();
+    assert_eq!(ans, b"4");
+
+    assert!(BASE32.encode_bits(&[0xff], 9, buf.as_mut_slice().as_out()).is_err());
+
+    // the last character does not read past the end of `src`
+    let src = vec![0xffu8];
+    for (nbits, expected) in [(6, "7Q"), (7, "7Y"), (8, "74")] {
+        let ans = BASE32.encode_bits(&src, nbits, buf.as_mut_slice().as_out()).unwrap();
+        assert_eq!(ans, expected.as_bytes());
+    }
+    assert!(BASE32.decode_bits(b"MY==", out.as_mut_slice().as_out()).is_err());
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for base32 in [BASE32, BASE32HEX] {
+            // byte-aligned multiples of 5 bytes agree with the regular codec
+            if n % 5 == 0 {
+                let mut encoded = vec![0u8; n / 5 * 8];
+                let mut expected = vec![0u8; base32.encoded_length(n)];
+                assert_eq!(
+                    base32.encode_bits(&bytes, n * 8, encoded.as_out()).unwrap(),
+                    base32.encode(&bytes, expected.as_out()).unwrap()
+                );
+            }
+
+            for nbits in 0..=n * 8 {
+                let mut encoded = vec![0u8; (nbits + 4) / 5];
+                let encoded = base32.encode_bits(&bytes, nbits, encoded.as_out()).unwrap();
+
+                let mut decoded = vec![0u8; (encoded.len() * 5 + 7) / 8];
+                let decoded = base32.decode_bits(encoded, decoded.as_out()).unwrap();
+
+                let full = nbits / 8;
+                assert_eq!(decoded[..full], bytes[..full]);
+                if nbits % 8 != 0 {
+                    let mask = 0xffu8 << (8 - nbits % 8);
+                    assert_eq!(decoded[full] & mask, bytes[full] & mask);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn custom_padding() {
+    let base32 = BASE32.with_pad_char(b'*');
+    assert_eq!(base32.pad_char(), Some(b'*'));
+    assert_eq!(base32.encode_to_string("f"), "MY******");
+    assert_eq!(base32.encode_to_string("foob"), "MZXW6YQ*");
+    assert_eq!(base32.decode_to_vec("MZXW6YQ*").unwrap(), b"foob");
+    assert!(base32.check(b"MZXW6YQ*").is_ok());
+    assert!(base32.decode_to_vec("MZXW6YQ=").is_err());
+
+    let no_pad = BASE32.with_pad_char(b'*').with_padding(false);
+    assert_eq!(no_pad.pad_char(), None);
+    assert_eq!(no_pad.encode_to_string("foob"), "MZXW6YQ");
+    assert_eq!(BASE32_NO_PAD.with_padding(true).encode_to_string("foob"), "MZXW6YQ=");
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for (base32, no_pad) in [(BASE32, BASE32_NO_PAD), (BASE32HEX, BASE32HEX_NO_PAD)] {
+            let base32 = base32.with_padding(false);
+            let encoded = base32.encode_to_string(&bytes);
+            assert_eq!(encoded, no_pad.encode_to_string(&bytes));
+            assert!(matches!(encoded.len() % 8, 0 | 2 | 4 | 5 | 7));
+            assert_eq!(base32.decode_to_vec(&encoded).unwrap(), bytes);
+
+            let base32 = base32.with_padding(true).with_pad_char(b'.');
+            let encoded = base32.encode_to_string(&bytes);
+            assert_eq!(encoded.len() % 8, 0);
+            assert_eq!(base32.decode_to_vec(&encoded).unwrap(), bytes);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn custom_padding_in_charset() {
+    let _ = BASE32.with_pad_char(b'A');
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn char_value() {
+    for base32 in [BASE32, BASE32HEX, BASE32_NO_PAD, BASE32HEX_NO_PAD] {
+        let charset = base32.charset();
+        for c in 0..=255u8 {
+            let pos = charset.iter().position(|&x| x == c).map(|i| i as u8);
+            assert_eq!(base32.char_value(c), pos);
+            assert_eq!(base32.contains_char(c), pos.is_some());
+
+            // The decoder accepts exactly the same characters.
+            let mut group = [charset[0]; 8];
+            group[0] = c;
+            assert_eq!(base32.check(&group).is_ok(), pos.is_some());
+        }
+    }
+
+    const VALUE: Option<u8> = BASE32HEX.char_value(b'V');
+    assert_eq!(VALUE, Some(31));
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn crockford() {
+    use base32_simd::crockford;
+
+    let cases: &[(&[u8], &str)] = &[
+        (b"", "0"),
+        (b"\x08", "10*"),
+        (b"\x09", "14U"),
+        (b"\x12", "28="),
+        (b"\x1b", "3C$"),
+        (b"\x24", "4G~"),
+        (b"\xff", "ZWN"),
+        (b"\x01\x02\x03", "04106C"),
+        (b"hello", "D1JPRV3FJ"),
+        (b"foobar", "CSQPYRK1E8R"),
+    ];
+    for &(bytes, encoded) in cases {
+        let body = &encoded[..encoded.len() - 1];
+        assert_eq!(crockford::encode_to_string(bytes), body);
+        assert_eq!(crockford::decode_to_vec(body).unwrap(), bytes);
+        assert_eq!(crockford::encode_with_checksum_to_string(bytes), encoded);
+        assert_eq!(crockford::decode_with_checksum_to_vec(encoded).unwrap(), bytes);
+        assert_eq!(
+            crockford::decode_with_checksum_to_vec(encoded.to_ascii_lowercase()).unwrap(),
+            bytes
+        );
+
+        let mut buf = vec![0u8; encoded.len()];
+        assert_eq!(
+            crockford::encode_with_checksum(bytes, buf.as_out()).unwrap(),
+            encoded.as_bytes()
+        );
+        let mut buf = vec![0u8; bytes.len()];
+        assert_eq!(
+            crockford::decode_with_checksum(encoded.as_bytes(), buf.as_out()).unwrap(),
+            bytes
+        );
+    }
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        let encoded = crockford::encode_with_checksum_to_string(&bytes);
+        assert_eq!(encoded.len(), crockford::encoded_length(n) + 1);
+        assert_eq!(crockford::decode_with_checksum_to_vec(&encoded).unwrap(), bytes);
+
+        let mut buf = vec![0u8; n];
+        let body = &encoded.as_bytes()[..encoded.len() - 1];
+        assert_eq!(crockford::decode(body, buf.as_out()).unwrap(), bytes);
+    }
+
+    // aliases of 0 and 1
+    assert_eq!(crockford::decode_to_vec("O4lO6").unwrap(), b"\x01\x02\x03");
+    assert_eq!(crockford::decode_to_vec("o4iO6").unwrap(), b"\x01\x02\x03");
+
+    // wrong check symbols, invalid characters, lengths and non-canonical trailing characters
+    for bad in ["", "D1JPRV3FA", "D1JPRV3F*", "ZW", "0U", "ZU0", "ZX", "000N"] {
+        assert!(crockford::decode_with_checksum_to_vec(bad).is_err(), "{bad}");
+    }
+    for bad in ["U0", "0*", "0", "012", "ZX"] {
+        assert!(crockford::decode_to_vec(bad).is_err(), "{bad}");
+    }
+
+    let mut buf = [0u8; 8];
+    assert!(crockford::encode_with_checksum(b"hello", buf.as_mut_slice().as_out()).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn crockford_decode_inplace() {
+    use base32_simd::crockford;
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        let encoded = crockford::encode_to_string(&bytes);
+
+        let mut data = encoded.clone().into_bytes();
+        assert_eq!(crockford::decode_inplace(&mut data).unwrap(), bytes);
+
+        // a hyphen after every 4 characters, and some leading, trailing and doubled hyphens
+        let mut data: Vec<u8> = b"-".to_vec();
+        for (i, chunk) in encoded.to_ascii_lowercase().as_bytes().chunks(4).enumerate() {
+            if i > 0 {
+                data.extend_from_slice(if i % 3 == 0 { b"--" } else { b"-" });
+            }
+            data.extend_from_slice(chunk);
+        }
+        data.push(b'-');
+        assert_eq!(crockford::decode_inplace(&mut data).unwrap(), bytes);
+    }
+
+    // long inputs with hyphens of every density
+    for n in [40, 100, 300] {
+        let bytes = rand_bytes(n);
+        let encoded = crockford::encode_to_string(&bytes);
+        for step in [1, 2, 3, 7, 8, 9, 16, 17] {
+            let mut data = Vec::new();
+            for (i, &c) in encoded.as_bytes().iter().enumerate() {
+                if i % step == 0 {
+                    data.push(b'-');
+                }
+                data.push(c);
+            }
+            assert_eq!(crockford::decode_inplace(&mut data).unwrap(), bytes);
+        }
+    }
+
+    // aliases in whole blocks
+    let aliases = "OiLoIl0-1".repeat(8);
+    let expected = crockford::decode_to_vec(aliases.replace('-', "")).unwrap();
+    let mut data = aliases.into_bytes();
+    assert_eq!(crockford::decode_inplace(&mut data).unwrap(), expected);
+
+    // invalid characters next to the ranges of the character set
+    let encoded = crockford::encode_to_string(rand_bytes(100));
+    for &c in b"/:@[`{Uu\x00\x7f\x80\xff" {
+        for pos in [0, 15, 16, 17, 40, 159] {
+            let mut data = encoded.clone().into_bytes();
+            data[pos / 5 * 5] = b'-';
+            data[pos] = c;
+            let err = crockford::decode_inplace(&mut data).unwrap_err();
+            assert_eq!(err.index(), Some(pos), "{c:#x} at {pos}");
+        }
+    }
+
+    let mut data = *b"----";
+    assert!(crockford::decode_inplace(&mut data).unwrap().is_empty());
+
+    for (bad, index) in [
+        ("01U-4", Some(2)),
+        ("0-1-*", Some(4)),
+        ("04-1O-6 ", Some(7)),
+        ("0-12", None),
+        ("Z-X", None),
+    ] {
+        let mut data = bad.as_bytes().to_vec();
+        let err = crockford::decode_inplace(&mut data).unwrap_err();
+        assert_eq!(err.index(), index, "{bad}");
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn nix() {
+    use base32_simd::nix;
+
+    let cases: &[(&[u8], &str)] = &[
+        (b"", ""),
+        (b"\x01", "01"),
+        (b"\xff", "7z"),
+        (b"foobar", "3jc5i6yvv6"),
+        // sha256("") and sha256("abc")
+        (
+            &const_str::hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+            "0mdqa9w1p6cmli6976v4wi0sw9r4p5prkj7lzfd1877wk11c9c73",
+        ),
+        (
+            &const_str::hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            "1b8m03r63zqhnjf7l5wnldhh7c134ap5vpj0850ymkq1iyzicy5s",
+        ),
+    ];
+    for &(bytes, encoded) in cases {
+        assert_eq!(nix::encode_to_string(bytes), encoded);
+        assert_eq!(nix::decode_to_vec(encoded).unwrap(), bytes);
+
+        let mut buf = vec![0u8; nix::encoded_length(bytes.len())];
+        assert_eq!(nix::encode_as_str(bytes, buf.as_out()).unwrap(), encoded);
+    }
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        let encoded = nix::encode_to_string(&bytes);
+        assert_eq!(encoded.len(), nix::encoded_length(n));
+        assert_eq!(nix::decoded_length(encoded.len()).unwrap(), n);
+
+        let mut buf = vec![0u8; n];
+        assert_eq!(nix::decode(encoded.as_bytes(), buf.as_out()).unwrap(), bytes);
+    }
+
+    // invalid characters, lengths and non-canonical leading characters
+    for bad in ["0e", "0o", "0t", "0u", "0=", "0", "012", "80", "zz", "0MDQ"] {
+        assert!(nix::decode_to_vec(bad).is_err(), "{bad}");
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn multibase() {
+    use base32_simd::multibase::{self, Variant};
+
+    let variants = [
+        Variant::Base32,
+        Variant::Base32Upper,
+        Variant::Base32Pad,
+        Variant::Base32PadUpper,
+        Variant::Base32Hex,
+        Variant::Base32HexUpper,
+        Variant::Base32HexPad,
+        Variant::Base32HexPadUpper,
+    ];
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for variant in variants {
+            let s = multibase::encode(variant, &bytes);
+            assert_eq!(s.len(), s.capacity());
+            assert_eq!(Variant::from_prefix(s.as_bytes()[0]), Some(variant));
+
+            let payload = variant.base32().encode_to_string(&bytes);
+            if variant.is_lowercase() {
+                assert_eq!(s[1..], payload.to_ascii_lowercase());
+            } else {
+                assert_eq!(s[1..], payload);
+            }
+
+            let (v, data) = multibase::decode(&s).unwrap();
+            assert_eq!(v, variant);
+            assert_eq!(&*data, bytes);
+        }
+    }
+
+    // the examples of the multibase spec
+    let cases = [
+        ("birswgzloorzgc3djpjssazlwmvzhs5dinfxgoijb", Variant::Base32),
+        ("BIRSWGZLOORZGC3DJPJSSAZLWMVZHS5DINFXGOIJB", Variant::Base32Upper),
+        ("cirswgzloorzgc3djpjssazlwmvzhs5dinfxgoijb", Variant::Base32Pad),
+        ("CIRSWGZLOORZGC3DJPJSSAZLWMVZHS5DINFXGOIJB", Variant::Base32PadUpper),
+        ("v8him6pbeehp62r39f9ii0pbmclp7it38d5n6e891", Variant::Base32Hex),
+        ("V8HIM6PBEEHP62R39F9II0PBMCLP7IT38D5N6E891", Variant::Base32HexUpper),
+        ("t8him6pbeehp62r39f9ii0pbmclp7it38d5n6e891", Variant::Base32HexPad),
+        ("T8HIM6PBEEHP62R39F9II0PBMCLP7IT38D5N6E891", Variant::Base32HexPadUpper),
+    ];
+    for (s, variant) in cases {
+        let (v, data) = multibase::decode(s).unwrap();
+        assert_eq!(v, variant);
+        assert_eq!(&*data, b"Decentralize everything!!");
+    }
+
+    for bad in ["", "m", "bnbswy3dp=", "cnbswy3dpeb", "Bnbswy3dp", "vw", "T\u{e9}"] {
+        assert!(multibase::decode(bad).is_err(), "{bad:?}");
+    }
+    assert_eq!(multibase::decode("znbswy3dp").unwrap_err().index(), Some(0));
+    assert_eq!(multibase::decode("bnbSwy3dp").unwrap_err().index(), Some(3));
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn forgiving() {
+    let cases: &[(&str, &[u8])] = &[
+        ("", b""),
+        ("JBSWY3DPEHPK3PXP", b"Hello!\xde\xad\xbe\xef"),
+        ("jbsw y3dp ehpk 3pxp", b"Hello!\xde\xad\xbe\xef"),
+        ("JBSWY3DP\r\nEHPK3PXP\n", b"Hello!\xde\xad\xbe\xef"),
+        ("MZXW6===", b"foo"),
+        ("mzxw6", b"foo"),
+        (" m z x w 6 = = = ", b"foo"),
+        ("MZXW6YQ=", b"foob"),
+    ];
+
+    for base32 in [BASE32, BASE32_NO_PAD] {
+        for &(src, expected) in cases {
+            let mut buf = src.as_bytes().to_vec();
+            let ans = base32.forgiving_decode_inplace(&mut buf).unwrap();
+            assert_eq!(ans, expected, "src = {src:?}");
+
+            let mut buf = vec![0; src.len()];
+            let ans = base32.forgiving_decode(src.as_bytes(), buf.as_out()).unwrap();
+            assert_eq!(ans, expected, "src = {src:?}");
+
+            let ans = base32.forgiving_decode_to_vec(src).unwrap();
+            assert_eq!(ans, expected, "src = {src:?}");
+        }
+    }
+
+    let ans = BASE32HEX.forgiving_decode_to_vec("cpnmu ").unwrap();
+    assert_eq!(ans, b"foo");
+
+    for bad in ["M", "MZX", "MZXW6Y", "MZ=XW6", "MZXW6!", "MZXW6=\u{a0}"] {
+        assert!(BASE32.forgiving_decode_to_vec(bad).is_err(), "bad = {bad:?}");
+    }
+
+    let mut buf = [0u8; 4];
+    assert!(BASE32
+        .forgiving_decode(b"MZXW6===", buf.as_mut_slice().as_out())
+        .is_err());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_exact() {
+    let key: [u8; 20] = BASE32.decode_exact(b"GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ").unwrap();
+    assert_eq!(&key, b"12345678901234567890");
+
+    let ans: [u8; 3] = BASE32.decode_exact(b"MZXW6===").unwrap();
+    assert_eq!(&ans, b"foo");
+    let ans: [u8; 3] = BASE32_NO_PAD.decode_exact(b"MZXW6").unwrap();
+    assert_eq!(&ans, b"foo");
+    let ans: [u8; 0] = BASE32.decode_exact(b"").unwrap();
+    assert_eq!(ans, []);
+
+    assert!(BASE32.decode_exact::<2>(b"MZXW6===").is_err());
+    assert!(BASE32.decode_exact::<4>(b"MZXW6===").is_err());
+    assert!(BASE32.decode_exact::<3>(b"MZXW6").is_err());
+    assert!(BASE32.decode_exact::<3>(b"MZXW7===").is_err());
+    assert!(BASE32.decode_exact::<3>(b"MZXW6==").is_err());
+    assert!(BASE32.decode_exact::<3>(b"MZ1W6===").is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn check() {
+    for n in 0..80 {
+        let bytes = rand_bytes(n);
+        for base32 in [BASE32, BASE32HEX, BASE32_NO_PAD, BASE32HEX_NO_PAD] {
+            let encoded = base32.encode_type::<Vec<u8>>(&bytes);
+            assert!(base32.check(&encoded).is_ok());
+
+            let mut buf = vec![0u8; encoded.len()];
+            for i in 0..encoded.len() {
+                for x in [b'a', b'1', b'W', b'=', b'\n', 0x80] {
+                    let mut corrupted = encoded.clone();
+                    corrupted[i] = x;
+                    let decoded = base32.decode(&corrupted, buf.as_mut_slice().as_out());
+                    assert_eq!(base32.check(&corrupted).is_ok(), decoded.is_ok());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn reference() {
+    use vsimd::reference::{decode, encode};
+
+    for n in 0..256 {
+        let bytes = rand_bytes(n);
+        for base32 in [BASE32, BASE32HEX, BASE32_NO_PAD, BASE32HEX_NO_PAD] {
+            let charset = base32.charset();
+            let pad = base32.pad_char();
+
+            let encoded = encode(&bytes, charset, pad);
+            assert_eq!(base32.encode_type::<Vec<u8>>(&bytes), encoded);
+            assert_eq!(base32.decode_to_vec(&encoded).unwrap(), bytes);
+
+            if encoded.is_empty() {
+                continue;
+            }
+            let pos = rand::random::<usize>() % encoded.len();
+            for x in [b'A', b'0', b'7', b'V', b'=', b'a', 0x80] {
+                let mut corrupted = encoded.clone();
+                corrupted[pos] = x;
+                let expected = decode(&corrupted, charset, pad);
+                assert_eq!(base32.decode_to_vec(&corrupted).ok(), expected);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn totp_secret() {
+    let expected: &[u8] = b"Hello!\xde\xad\xbe\xef";
+    for src in [
+        "JBSWY3DPEHPK3PXP",
+        "jbswy3dpehpk3pxp",
+        "jbsw y3dp ehpk 3pxp",
+        "JBSW Y3DP EHPK 3PXP",
+        " jbsw\ty3dp\nehpk 3pxp ",
+    ] {
+        let ans = base32_simd::decode_totp_secret(src).unwrap();
+        assert_eq!(&*ans, expected, "src = {src:?}");
+    }
+
+    let ans = base32_simd::decode_totp_secret("mzxw 6===").unwrap();
+    assert_eq!(&*ans, b"foo");
+
+    let ans = base32_simd::decode_totp_secret("").unwrap();
+    assert!(ans.is_empty());
+
+    for bad in ["jbsw-y3dp", "jbsw y3dp ehpk 3pxp1", "0123 4567"] {
+        assert!(base32_simd::decode_totp_secret(bad).is_err(), "src = {bad:?}");
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn check_relaxed() {
+    for &src in &[
+        "",
+        "jbsw y3dp ehpk 3pxp",
+        " m z x w 6 = = = ",
+        "MZXW6YQ=",
+        "mzxw6\n",
+        "M",
+        "MZ=XW6",
+        "MZXW6!",
+    ] {
+        let expected = BASE32.forgiving_decode_to_vec(src).is_ok();
+        assert_eq!(BASE32.check_relaxed(src.as_bytes()).is_ok(), expected, "src = {src:?}");
+    }
+
+    for n in [0, 1, 4, 5, 100, 157, 160, 161, 1000] {
+        let bytes = rand_bytes(n);
+        for base32 in [BASE32, BASE32HEX_NO_PAD] {
+            let encoded = base32.encode_type::<Vec<u8>>(&bytes);
+
+            let mut mixed = Vec::new();
+            for (i, &x) in encoded.iter().enumerate() {
+                mixed.push(if i % 3 == 0 { x.to_ascii_lowercase() } else { x });
+                if i % 4 == 3 {
+                    mixed.push(b' ');
+                }
+            }
+
+            for src in [&encoded, &mixed] {
+                base32.check_relaxed(src).unwrap();
+
+                for i in (0..src.len()).step_by(7) {
+                    for bad in [b'!', b'=', b'1', b'\n'] {
+                        let mut src = src.clone();
+                        src[i] = bad;
+                        let expected = base32.forgiving_decode_to_vec(&src).is_ok();
+                        assert_eq!(base32.check_relaxed(&src).is_ok(), expected, "n = {n}, i = {i}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn grouped() {
+    for base32 in [BASE32, BASE32_NO_PAD, BASE32HEX, BASE32HEX_NO_PAD] {
+        for n in 0..100 {
+            let data = rand_bytes(n);
+            let plain = base32.encode_to_string(&data);
+            for (group, sep) in [(1, " "), (8, " "), (32, "\n"), (7, "\r\n"), (64, ""), (1000, "\n")] {
+                let encoded = base32.encode_grouped_to_string(&data, group, sep);
+                assert_eq!(Some(encoded.len()), base32.grouped_encoded_length(n, group, sep.len()));
+
+                let expected: Vec<&str> = plain
+                    .as_bytes()
+                    .chunks(group)
+                    .map(|c| core::str::from_utf8(c).unwrap())
+                    .collect();
+                if !sep.is_empty() {
+                    assert_eq!(encoded, expected.join(sep));
+                }
+
+                let mut buf = vec![0u8; encoded.len()];
+                let ans = base32.decode_wrapped(encoded.as_bytes(), buf.as_out()).unwrap();
+                assert_eq!(ans, data);
+
+                let mut buf = encoded.clone().into_bytes();
+                let ans = base32.decode_wrapped_inplace(&mut buf).unwrap();
+                assert_eq!(ans, data);
+            }
+        }
+    }
+
+    // the case and the padding are strict
+    let mut buf = [0u8; 64];
+    assert!(BASE32
+        .decode_wrapped(b"NBSW Y3DP\n", buf.as_mut_slice().as_out())
+        .is_ok());
+    assert!(BASE32
+        .decode_wrapped(b"nbsw y3dp\n", buf.as_mut_slice().as_out())
+        .is_err());
+    assert!(BASE32
+        .decode_wrapped(b"NBSW Y3DP EE", buf.as_mut_slice().as_out())
+        .is_err());
+    assert!(BASE32.decode_wrapped(b"NBSW Y3DP EE", buf[..8].as_out()).is_err());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn transcode() {
+    let variants = [
+        BASE32,
+        BASE32HEX,
+        BASE32_NO_PAD,
+        BASE32HEX_NO_PAD,
+        BASE32.with_pad_char(b'~'),
+    ];
+
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+        for from in &variants {
+            let mut src = vec![0u8; from.encoded_length(n)];
+            let src = from.encode(&bytes, src.as_mut_slice().as_out()).unwrap();
+            for to in &variants {
+                let mut expected = vec![0u8; to.encoded_length(n)];
+                let expected = to.encode(&bytes, expected.as_mut_slice().as_out()).unwrap();
+
+                let mut buf = vec![0u8; expected.len()];
+                let ans = Base32::transcode(src, from, to, buf.as_mut_slice().as_out()).unwrap();
+                assert_eq!(ans, &*expected);
+
+                if !expected.is_empty() {
+                    let mut buf = vec![0u8; expected.len() - 1];
+                    assert!(Base32::transcode(src, from, to, buf.as_mut_slice().as_out()).is_err());
+                }
+            }
+        }
+    }
+
+    let mut buf = [0u8; 64];
+    for (src, from) in [
+        ("MZXW6===", &BASE32HEX),
+        ("MZXW6YQ", &BASE32),
+        ("MZXW1YQ=", &BASE32),
+        ("MZXW6YR=", &BASE32),
+    ] {
+        let to = &BASE32HEX;
+        assert!(Base32::transcode(src.as_bytes(), from, to, buf.as_mut_slice().as_out()).is_err());
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn lengths() {
+    // The partial groups of 0, 1, 2, 3 and 4 bytes.
+    const EXTRA: [usize; 5] = [0, 2, 4, 5, 7];
+    for n in 0..40 {
+        let (q, r) = (n / 5, n % 5);
+        assert_eq!(BASE32_NO_PAD.encoded_length(n), q * 8 + EXTRA[r]);
+        assert_eq!(BASE32.encoded_length(n), q * 8 + if r == 0 { 0 } else { 8 });
+    }
+
+    for base32 in [BASE32, BASE32HEX, BASE32_NO_PAD, BASE32HEX_NO_PAD] {
+        for n in 0..200 {
+            let bytes = rand_bytes(n);
+            let mut buf = vec![0u8; base32.encoded_length(n)];
+            let encoded = base32.encode(&bytes, buf.as_mut_slice().as_out()).unwrap();
+            assert_eq!(encoded.len(), base32.encoded_length(n));
+            assert_eq!(base32.decoded_length(encoded).unwrap(), n);
+            assert!(base32.estimated_decoded_length(encoded.len()) >= n);
+        }
+    }
+
+    for len in [1, 3, 6, 9, 11, 14] {
+        assert!(BASE32_NO_PAD.decoded_length(&vec![b'A'; len]).is_err());
+    }
+
+    // The largest input whose padded encoding fits in `isize::MAX`.
+    let max = isize::MAX as usize / 8 * 5;
+    for base32 in [BASE32, BASE32_NO_PAD] {
+        assert_eq!(base32.checked_encoded_length(max), Some(max / 5 * 8));
+        assert_eq!(base32.checked_encoded_length(usize::MAX / 2), None);
+        assert_eq!(base32.checked_encoded_length(usize::MAX), None);
+    }
+    assert_eq!(BASE32.checked_encoded_length(max + 1), None);
+    assert_eq!(BASE32_NO_PAD.checked_encoded_length(max + 1), Some(max / 5 * 8 + 2));
+
+    const LEN: Option<usize> = BASE32.checked_encoded_length(6);
+    assert_eq!(LEN, Some(16));
+}
@@ -82,12 +82,50 @@ impl UrlSafeAlsw {
 
 vsimd::impl_alsw!(UrlSafeAlsw);
 
+struct SortableAlsw;
+
+impl SortableAlsw {
+    #[inline]
+    const fn decode(c: u8) -> u8 {
+        match c {
+            b'-' => 0,
+            b'0'..=b'9' => c - b'0' + 1,
+            b'A'..=b'Z' => c - b'A' + 11,
+            b'_' => 37,
+            b'a'..=b'z' => c - b'a' + 38,
+            _ => 0xff,
+        }
+    }
+
+    /// The sortable charset has the same characters as the URL-safe charset.
+    #[inline]
+    const fn check_hash(i: u8) -> u8 {
+        UrlSafeAlsw::check_hash(i)
+    }
+
+    /// The hash is `0x70 + (c >> 4) + decode_hash(c & 0x0f)`.
+    /// `_` is the only character whose high nibble is shared with another range (`P`~`Z`),
+    /// so the hashes of the characters with low nibble `0xF` are moved away.
+    #[inline]
+    const fn decode_hash(i: u8) -> u8 {
+        match i {
+            0xF => 0x09,
+            _ => 0x01,
+        }
+    }
+}
+
+vsimd::impl_alsw!(SortableAlsw);
+
 pub const STANDARD_ALSW_CHECK_X2: AlswLut<V256> = StandardAlsw::check_lut().x2();
 pub const STANDARD_ALSW_DECODE_X2: AlswLut<V256> = StandardAlsw::decode_lut().x2();
 
 pub const URL_SAFE_ALSW_CHECK_X2: AlswLut<V256> = UrlSafeAlsw::check_lut().x2();
 pub const URL_SAFE_ALSW_DECODE_X2: AlswLut<V256> = UrlSafeAlsw::decode_lut().x2();
 
+pub const SORTABLE_ALSW_CHECK_X2: AlswLut<V256> = SortableAlsw::check_lut().x2();
+pub const SORTABLE_ALSW_DECODE_X2: AlswLut<V256> = SortableAlsw::decode_lut().x2();
+
 #[cfg(test)]
 mod algorithm {
     use super::*;
@@ -112,6 +150,16 @@ mod algorithm {
         UrlSafeAlsw::test_decode();
     }
 
+    #[cfg_attr(
+        any(miri, not(all(target_arch = "x86_64", target_os = "linux", target_env = "gnu"))),
+        ignore
+    )]
+    #[test]
+    fn sortable_alsw() {
+        SortableAlsw::test_check();
+        SortableAlsw::test_decode();
+    }
+
     #[cfg(feature = "std")]
     #[test]
     #[ignore]
@@ -0,0 +1,63 @@
+use crate::{Base64, Error, Out};
+
+use core::mem::MaybeUninit;
+
+/// A memory arena which hands out uninitialized byte buffers.
+///
+/// It allows [`Base64::decode_in_arena`] to return an allocated output without the global allocator.
+/// Bump allocators are typical implementors.
+///
+/// # Examples
+///
+/// A fixed-size bump arena:
+///
+/// ```
+/// use base64_simd::{Arena, STANDARD};
+///
+/// use core::cell::{Cell, UnsafeCell};
+/// use core::mem::MaybeUninit;
+///
+/// struct Bump<const N: usize> {
+///     buf: UnsafeCell<[MaybeUninit<u8>; N]>,
+///     used: Cell<usize>,
+/// }
+///
+/// impl<const N: usize> Arena for Bump<N> {
+///     fn alloc_uninit(&self, len: usize) -> &mut [MaybeUninit<u8>] {
+///         let start = self.used.get();
+///         assert!(len <= N - start, "arena exhausted");
+///         self.used.set(start + len);
+///         // The ranges handed out never overlap.
+///         unsafe { (*self.buf.get()).get_unchecked_mut(start..start + len) }
+///     }
+/// }
+///
+/// let arena = Bump::<64> { buf: UnsafeCell::new([MaybeUninit::uninit(); 64]), used: Cell::new(0) };
+/// let a = STANDARD.decode_in_arena(b"aGVsbG8=", &arena).unwrap();
+/// let b = STANDARD.decode_in_arena(b"d29ybGQ=", &arena).unwrap();
+/// assert_eq!((&*a, &*b), (&b"hello"[..], &b"world"[..]));
+/// ```
+pub trait Arena {
+    /// Allocates an uninitialized buffer of exactly `len` bytes.
+    ///
+    /// The buffer must not overlap any other buffer which is still borrowed from the arena.
+    /// An arena which runs out of memory may panic.
+    #[allow(clippy::mut_from_ref)]
+    fn alloc_uninit(&self, len: usize) -> &mut [MaybeUninit<u8>];
+}
+
+impl Base64 {
+    /// Decodes a base64 string to bytes allocated from `arena`.
+    ///
+    /// Exactly [`decoded_length`](Self::decoded_length) bytes are allocated,
+    /// and nothing is allocated if `src` is partially invalid.
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `src` is invalid.
+    #[inline]
+    pub fn decode_in_arena<'a, A: Arena + ?Sized>(&self, src: &[u8], arena: &'a A) -> Result<&'a mut [u8], Error> {
+        let len = self.decoded_length(src)?;
+        let dst = Out::from_uninit_slice(arena.alloc_uninit(len));
+        self.decode(src, dst)
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{Base64, Error, Out};
+
+use std::io::BorrowedBuf;
+
+impl Base64 {
+    /// Encodes the filled part of `src` to a base64 string.
+    ///
+    /// `src` is usually filled by [`Read::read_buf`](std::io::Read::read_buf).
+    /// The initialized but unfilled part is not encoded.
+    ///
+    /// This function is only available with the feature flags `std` and `unstable`.
+    ///
+    /// # Errors
+    /// This function returns `Err` if the length of `dst` is not enough.
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "unstable"))))]
+    #[inline]
+    pub fn encode_from_borrowed<'d>(&self, src: &BorrowedBuf<'_>, dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        self.encode(src.filled(), dst)
+    }
+
+    /// Decodes the filled part of `src` to bytes.
+    ///
+    /// `src` is usually filled by [`Read::read_buf`](std::io::Read::read_buf),
+    /// so the input is decoded without copying it out of the uninitialized buffer first.
+    /// The initialized but unfilled part is not decoded.
+    ///
+    /// This function is only available with the feature flags `std` and `unstable`.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the filled part of `src` is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(core_io_borrowed_buf, read_buf)]
+    /// use base64_simd::{AsOut, STANDARD};
+    ///
+    /// use std::io::{BorrowedBuf, Read};
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut storage = [MaybeUninit::<u8>::uninit(); 64];
+    /// let mut src = BorrowedBuf::from(storage.as_mut_slice());
+    /// let mut reader: &[u8] = b"aGVsbG8=";
+    /// reader.read_buf(src.unfilled()).unwrap();
+    ///
+    /// let mut buf = [0u8; 8];
+    /// let ans = STANDARD.decode_from_borrowed(&src, buf.as_mut_slice().as_out()).unwrap();
+    /// assert_eq!(ans, b"hello");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "std", feature = "unstable"))))]
+    #[inline]
+    pub fn decode_from_borrowed<'d>(&self, src: &BorrowedBuf<'_>, dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        self.decode(src.filled(), dst)
+    }
+}
@@ -0,0 +1,94 @@
+use crate::Base64;
+
+use vsimd::isa::{InstructionSet, AVX2, NEON, SSSE3, WASM128};
+
+/// The code path selected by the codec.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// x86 AVX2
+    Avx2,
+    /// x86 SSSE3
+    Ssse3,
+    /// ARM NEON
+    Neon,
+    /// WebAssembly SIMD128
+    Simd128,
+    /// Portable scalar code
+    Fallback,
+}
+
+/// The effective configuration of the codec on the current CPU.
+///
+/// See [`Base64::capabilities`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The selected backend.
+    pub backend: Backend,
+
+    /// The width of the vector registers used by the backend, in bytes.
+    ///
+    /// It is `0` for [`Backend::Fallback`].
+    pub vector_width: usize,
+}
+
+/// The backend pinned by the `force-*` feature flags on the current target.
+#[inline(always)]
+fn forced_backend() -> Option<Backend> {
+    if cfg!(feature = "force-scalar") {
+        return Some(Backend::Fallback);
+    }
+    if cfg!(any(target_arch = "x86", target_arch = "x86_64")) {
+        if cfg!(feature = "force-avx2") {
+            return Some(Backend::Avx2);
+        }
+        if cfg!(feature = "force-sse41") {
+            return Some(Backend::Ssse3);
+        }
+    }
+    if cfg!(any(
+        all(feature = "unstable", target_arch = "arm"),
+        target_arch = "aarch64"
+    )) && cfg!(feature = "force-neon")
+    {
+        return Some(Backend::Neon);
+    }
+    None
+}
+
+impl Base64 {
+    /// Returns the code path which is used by the SIMD-accelerated functions on the current CPU.
+    ///
+    /// The backend is resolved in the same order as the runtime dispatch.
+    /// A backend forced by a feature flag is reported without detection.
+    /// Without the `detect` feature, only the target features enabled at compile time are considered.
+    ///
+    /// Constant-time variants always use the portable scalar code for the character mapping,
+    /// regardless of the reported backend.
+    #[inline]
+    #[must_use]
+    pub fn capabilities() -> Capabilities {
+        let backend = if let Some(backend) = forced_backend() {
+            backend
+        } else if AVX2::is_enabled() {
+            Backend::Avx2
+        } else if SSSE3::is_enabled() {
+            Backend::Ssse3
+        } else if NEON::is_enabled() {
+            Backend::Neon
+        } else if WASM128::is_enabled() {
+            Backend::Simd128
+        } else {
+            Backend::Fallback
+        };
+
+        let vector_width = match backend {
+            Backend::Avx2 => 32,
+            Backend::Ssse3 | Backend::Neon | Backend::Simd128 => 16,
+            Backend::Fallback => 0,
+        };
+
+        Capabilities { backend, vector_width }
+    }
+}
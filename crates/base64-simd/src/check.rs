@@ -1,6 +1,6 @@
-use crate::alsw::{STANDARD_ALSW_CHECK_X2, URL_SAFE_ALSW_CHECK_X2};
+use crate::alsw::{SORTABLE_ALSW_CHECK_X2, STANDARD_ALSW_CHECK_X2, URL_SAFE_ALSW_CHECK_X2};
 use crate::decode::{decode_ascii4, decode_ascii8, decode_extra};
-use crate::decode::{STANDARD_DECODE_TABLE, URL_SAFE_DECODE_TABLE};
+use crate::decode::{SORTABLE_DECODE_TABLE, STANDARD_DECODE_TABLE, URL_SAFE_DECODE_TABLE};
 use crate::{Config, Error, Kind};
 
 use vsimd::alsw::AlswLut;
@@ -11,12 +11,17 @@ use core::ptr::null_mut;
 
 #[inline]
 pub(crate) unsafe fn check_fallback(mut src: *const u8, mut n: usize, config: Config) -> Result<(), Error> {
+    if config.constant_time {
+        return crate::ct::decode::<false>(src, null_mut(), n, config, false);
+    }
+
     let kind = config.kind;
     let forgiving = config.extra.forgiving();
 
     let table = match kind {
         Kind::Standard => STANDARD_DECODE_TABLE.as_ptr(),
         Kind::UrlSafe => URL_SAFE_DECODE_TABLE.as_ptr(),
+        Kind::Sortable => SORTABLE_DECODE_TABLE.as_ptr(),
     };
 
     unsafe {
@@ -44,11 +49,16 @@ pub(crate) unsafe fn check_simd<S: SIMD256>(
     mut n: usize,
     config: Config,
 ) -> Result<(), Error> {
+    if config.constant_time {
+        return crate::ct::decode::<false>(src, null_mut(), n, config, false);
+    }
+
     let kind = config.kind;
 
     let check_lut = match kind {
         Kind::Standard => STANDARD_ALSW_CHECK_X2,
         Kind::UrlSafe => URL_SAFE_ALSW_CHECK_X2,
+        Kind::Sortable => SORTABLE_ALSW_CHECK_X2,
     };
 
     unsafe {
@@ -69,3 +79,37 @@ pub(crate) unsafe fn check_simd<S: SIMD256>(
 fn check_ascii32<S: SIMD256>(s: S, x: V256, check: AlswLut<V256>) -> bool {
     vsimd::alsw::check_ascii_xn(s, x, check)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{SORTABLE, STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+
+    use vsimd::isa::{Fallback, InstructionSet};
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn fallback_isa() {
+        let s = unsafe { Fallback::new() };
+        let src: Vec<u8> = (0..150u32).map(|i| (i * 37 + 11) as u8).collect();
+
+        for base64 in [STANDARD_NO_PAD, URL_SAFE_NO_PAD, SORTABLE] {
+            let mut encoded = vec![0u8; crate::encode::encoded_length_unchecked(src.len(), base64.config)];
+            unsafe { crate::encode::encode_fallback(src.as_ptr(), src.len(), encoded.as_mut_ptr(), base64.config) };
+            for n in (0..encoded.len()).filter(|n| n % 4 != 1) {
+                let mut data = encoded[..n].to_vec();
+                let check = |data: &[u8]| unsafe {
+                    let expected = check_fallback(data.as_ptr(), n, base64.config);
+                    let ans = check_simd(s, data.as_ptr(), n, base64.config);
+                    assert_eq!(ans.is_ok(), expected.is_ok(), "n = {n}");
+                };
+                check(&data);
+                if n > 0 {
+                    data[n * 2 / 3] = b'=';
+                    check(&data);
+                }
+            }
+        }
+    }
+}
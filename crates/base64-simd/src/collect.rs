@@ -0,0 +1,107 @@
+use crate::STANDARD;
+
+use alloc::string::String;
+
+use core::fmt;
+use core::ops::Deref;
+
+/// A base64 string collected from bytes.
+///
+/// It implements [`FromIterator<u8>`], so an iterator of bytes can be encoded by [`collect`](Iterator::collect).
+/// The bytes are encoded with [`STANDARD`] in chunks as they are collected.
+///
+/// # Examples
+///
+/// ```
+/// use base64_simd::Base64String;
+///
+/// let encoded: Base64String = b"hello".iter().map(|x| x.to_ascii_uppercase()).collect();
+/// assert_eq!(encoded.as_str(), "SEVMTE8=");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Base64String(String);
+
+impl Base64String {
+    /// Returns the base64 string.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the inner [`String`].
+    #[inline]
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl FromIterator<u8> for Base64String {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        // A multiple of 3, so that no padding is produced until the end.
+        const CHUNK: usize = 768;
+
+        let iter = iter.into_iter();
+        let mut ans = String::with_capacity(STANDARD.checked_encoded_length(iter.size_hint().0).unwrap_or(0));
+
+        let mut buf = [0u8; CHUNK];
+        let mut len = 0;
+        for x in iter {
+            buf[len] = x;
+            len += 1;
+            if len == CHUNK {
+                STANDARD.encode_append(&buf[..], &mut ans);
+                len = 0;
+            }
+        }
+        STANDARD.encode_append(&buf[..len], &mut ans);
+
+        Self(ans)
+    }
+}
+
+impl<'a> FromIterator<&'a u8> for Base64String {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = &'a u8>>(iter: I) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl Deref for Base64String {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Base64String {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Base64String {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl From<Base64String> for String {
+    #[inline]
+    fn from(s: Base64String) -> Self {
+        s.0
+    }
+}
+
+impl fmt::Display for Base64String {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        <str as fmt::Display>::fmt(&self.0, f)
+    }
+}
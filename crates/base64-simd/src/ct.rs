@@ -0,0 +1,218 @@
+//! Constant-time base64 encoding and decoding.
+//!
+//! These functions never use secret data as a table index or a branch condition.
+//! The mapping between 6-bit values and characters is computed with branchless range arithmetic.
+
+use crate::{Config, Error, Kind};
+
+use vsimd::tools::{read, write};
+
+/// Returns `-1` if `lo <= c <= hi`, otherwise `0`.
+#[inline(always)]
+fn in_range(c: i32, lo: i32, hi: i32) -> i32 {
+    ((lo - 1 - c) & (c - hi - 1)) >> 8
+}
+
+/// Returns `-1` if `c == x`, otherwise `0`.
+#[inline(always)]
+fn equals(c: i32, x: i32) -> i32 {
+    in_range(c, x, x)
+}
+
+/// Returns `-1` if `x >= k`, otherwise `0`.
+#[inline(always)]
+fn ge(x: i32, k: i32) -> i32 {
+    (k - 1 - x) >> 8
+}
+
+#[inline(always)]
+fn encode_char(x: u8, kind: Kind) -> u8 {
+    let x = x as i32;
+    let (c62, c63) = match kind {
+        Kind::Standard => (b'+' as i32, b'/' as i32),
+        Kind::UrlSafe => (b'-' as i32, b'_' as i32),
+        Kind::Sortable => return encode_char_sortable(x),
+    };
+
+    let mut c = x + b'A' as i32;
+    c += ge(x, 26) & (b'a' as i32 - b'A' as i32 - 26);
+    c += ge(x, 52) & (b'0' as i32 - b'a' as i32 - 26);
+    c += ge(x, 62) & (c62 - b'0' as i32 - 10);
+    c += ge(x, 63) & (c63 - c62 - 1);
+    c as u8
+}
+
+#[inline(always)]
+fn encode_char_sortable(x: i32) -> u8 {
+    let mut c = x + b'-' as i32;
+    c += ge(x, 1) & (b'0' as i32 - 1 - b'-' as i32);
+    c += ge(x, 11) & (b'A' as i32 - 11 - (b'0' as i32 - 1));
+    c += ge(x, 37) & (b'_' as i32 - 37 - (b'A' as i32 - 11));
+    c += ge(x, 38) & (b'a' as i32 - 38 - (b'_' as i32 - 37));
+    c as u8
+}
+
+/// Returns the 6-bit value of `c`, or `0xff` if `c` is invalid.
+#[inline(always)]
+fn decode_char(c: u8, kind: Kind, any: bool) -> u8 {
+    let c = c as i32;
+
+    let (s62, s63, u62, u63) = match (kind, any) {
+        (_, true) => (-1, -1, -1, -1),
+        (Kind::Standard, false) => (-1, -1, 0, 0),
+        (Kind::UrlSafe, false) => (0, 0, -1, -1),
+        (Kind::Sortable, false) => return decode_char_sortable(c),
+    };
+
+    let mut y = -1;
+    y += in_range(c, b'A' as i32, b'Z' as i32) & (c - b'A' as i32 + 1);
+    y += in_range(c, b'a' as i32, b'z' as i32) & (c - b'a' as i32 + 27);
+    y += in_range(c, b'0' as i32, b'9' as i32) & (c - b'0' as i32 + 53);
+    y += equals(c, b'+' as i32) & s62 & 63;
+    y += equals(c, b'/' as i32) & s63 & 64;
+    y += equals(c, b'-' as i32) & u62 & 63;
+    y += equals(c, b'_' as i32) & u63 & 64;
+    y as u8
+}
+
+#[inline(always)]
+fn decode_char_sortable(c: i32) -> u8 {
+    let mut y = -1;
+    y += equals(c, b'-' as i32) & 1;
+    y += in_range(c, b'0' as i32, b'9' as i32) & (c - b'0' as i32 + 2);
+    y += in_range(c, b'A' as i32, b'Z' as i32) & (c - b'A' as i32 + 12);
+    y += equals(c, b'_' as i32) & (c - b'_' as i32 + 38);
+    y += in_range(c, b'a' as i32, b'z' as i32) & (c - b'a' as i32 + 39);
+    y as u8
+}
+
+#[inline]
+pub(crate) unsafe fn encode(mut src: *const u8, mut len: usize, mut dst: *mut u8, config: Config) {
+    let kind = config.kind;
+    let pad = config.pad();
+
+    while len >= 3 {
+        let x = u32::from_be_bytes([0, read(src, 0), read(src, 1), read(src, 2)]);
+        let mut i = 0;
+        while i < 4 {
+            let bits = ((x >> (18 - i * 6)) & 0x3f) as u8;
+            write(dst, i, encode_char(bits, kind));
+            i += 1;
+        }
+        src = src.add(3);
+        dst = dst.add(4);
+        len -= 3;
+    }
+
+    match len {
+        0 => {}
+        1 => {
+            let x = read(src, 0);
+            write(dst, 0, encode_char(x >> 2, kind));
+            write(dst, 1, encode_char((x << 4) & 0x3f, kind));
+            if let Some(pad) = pad {
+                write(dst, 2, pad);
+                write(dst, 3, pad);
+            }
+        }
+        2 => {
+            let x1 = read(src, 0);
+            let x2 = read(src, 1);
+            write(dst, 0, encode_char(x1 >> 2, kind));
+            write(dst, 1, encode_char(((x1 << 4) | (x2 >> 4)) & 0x3f, kind));
+            write(dst, 2, encode_char((x2 << 2) & 0x3f, kind));
+            if let Some(pad) = pad {
+                write(dst, 3, pad);
+            }
+        }
+        _ => core::hint::unreachable_unchecked(),
+    }
+}
+
+#[inline]
+pub(crate) unsafe fn decode<const WRITE: bool>(
+    mut src: *const u8,
+    mut dst: *mut u8,
+    mut n: usize,
+    config: Config,
+    any: bool,
+) -> Result<(), Error> {
+    let kind = config.kind;
+    let forgiving = config.extra.forgiving();
+
+    let mut flag: u8 = 0;
+
+    while n >= 4 {
+        let y1 = decode_char(read(src, 0), kind, any);
+        let y2 = decode_char(read(src, 1), kind, any);
+        let y3 = decode_char(read(src, 2), kind, any);
+        let y4 = decode_char(read(src, 3), kind, any);
+        flag |= y1 | y2 | y3 | y4;
+
+        if WRITE {
+            write(dst, 0, (y1 << 2) | (y2 >> 4));
+            write(dst, 1, (y2 << 4) | (y3 >> 2));
+            write(dst, 2, (y3 << 6) | y4);
+            dst = dst.add(3);
+        }
+
+        src = src.add(4);
+        n -= 4;
+    }
+
+    match n {
+        0 => {}
+        2 => {
+            let y1 = decode_char(read(src, 0), kind, any);
+            let y2 = decode_char(read(src, 1), kind, any);
+            flag |= y1 | y2;
+            if !forgiving {
+                flag |= ((y2 & 0x0f) != 0) as u8 * 0x80;
+            }
+            if WRITE {
+                write(dst, 0, (y1 << 2) | (y2 >> 4));
+            }
+        }
+        3 => {
+            let y1 = decode_char(read(src, 0), kind, any);
+            let y2 = decode_char(read(src, 1), kind, any);
+            let y3 = decode_char(read(src, 2), kind, any);
+            flag |= y1 | y2 | y3;
+            if !forgiving {
+                flag |= ((y3 & 0x03) != 0) as u8 * 0x80;
+            }
+            if WRITE {
+                write(dst, 0, (y1 << 2) | (y2 >> 4));
+                write(dst, 1, (y2 << 4) | (y3 >> 2));
+            }
+        }
+        _ => core::hint::unreachable_unchecked(),
+    }
+
+    ensure!(flag & 0x80 == 0);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::decode::{SORTABLE_DECODE_TABLE, STANDARD_DECODE_TABLE, URL_SAFE_DECODE_TABLE};
+    use crate::{SORTABLE_CHARSET, STANDARD_CHARSET, URL_SAFE_CHARSET};
+
+    #[test]
+    fn char_mapping() {
+        for (kind, charset, table) in [
+            (Kind::Standard, STANDARD_CHARSET, STANDARD_DECODE_TABLE),
+            (Kind::UrlSafe, URL_SAFE_CHARSET, URL_SAFE_DECODE_TABLE),
+            (Kind::Sortable, SORTABLE_CHARSET, SORTABLE_DECODE_TABLE),
+        ] {
+            for x in 0..64u8 {
+                assert_eq!(encode_char(x, kind), charset[x as usize]);
+            }
+            for c in 0..=255u8 {
+                assert_eq!(decode_char(c, kind, false), table[c as usize]);
+            }
+        }
+    }
+}
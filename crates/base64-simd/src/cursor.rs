@@ -0,0 +1,102 @@
+use crate::{Base64, Config, Error};
+
+use alloc::boxed::Box;
+
+/// A cursor which decodes base64 regions of an input buffer one by one.
+///
+/// It keeps track of the position in the buffer,
+/// so that base64 regions can be interleaved with other fields read by the caller.
+///
+/// # Examples
+///
+/// ```
+/// let base64 = base64_simd::STANDARD;
+/// let mut cursor = base64.cursor(b"Zm9v|YmFy");
+///
+/// assert_eq!(&*cursor.decode_next(4).unwrap(), b"foo");
+/// assert_eq!(cursor.remaining(), b"|YmFy");
+///
+/// cursor.advance(1);
+/// assert_eq!(&*cursor.decode_next(4).unwrap(), b"bar");
+/// assert_eq!(cursor.position(), 9);
+/// assert!(cursor.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Base64Cursor<'a> {
+    config: Config,
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Base64Cursor<'a> {
+    /// Returns the current position in the input buffer.
+    #[inline]
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the unread part of the input buffer.
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.data[self.pos..]
+    }
+
+    /// Returns `true` if the whole input buffer has been consumed.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.data.len()
+    }
+
+    /// Skips `n` bytes of the input buffer without decoding them.
+    ///
+    /// # Panics
+    /// This function asserts that `n` is not greater than the length of the unread part.
+    #[inline]
+    pub fn advance(&mut self, n: usize) {
+        assert!(n <= self.data.len() - self.pos);
+        self.pos += n;
+    }
+
+    /// Decodes the next `n_chars` characters and advances the cursor past them.
+    ///
+    /// Unless the region reaches the end of the input buffer, `n_chars` must be a multiple of 4,
+    /// so that the cursor always stays on a quantum boundary.
+    /// The region is decoded as a complete base64 string, including its padding if any.
+    ///
+    /// The cursor is not moved if this function fails.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + `n_chars` is greater than the length of the unread part.
+    /// + `n_chars` is not a multiple of 4 and the region does not reach the end.
+    /// + the content of the region is invalid.
+    #[inline]
+    pub fn decode_next(&mut self, n_chars: usize) -> Result<Box<[u8]>, Error> {
+        let rest = self.remaining();
+        ensure!(n_chars <= rest.len(), InvalidLength);
+        ensure!(n_chars % 4 == 0 || n_chars == rest.len(), InvalidLength);
+
+        let base64 = Base64 { config: self.config };
+        let ans = base64.decode_type::<Box<[u8]>>(&rest[..n_chars])?;
+
+        self.pos += n_chars;
+        Ok(ans)
+    }
+}
+
+impl Base64 {
+    /// Creates a [`Base64Cursor`] at the beginning of `data`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[inline]
+    #[must_use]
+    pub fn cursor<'a>(&self, data: &'a [u8]) -> Base64Cursor<'a> {
+        Base64Cursor {
+            config: self.config,
+            data,
+            pos: 0,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Data URLs with base64 payloads.
+//!
+//! A data URL has the form `data:<mime>;base64,<payload>`.
+//! The payload uses the [`STANDARD`](crate::STANDARD) variant.
+//!
+//! See <https://datatracker.ietf.org/doc/html/rfc2397>
+//!
+//! # Examples
+//!
+//! ```
+//! use base64_simd::data_uri;
+//!
+//! let uri = data_uri::encode("text/plain", b"hello");
+//! assert_eq!(uri, "data:text/plain;base64,aGVsbG8=");
+//!
+//! let (mime, data) = data_uri::decode(&uri).unwrap();
+//! assert_eq!(mime, "text/plain");
+//! assert_eq!(&*data, b"hello");
+//! ```
+
+use crate::{Error, STANDARD};
+
+use alloc::boxed::Box;
+use alloc::string::String;
+
+const SCHEME: &str = "data:";
+const BASE64_MARKER: &str = ";base64";
+
+/// Encodes `data` to a data URL with the media type `mime`.
+///
+/// `mime` is written as is. It should not contain `,`.
+/// The allocation is sized exactly for the prefix and the encoded payload.
+#[inline]
+#[must_use]
+pub fn encode(mime: &str, data: &[u8]) -> String {
+    let len = SCHEME.len() + mime.len() + BASE64_MARKER.len() + 1 + STANDARD.encoded_length(data.len());
+    let mut uri = String::with_capacity(len);
+    uri.push_str(SCHEME);
+    uri.push_str(mime);
+    uri.push_str(BASE64_MARKER);
+    uri.push(',');
+    STANDARD.encode_append(data, &mut uri);
+    debug_assert_eq!(uri.len(), len);
+    uri
+}
+
+/// Decodes a data URL with a base64 payload.
+///
+/// Returns the media type, including its parameters, and the decoded payload.
+/// The media type is empty if it is omitted in `uri`.
+/// The scheme and the `;base64` marker are matched case-insensitively.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + `uri` is not a data URL.
+/// + the payload is not marked as base64.
+/// + the payload is invalid.
+#[inline]
+pub fn decode(uri: &str) -> Result<(String, Box<[u8]>), Error> {
+    let uri = uri.as_bytes();
+    ensure!(uri.len() >= SCHEME.len() && uri[..SCHEME.len()].eq_ignore_ascii_case(SCHEME.as_bytes()));
+    let rest = &uri[SCHEME.len()..];
+
+    let (header, payload) = match rest.iter().position(|&x| x == b',') {
+        Some(pos) => (&rest[..pos], &rest[pos + 1..]),
+        None => return Err(Error::new()),
+    };
+
+    ensure!(header.len() >= BASE64_MARKER.len());
+    let (mime, marker) = header.split_at(header.len() - BASE64_MARKER.len());
+    ensure!(marker.eq_ignore_ascii_case(BASE64_MARKER.as_bytes()));
+
+    // `mime` is surrounded by ASCII characters in `uri`, so it is valid UTF-8.
+    let mime = unsafe { core::str::from_utf8_unchecked(mime) };
+
+    let data = STANDARD.decode_type::<Box<[u8]>>(payload)?;
+    Ok((mime.into(), data))
+}
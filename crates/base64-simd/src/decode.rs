@@ -1,10 +1,11 @@
-use crate::alsw::{STANDARD_ALSW_CHECK_X2, URL_SAFE_ALSW_CHECK_X2};
-use crate::alsw::{STANDARD_ALSW_DECODE_X2, URL_SAFE_ALSW_DECODE_X2};
-use crate::{Config, Error, Extra, Kind};
-use crate::{STANDARD_CHARSET, URL_SAFE_CHARSET};
+use crate::alsw::{SORTABLE_ALSW_CHECK_X2, STANDARD_ALSW_CHECK_X2, URL_SAFE_ALSW_CHECK_X2};
+use crate::alsw::{SORTABLE_ALSW_DECODE_X2, STANDARD_ALSW_DECODE_X2, URL_SAFE_ALSW_DECODE_X2};
+use crate::nul::truncate_at_nul;
+use crate::{Config, Error, ErrorKind, Extra, Kind, NulPolicy};
+use crate::{SORTABLE_CHARSET, STANDARD_CHARSET, URL_SAFE_CHARSET};
 
 use vsimd::alsw::AlswLut;
-use vsimd::isa::{NEON, SSSE3, WASM128};
+use vsimd::isa::{AVX2, NEON, SSSE3, WASM128};
 use vsimd::mask::u8x32_highbit_any;
 use vsimd::matches_isa;
 use vsimd::tools::{read, write};
@@ -25,57 +26,137 @@ const fn decode_table(charset: &'static [u8; 64]) -> [u8; 256] {
 
 pub const STANDARD_DECODE_TABLE: &[u8; 256] = &decode_table(STANDARD_CHARSET);
 pub const URL_SAFE_DECODE_TABLE: &[u8; 256] = &decode_table(URL_SAFE_CHARSET);
+pub const SORTABLE_DECODE_TABLE: &[u8; 256] = &decode_table(SORTABLE_CHARSET);
+
+pub const ANY_DECODE_TABLE: &[u8; 256] = &{
+    let mut table = decode_table(STANDARD_CHARSET);
+    table[b'-' as usize] = 62;
+    table[b'_' as usize] = 63;
+    table
+};
 
 #[inline(always)]
 pub(crate) fn decoded_length(src: &[u8], config: Config) -> Result<(usize, usize), Error> {
+    let src = match config.nul {
+        NulPolicy::Stop => truncate_at_nul(src),
+        NulPolicy::Error | NulPolicy::Skip => src,
+    };
+    significant_length(src, config)
+}
+
+#[inline(always)]
+const fn count_pad(src: &[u8], pad: u8) -> usize {
+    let len = src.len();
+    if src[len - 1] == pad {
+        if src[len - 2] == pad {
+            2
+        } else {
+            1
+        }
+    } else {
+        0
+    }
+}
+
+/// Returns the number of significant characters and the decoded length.
+#[inline(always)]
+const fn significant_length(src: &[u8], config: Config) -> Result<(usize, usize), Error> {
     if src.is_empty() {
         return Ok((0, 0));
     }
 
-    let n = unsafe {
-        let len = src.len();
-
-        let count_pad = || {
-            let last1 = *src.get_unchecked(len - 1);
-            let last2 = *src.get_unchecked(len - 2);
-            if last1 == b'=' {
-                if last2 == b'=' {
-                    2
-                } else {
-                    1
-                }
+    let len = src.len();
+    let n = match config.extra {
+        Extra::Pad => {
+            ensure!(len % 4 == 0, InvalidLength);
+            len - count_pad(src, config.pad)
+        }
+        Extra::NoPad => len,
+        Extra::OptionalPad | Extra::Forgiving => {
+            if len % 4 == 0 {
+                len - count_pad(src, config.pad)
             } else {
-                0
-            }
-        };
-
-        match config.extra {
-            Extra::Pad => {
-                ensure!(len % 4 == 0);
-                len - count_pad()
-            }
-            Extra::NoPad => len,
-            Extra::Forgiving => {
-                if len % 4 == 0 {
-                    len - count_pad()
-                } else {
-                    len
-                }
+                len
             }
         }
     };
 
+    // `n` is the number of significant characters.
+    // Each full quantum of 4 characters is decoded to 3 bytes,
+    // and a trailing partial quantum of 2 or 3 characters is decoded to 1 or 2 bytes.
+    // A trailing single character carries only 6 bits, which can not form a byte.
+    // The rule is the same for all variants, including the forgiving one.
     let m = match n % 4 {
         0 => n / 4 * 3,
-        1 => return Err(Error::new()),
         2 => n / 4 * 3 + 1,
         3 => n / 4 * 3 + 2,
-        _ => unsafe { core::hint::unreachable_unchecked() },
+        _ => return Err(Error::with_kind(ErrorKind::InvalidLength)),
     };
 
     Ok((n, m))
 }
 
+pub(crate) const fn decode_table_of(kind: Kind) -> &'static [u8; 256] {
+    match kind {
+        Kind::Standard => STANDARD_DECODE_TABLE,
+        Kind::UrlSafe => URL_SAFE_DECODE_TABLE,
+        Kind::Sortable => SORTABLE_DECODE_TABLE,
+    }
+}
+
+/// Decodes `src` to an array of exactly `N` bytes in const contexts.
+///
+/// The rules are the same as [`decode_fallback`], except that NUL bytes are always invalid.
+pub(crate) const fn decode_const<const N: usize>(src: &[u8], config: Config) -> Result<[u8; N], Error> {
+    let (n, m) = match significant_length(src, config) {
+        Ok(x) => x,
+        Err(e) => return Err(e),
+    };
+    ensure!(m == N, InvalidLength);
+
+    let table = decode_table_of(config.kind);
+    let mut dst = [0; N];
+
+    let mut i = 0;
+    let mut j = 0;
+    while n - i >= 4 {
+        let y1 = table[src[i] as usize];
+        let y2 = table[src[i + 1] as usize];
+        let y3 = table[src[i + 2] as usize];
+        let y4 = table[src[i + 3] as usize];
+        ensure!((y1 | y2 | y3 | y4) != 0xff);
+
+        dst[j] = (y1 << 2) | (y2 >> 4);
+        dst[j + 1] = (y2 << 4) | (y3 >> 2);
+        dst[j + 2] = (y3 << 6) | y4;
+        i += 4;
+        j += 3;
+    }
+
+    let forgiving = config.extra.forgiving();
+    match n - i {
+        2 => {
+            let y1 = table[src[i] as usize];
+            let y2 = table[src[i + 1] as usize];
+            ensure!((y1 | y2) != 0xff && (forgiving || (y2 & 0x0f) == 0));
+
+            dst[j] = (y1 << 2) | (y2 >> 4);
+        }
+        3 => {
+            let y1 = table[src[i] as usize];
+            let y2 = table[src[i + 1] as usize];
+            let y3 = table[src[i + 2] as usize];
+            ensure!((y1 | y2 | y3) != 0xff && (forgiving || (y3 & 0x03) == 0));
+
+            dst[j] = (y1 << 2) | (y2 >> 4);
+            dst[j + 1] = (y2 << 4) | (y3 >> 2);
+        }
+        _ => {}
+    }
+
+    Ok(dst)
+}
+
 #[inline(always)]
 pub unsafe fn decode_ascii8<const WRITE: bool>(src: *const u8, dst: *mut u8, table: *const u8) -> Result<(), Error> {
     let mut y: u64 = 0;
@@ -139,53 +220,47 @@ pub unsafe fn decode_extra<const WRITE: bool>(
     table: *const u8,
     forgiving: bool,
 ) -> Result<(), Error> {
-    match extra {
-        0 => {}
-        1 => core::hint::unreachable_unchecked(),
-        2 => {
-            let [x1, x2] = src.cast::<[u8; 2]>().read();
+    ensure!(vsimd::reference::decode_bits::<6, WRITE>(
+        src, extra, dst, table, forgiving
+    ));
+    Ok(())
+}
 
-            let y1 = read(table, x1 as usize);
-            let y2 = read(table, x2 as usize);
-            ensure!((y1 | y2) != 0xff && (forgiving || (y2 & 0x0f) == 0));
+#[inline]
+pub(crate) unsafe fn decode_fallback(src: *const u8, dst: *mut u8, n: usize, config: Config) -> Result<(), Error> {
+    #[cfg(feature = "stats")]
+    crate::stats::add_tail(n);
 
-            if WRITE {
-                write(dst, 0, (y1 << 2) | (y2 >> 4));
-            }
-        }
-        3 => {
-            let [x1, x2, x3] = src.cast::<[u8; 3]>().read();
+    if config.constant_time {
+        return crate::ct::decode::<true>(src, dst, n, config, false);
+    }
 
-            let y1 = read(table, x1 as usize);
-            let y2 = read(table, x2 as usize);
-            let y3 = read(table, x3 as usize);
-            ensure!((y1 | y2 | y3) != 0xff && (forgiving || (y3 & 0x03) == 0));
+    let table = match config.kind {
+        Kind::Standard => STANDARD_DECODE_TABLE.as_ptr(),
+        Kind::UrlSafe => URL_SAFE_DECODE_TABLE.as_ptr(),
+        Kind::Sortable => SORTABLE_DECODE_TABLE.as_ptr(),
+    };
+    decode_table_fallback(src, dst, n, table, config.extra.forgiving())
+}
 
-            if WRITE {
-                write(dst, 0, (y1 << 2) | (y2 >> 4));
-                write(dst, 1, (y2 << 4) | (y3 >> 2));
-            }
-        }
-        _ => core::hint::unreachable_unchecked(),
+#[inline]
+pub(crate) unsafe fn decode_any_fallback(src: *const u8, dst: *mut u8, n: usize, config: Config) -> Result<(), Error> {
+    if config.constant_time {
+        return crate::ct::decode::<true>(src, dst, n, config, true);
     }
-    Ok(())
+
+    let table = ANY_DECODE_TABLE.as_ptr();
+    decode_table_fallback(src, dst, n, table, config.extra.forgiving())
 }
 
-#[inline]
-pub(crate) unsafe fn decode_fallback(
+#[inline(always)]
+unsafe fn decode_table_fallback(
     mut src: *const u8,
     mut dst: *mut u8,
     mut n: usize,
-    config: Config,
+    table: *const u8,
+    forgiving: bool,
 ) -> Result<(), Error> {
-    let kind = config.kind;
-    let forgiving = config.extra.forgiving();
-
-    let table = match kind {
-        Kind::Standard => STANDARD_DECODE_TABLE.as_ptr(),
-        Kind::UrlSafe => URL_SAFE_DECODE_TABLE.as_ptr(),
-    };
-
     // n*3/4 >= 6+2
     while n >= 11 {
         decode_ascii8::<true>(src, dst, table)?;
@@ -213,30 +288,120 @@ pub(crate) unsafe fn decode_simd<S: SIMD256>(
     mut n: usize,
     config: Config,
 ) -> Result<(), Error> {
+    if config.constant_time {
+        #[cfg(feature = "stats")]
+        crate::stats::add_tail(n);
+
+        return crate::ct::decode::<true>(src, dst, n, config, false);
+    }
+
     let kind = config.kind;
 
+    #[cfg(feature = "stats")]
+    let total = n;
+
     let (check_lut, decode_lut) = match kind {
         Kind::Standard => (STANDARD_ALSW_CHECK_X2, STANDARD_ALSW_DECODE_X2),
         Kind::UrlSafe => (URL_SAFE_ALSW_CHECK_X2, URL_SAFE_ALSW_DECODE_X2),
+        Kind::Sortable => (SORTABLE_ALSW_CHECK_X2, SORTABLE_ALSW_DECODE_X2),
     };
 
-    // n*3/4 >= 24+4
-    while n >= 38 {
+    // n*3/4 >= 24+8
+    while n >= 43 {
         let x = s.v256_load_unaligned(src);
         let y = try_!(decode_ascii32(s, x, check_lut, decode_lut));
 
-        let (y1, y2) = y.to_v128x2();
-        s.v128_store_unaligned(dst, y1);
-        s.v128_store_unaligned(dst.add(12), y2);
+        store_bytes24(s, dst, y);
 
         src = src.add(32);
         dst = dst.add(24);
         n -= 32;
     }
 
+    #[cfg(feature = "stats")]
+    crate::stats::add_bulk(total - n);
+
     decode_fallback(src, dst, n, config)
 }
 
+#[inline(always)]
+pub(crate) unsafe fn decode_any_simd<S: SIMD256>(
+    s: S,
+    mut src: *const u8,
+    mut dst: *mut u8,
+    mut n: usize,
+    config: Config,
+) -> Result<(), Error> {
+    if config.constant_time {
+        return crate::ct::decode::<true>(src, dst, n, config, true);
+    }
+
+    // n*3/4 >= 24+8
+    while n >= 43 {
+        let x = s.v256_load_unaligned(src);
+        let x = url_safe_to_standard(s, x);
+        let y = try_!(decode_ascii32(s, x, STANDARD_ALSW_CHECK_X2, STANDARD_ALSW_DECODE_X2));
+
+        store_bytes24(s, dst, y);
+
+        src = src.add(32);
+        dst = dst.add(24);
+        n -= 32;
+    }
+
+    decode_any_fallback(src, dst, n, config)
+}
+
+/// Replaces `-` with `+` and `_` with `/`.
+#[inline(always)]
+fn url_safe_to_standard<S: SIMD256>(s: S, x: V256) -> V256 {
+    let m1 = s.u8x32_eq(x, s.u8x32_splat(b'-'));
+    let m2 = s.u8x32_eq(x, s.u8x32_splat(b'_'));
+    let d1 = s.v256_and(m1, s.u8x32_splat(b'-' ^ b'+'));
+    let d2 = s.v256_and(m2, s.u8x32_splat(b'_' ^ b'/'));
+    s.v256_xor(x, s.v256_or(d1, d2))
+}
+
+#[inline]
+pub(crate) unsafe fn decode_trusted_fallback(src: *const u8, dst: *mut u8, n: usize, config: Config) {
+    let result = decode_fallback(src, dst, n, config);
+    debug_assert!(result.is_ok());
+}
+
+#[inline(always)]
+pub(crate) unsafe fn decode_trusted_simd<S: SIMD256>(
+    s: S,
+    mut src: *const u8,
+    mut dst: *mut u8,
+    mut n: usize,
+    config: Config,
+) {
+    if config.constant_time {
+        return decode_trusted_fallback(src, dst, n, config);
+    }
+
+    let decode_lut = match config.kind {
+        Kind::Standard => STANDARD_ALSW_DECODE_X2,
+        Kind::UrlSafe => URL_SAFE_ALSW_DECODE_X2,
+        Kind::Sortable => SORTABLE_ALSW_DECODE_X2,
+    };
+
+    // n*3/4 >= 24+8
+    while n >= 43 {
+        let x = s.v256_load_unaligned(src);
+        let bits = vsimd::alsw::decode_ascii_xn_unchecked(s, x, decode_lut);
+        let y = merge_bits_x2(s, bits);
+
+        store_bytes24(s, dst, y);
+
+        src = src.add(32);
+        dst = dst.add(24);
+        n -= 32;
+    }
+
+    decode_trusted_fallback(src, dst, n, config);
+}
+
 #[inline(always)]
 fn merge_bits_x2<S: SIMD256>(s: S, x: V256) -> V256 {
     // x : {00aaaaaa|00bbbbbb|00cccccc|00dddddd} x8
@@ -279,6 +444,27 @@ fn merge_bits_x2<S: SIMD256>(s: S, x: V256) -> V256 {
     // {AAAB|BBCC|CDDD|0000|EEEF|FFGG|GHHH|0000}
 }
 
+/// Stores the 24 bytes from `merge_bits_x2`, which are the low 12 bytes of each lane.
+///
+/// It writes 32 bytes to `dst` on AVX2 and 28 bytes on the others.
+#[inline(always)]
+unsafe fn store_bytes24<S: SIMD256>(s: S, dst: *mut u8, y: V256) {
+    if matches_isa!(S, AVX2) {
+        // u32x8: [0, 1, 2, 4, 5, 6, 3, 7]
+        const PACK: V256 = V256::from_bytes([
+            0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, //
+            5, 0, 0, 0, 6, 0, 0, 0, 3, 0, 0, 0, 7, 0, 0, 0, //
+        ]);
+        let y = s.permute_cross_lane(y, PACK);
+        // {AAAB|BBCC|CDDD|EEEF|FFGG|GHHH|0000|0000}
+        s.v256_store_unaligned(dst, y);
+    } else {
+        let (y1, y2) = y.to_v128x2();
+        s.v128_store_unaligned(dst, y1);
+        s.v128_store_unaligned(dst.add(12), y2);
+    }
+}
+
 #[inline(always)]
 fn decode_ascii32<S: SIMD256>(s: S, x: V256, check: AlswLut<V256>, decode: AlswLut<V256>) -> Result<V256, Error> {
     let (c1, c2) = vsimd::alsw::decode_ascii_xn(s, x, check, decode);
@@ -0,0 +1,170 @@
+use crate::decode::decoded_length;
+use crate::{Base64, Config, Error, NulPolicy};
+
+use core::ops::Not;
+
+/// The progress of a [`Base64Decoder`] step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of bytes consumed from the input.
+    pub read: usize,
+    /// The number of bytes written to the output.
+    pub written: usize,
+    /// Whether the end of the base64 string has been reached.
+    pub done: bool,
+}
+
+/// A sans-io base64 decoder.
+///
+/// The caller feeds input buffers by [`consume`](Self::consume) and gets the decoded bytes in output buffers,
+/// so the decoder can be driven by any IO runtime.
+/// An incomplete quantum is carried over to the next call.
+///
+/// The end of the string is reached when a padded quantum is decoded,
+/// or when [`finish`](Self::finish) is called.
+///
+/// Whitespace is not skipped.
+/// After an error, the decoder should be discarded.
+///
+/// # Examples
+///
+/// ```
+/// let mut decoder = base64_simd::STANDARD.decoder();
+/// let mut buf = [0; 16];
+///
+/// let p1 = decoder.consume(b"aGVsbG8g", &mut buf).unwrap();
+/// assert_eq!((p1.read, p1.written, p1.done), (8, 6, false));
+///
+/// let p2 = decoder.consume(b"d29ybGQ=", &mut buf[p1.written..]).unwrap();
+/// assert_eq!((p2.read, p2.written, p2.done), (8, 5, true));
+///
+/// assert_eq!(&buf[..p1.written + p2.written], b"hello world");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Base64Decoder {
+    config: Config,
+    buf: [u8; 4],
+    buf_len: usize,
+    done: bool,
+}
+
+impl Base64Decoder {
+    /// Decodes as much of `input` as `output` can hold.
+    ///
+    /// The caller should call this function again with the unread input when `read < input.len()`.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the content of `input` is invalid.
+    /// + there is more input after the end of the string.
+    #[inline]
+    pub fn consume(&mut self, input: &[u8], output: &mut [u8]) -> Result<Progress, Error> {
+        let config = self.config;
+        let mut read = 0;
+        let mut written = 0;
+
+        loop {
+            if self.buf_len == 4 {
+                let (n, m) = decoded_length(&self.buf, config)?;
+                if output.len() - written < m {
+                    break;
+                }
+                unsafe {
+                    let dst = output.as_mut_ptr().add(written);
+                    crate::multiversion::decode::auto(self.buf.as_ptr(), dst, n, config)?;
+                }
+                written += m;
+                self.buf_len = 0;
+                if n < 4 {
+                    self.done = true;
+                }
+            }
+
+            if read == input.len() {
+                break;
+            }
+            ensure!(self.done.not());
+
+            if self.buf_len == 0 {
+                let quanta = (input.len() - read) / 4;
+                let k = quanta.saturating_sub(1).min((output.len() - written) / 3);
+                if k > 0 {
+                    unsafe {
+                        let src = input.as_ptr().add(read);
+                        let dst = output.as_mut_ptr().add(written);
+                        crate::multiversion::decode::auto(src, dst, k * 4, config)?;
+                    }
+                    read += k * 4;
+                    written += k * 3;
+                }
+            }
+
+            let k = (4 - self.buf_len).min(input.len() - read);
+            self.buf[self.buf_len..self.buf_len + k].copy_from_slice(&input[read..read + k]);
+            self.buf_len += k;
+            read += k;
+        }
+
+        Ok(Progress {
+            read,
+            written,
+            done: self.done,
+        })
+    }
+
+    /// Decodes the incomplete quantum carried over at the end of the input.
+    ///
+    /// `done` is `false` if `output` is too small, in which case the caller should try again with more space.
+    /// At most 3 bytes are written.
+    ///
+    /// # Errors
+    /// This function returns `Err` if the carried-over characters are not a valid end of the string.
+    #[inline]
+    pub fn finish(&mut self, output: &mut [u8]) -> Result<Progress, Error> {
+        let mut written = 0;
+        if self.buf_len > 0 {
+            let config = self.config;
+            let (n, m) = decoded_length(&self.buf[..self.buf_len], config)?;
+            if output.len() < m {
+                return Ok(Progress {
+                    read: 0,
+                    written: 0,
+                    done: false,
+                });
+            }
+            unsafe { crate::multiversion::decode::auto(self.buf.as_ptr(), output.as_mut_ptr(), n, config)? };
+            written = m;
+            self.buf_len = 0;
+        }
+        self.done = true;
+        Ok(Progress {
+            read: 0,
+            written,
+            done: true,
+        })
+    }
+
+    /// Returns `true` if the end of the base64 string has been reached.
+    #[inline]
+    #[must_use]
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl Base64 {
+    /// Creates a [`Base64Decoder`].
+    #[inline]
+    #[must_use]
+    pub fn decoder(&self) -> Base64Decoder {
+        Base64Decoder {
+            config: Config {
+                nul: NulPolicy::Error,
+                ..self.config
+            },
+            buf: [0; 4],
+            buf_len: 0,
+            done: false,
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use crate::{Config, Kind};
-use crate::{STANDARD_CHARSET, URL_SAFE_CHARSET};
+use crate::{SORTABLE_CHARSET, STANDARD_CHARSET, URL_SAFE_CHARSET};
 
-use vsimd::isa::{NEON, SSE2, WASM128};
+use vsimd::isa::{Fallback, NEON, SSE2, WASM128};
 use vsimd::tools::{read, write};
 use vsimd::vector::{V128, V256};
-use vsimd::{matches_isa, POD};
+use vsimd::{is_isa_type, matches_isa, POD};
 use vsimd::{Scalable, SIMD128, SIMD256};
 
 #[inline(always)]
@@ -19,6 +19,22 @@ pub(crate) const fn encoded_length_unchecked(len: usize, config: Config) -> usiz
     }
 }
 
+/// Returns `None` if the encoded length is greater than `isize::MAX`,
+/// which is the size limit of any slice or allocation.
+///
+/// On 32-bit targets, an input of about 1.5 GiB reaches the limit.
+#[inline(always)]
+pub(crate) const fn checked_encoded_length(len: usize, config: Config) -> Option<usize> {
+    if len > usize::MAX / 2 {
+        return None;
+    }
+    let m = encoded_length_unchecked(len, config);
+    if m > isize::MAX as usize {
+        return None;
+    }
+    Some(m)
+}
+
 #[inline(always)]
 unsafe fn encode_bits24(src: *const u8, dst: *mut u8, charset: *const u8) {
     let x = u32::from_be_bytes([0, read(src, 0), read(src, 1), read(src, 2)]);
@@ -44,45 +60,35 @@ unsafe fn encode_bits48(src: *const u8, dst: *mut u8, charset: *const u8) {
 }
 
 #[inline(always)]
-unsafe fn encode_extra(extra: usize, src: *const u8, dst: *mut u8, charset: *const u8, padding: bool) {
-    match extra {
-        0 => {}
-        1 => {
-            let x = read(src, 0);
-            let y1 = read(charset, (x >> 2) as usize);
-            let y2 = read(charset, ((x << 6) >> 2) as usize);
-            write(dst, 0, y1);
-            write(dst, 1, y2);
-            if padding {
-                write(dst, 2, b'=');
-                write(dst, 3, b'=');
+unsafe fn encode_extra(extra: usize, src: *const u8, dst: *mut u8, charset: *const u8, pad: Option<u8>) {
+    let k = vsimd::reference::encode_bits::<6>(src, extra, dst, charset);
+    if let Some(pad) = pad {
+        if extra != 0 {
+            let mut i = k;
+            while i < 4 {
+                write(dst, i, pad);
+                i += 1;
             }
         }
-        2 => {
-            let x1 = read(src, 0);
-            let x2 = read(src, 1);
-            let y1 = read(charset, (x1 >> 2) as usize);
-            let y2 = read(charset, (((x1 << 6) >> 2) | (x2 >> 4)) as usize);
-            let y3 = read(charset, ((x2 << 4) >> 2) as usize);
-            write(dst, 0, y1);
-            write(dst, 1, y2);
-            write(dst, 2, y3);
-            if padding {
-                write(dst, 3, b'=');
-            }
-        }
-        _ => core::hint::unreachable_unchecked(),
     }
 }
 
 #[inline]
 pub(crate) unsafe fn encode_fallback(mut src: *const u8, mut len: usize, mut dst: *mut u8, config: Config) {
+    #[cfg(feature = "stats")]
+    crate::stats::add_tail(len);
+
+    if config.constant_time {
+        return crate::ct::encode(src, len, dst, config);
+    }
+
     let kind = config.kind;
-    let padding = config.extra.padding();
+    let pad = config.pad();
 
     let charset = match kind {
         Kind::Standard => STANDARD_CHARSET.as_ptr(),
         Kind::UrlSafe => URL_SAFE_CHARSET.as_ptr(),
+        Kind::Sortable => SORTABLE_CHARSET.as_ptr(),
     };
 
     const L: usize = 4;
@@ -112,7 +118,7 @@ pub(crate) unsafe fn encode_fallback(mut src: *const u8, mut len: usize, mut dst
     }
     len %= 3;
 
-    encode_extra(len, src, dst, charset, padding);
+    encode_extra(len, src, dst, charset, pad);
 }
 
 #[inline(always)]
@@ -123,12 +129,23 @@ pub(crate) unsafe fn encode_simd<S: SIMD256>(
     mut dst: *mut u8,
     config: Config,
 ) {
+    if config.constant_time {
+        #[cfg(feature = "stats")]
+        crate::stats::add_tail(len);
+
+        return crate::ct::encode(src, len, dst, config);
+    }
+
     let kind = config.kind;
 
+    #[cfg(feature = "stats")]
+    let total = len;
+
     if len >= (6 + 24 + 4) {
         let (charset, shift_lut) = match kind {
             Kind::Standard => (STANDARD_CHARSET.as_ptr(), STANDARD_ENCODING_SHIFT_X2),
             Kind::UrlSafe => (URL_SAFE_CHARSET.as_ptr(), URL_SAFE_ENCODING_SHIFT_X2),
+            Kind::Sortable => (SORTABLE_CHARSET.as_ptr(), SORTABLE_ENCODING_SHIFT_X2),
         };
 
         for _ in 0..2 {
@@ -140,7 +157,7 @@ pub(crate) unsafe fn encode_simd<S: SIMD256>(
 
         while len >= (24 + 4) {
             let x = s.v256_load_unaligned(src.sub(4));
-            let y = encode_bytes24(s, x, shift_lut);
+            let y = encode_bytes24(s, x, shift_lut, kind);
             s.v256_store_unaligned(dst, y);
             src = src.add(24);
             dst = dst.add(32);
@@ -148,18 +165,30 @@ pub(crate) unsafe fn encode_simd<S: SIMD256>(
         }
     }
 
-    if len >= 12 + 4 {
+    if len >= 3 {
         let shift_lut = match kind {
             Kind::Standard => STANDARD_ENCODING_SHIFT,
             Kind::UrlSafe => URL_SAFE_ENCODING_SHIFT,
+            Kind::Sortable => SORTABLE_ENCODING_SHIFT,
         };
 
-        let x = s.v128_load_unaligned(src);
-        let y = encode_bytes12(s, x, shift_lut);
-        s.v128_store_unaligned(dst, y);
-        src = src.add(12);
-        dst = dst.add(16);
-        len -= 12;
+        while len >= 3 {
+            let k = if len >= 12 { 12 } else { len / 3 * 3 };
+            let x = s.v128_load_partial(src, k);
+            let y = encode_bytes12(s, x, shift_lut, kind);
+            s.v128_store_partial(dst, y, k / 3 * 4);
+            src = src.add(k);
+            dst = dst.add(k / 3 * 4);
+            len -= k;
+        }
+    }
+
+    #[cfg(feature = "stats")]
+    {
+        // The first 6 bytes of a long input are encoded by scalar code.
+        let head = if total >= (6 + 24 + 4) { 6 } else { 0 };
+        crate::stats::add_tail(head);
+        crate::stats::add_bulk(total - len - head);
     }
 
     encode_fallback(src, len, dst, config);
@@ -200,7 +229,7 @@ fn split_bits_x2<S: SIMD256>(s: S, x: V256) -> V256 {
         // {00aaaaaa|00bbbbbb|00cccccc|00dddddd} x8
     }
 
-    if matches_isa!(S, NEON | WASM128) {
+    if matches_isa!(S, NEON | WASM128) || is_isa_type!(S, Fallback) {
         let m1 = s.u32x8_splat(u32::from_le_bytes([0x00, 0xfc, 0x00, 0x00]));
         let x1 = s.u16x16_shr::<10>(s.v256_and(x0, m1));
         // x1: {00aaaaaa|000000000|00000000|00000000} x8
@@ -248,7 +277,7 @@ fn split_bits_x1<S: SIMD128>(s: S, x: V128) -> V128 {
         return s.v128_or(x3, x4);
     }
 
-    if matches_isa!(S, NEON | WASM128) {
+    if matches_isa!(S, NEON | WASM128) || is_isa_type!(S, Fallback) {
         let m1 = s.u32x4_splat(u32::from_le_bytes([0x00, 0xfc, 0x00, 0x00]));
         let x1 = s.u16x8_shr::<10>(s.v128_and(x0, m1));
 
@@ -288,16 +317,39 @@ const fn encoding_shift(charset: &'static [u8; 64]) -> V128 {
     V128::from_bytes(lut)
 }
 
+#[inline]
+const fn sortable_encoding_shift() -> V128 {
+    // 0        '-'   [0]
+    // 1~10     '0'   [1]
+    // 11~36    'A'   [2]
+    // 37       '_'   [3]
+    // 38~63    'a'   [4]
+
+    let mut lut = [0x80; 16];
+    lut[0] = b'-';
+    lut[1] = b'0' - 1;
+    lut[2] = b'A' - 11;
+    lut[3] = b'_' - 37;
+    lut[4] = b'a' - 38;
+    V128::from_bytes(lut)
+}
+
 const STANDARD_ENCODING_SHIFT: V128 = encoding_shift(STANDARD_CHARSET);
 const URL_SAFE_ENCODING_SHIFT: V128 = encoding_shift(URL_SAFE_CHARSET);
+const SORTABLE_ENCODING_SHIFT: V128 = sortable_encoding_shift();
 
 const STANDARD_ENCODING_SHIFT_X2: V256 = STANDARD_ENCODING_SHIFT.x2();
 const URL_SAFE_ENCODING_SHIFT_X2: V256 = URL_SAFE_ENCODING_SHIFT.x2();
+const SORTABLE_ENCODING_SHIFT_X2: V256 = SORTABLE_ENCODING_SHIFT.x2();
 
 #[inline(always)]
-fn encode_values<S: Scalable<V>, V: POD>(s: S, x: V, shift_lut: V) -> V {
+fn encode_values<S: Scalable<V>, V: POD>(s: S, x: V, shift_lut: V, kind: Kind) -> V {
     // x: {00aaaaaa|00bbbbbb|00cccccc|00dddddd} xn
 
+    if let Kind::Sortable = kind {
+        return encode_values_sortable(s, x, shift_lut);
+    }
+
     let x1 = s.u8xn_sub_sat(x, s.u8xn_splat(51));
     // 0~25    => 0
     // 26~51   => 0
@@ -320,23 +372,73 @@ fn encode_values<S: Scalable<V>, V: POD>(s: S, x: V, shift_lut: V) -> V {
 }
 
 #[inline(always)]
-fn encode_bytes24<S: SIMD256>(s: S, x: V256, shift_lut: V256) -> V256 {
+fn encode_values_sortable<S: Scalable<V>, V: POD>(s: S, x: V, shift_lut: V) -> V {
+    // x: {00aaaaaa|00bbbbbb|00cccccc|00dddddd} xn
+
+    let m1 = s.i8xn_lt(s.i8xn_splat(0), x);
+    let m2 = s.i8xn_lt(s.i8xn_splat(10), x);
+    let m3 = s.i8xn_lt(s.i8xn_splat(36), x);
+    let m4 = s.i8xn_lt(s.i8xn_splat(37), x);
+    let m = s.u8xn_add(s.u8xn_add(m1, m2), s.u8xn_add(m3, m4));
+    let idx = s.u8xn_sub(s.u8xn_splat(0), m);
+    // 0       => 0
+    // 1~10    => 1
+    // 11~36   => 2
+    // 37      => 3
+    // 38~63   => 4
+
+    let shift = s.u8x16xn_swizzle(shift_lut, idx);
+    s.u8xn_add(x, shift)
+    // {{ascii}} xn
+}
+
+#[inline(always)]
+fn encode_bytes24<S: SIMD256>(s: S, x: V256, shift_lut: V256, kind: Kind) -> V256 {
     // x: {????|AAAB|BBCC|CDDD|EEEF|FFGG|GHHH|????}
 
     let values = split_bits_x2(s, x);
     // values: {00aaaaaa|00bbbbbb|00cccccc|00dddddd} x8
 
-    encode_values(s, values, shift_lut)
+    encode_values(s, values, shift_lut, kind)
     // {{ascii}} x32
 }
 
 #[inline(always)]
-fn encode_bytes12<S: SIMD256>(s: S, x: V128, shift_lut: V128) -> V128 {
+fn encode_bytes12<S: SIMD256>(s: S, x: V128, shift_lut: V128, kind: Kind) -> V128 {
     // x: {AAAB|BBCC|CDDD|????}
 
     let values = split_bits_x1(s, x);
     // values: {00aaaaaa|00bbbbbb|00cccccc|00dddddd} x4
 
-    encode_values(s, values, shift_lut)
+    encode_values(s, values, shift_lut, kind)
     // {{ascii}} x16
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{SORTABLE, STANDARD, URL_SAFE_NO_PAD};
+
+    use vsimd::isa::InstructionSet;
+
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    fn fallback_isa() {
+        let s = unsafe { Fallback::new() };
+        let src: Vec<u8> = (0..200u32).map(|i| (i * 37 + 11) as u8).collect();
+
+        for base64 in [STANDARD, URL_SAFE_NO_PAD, SORTABLE] {
+            for n in 0..src.len() {
+                let len = encoded_length_unchecked(n, base64.config);
+                let mut expected = vec![0u8; len];
+                let mut ans = vec![0u8; len];
+                unsafe {
+                    encode_fallback(src.as_ptr(), n, expected.as_mut_ptr(), base64.config);
+                    encode_simd(s, src.as_ptr(), n, ans.as_mut_ptr(), base64.config);
+                }
+                assert_eq!(ans, expected, "n = {n}");
+            }
+        }
+    }
+}
@@ -1,26 +1,144 @@
 use core::fmt;
 
 /// Base64 Error
-pub struct Error(());
+pub struct Error {
+    kind: ErrorKind,
+    capacity: Option<CapacityError>,
+    index: Option<usize>,
+}
+
+/// The kind of a [`Error`]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The length of the input can not be produced by the base64 variant.
+    ///
+    /// For example, a padded input whose length is not a multiple of 4,
+    /// or an input whose length (without padding) is `4k+1`.
+    InvalidLength,
+
+    /// The input or the output is invalid for other reasons.
+    InvalidData,
+
+    /// The output buffer is too small.
+    ///
+    /// [`Error::capacity`] returns the required and provided sizes.
+    InsufficientCapacity,
+}
+
+/// The sizes of an output buffer which is too small.
+///
+/// See [`Error::capacity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    /// The number of bytes required by the output.
+    ///
+    /// The functions which decode the input in parts, such as [`Base64::decode_mime`](crate::Base64::decode_mime),
+    /// may stop at the first part which does not fit, so this can be a lower bound.
+    pub required: usize,
+    /// The number of bytes provided by the caller.
+    pub provided: usize,
+}
 
 impl Error {
     #[inline(always)]
     pub(crate) const fn new() -> Self {
-        Error(())
+        Self::with_kind(ErrorKind::InvalidData)
+    }
+
+    #[inline(always)]
+    pub(crate) const fn with_kind(kind: ErrorKind) -> Self {
+        Error {
+            kind,
+            capacity: None,
+            index: None,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn at(index: usize) -> Self {
+        Error {
+            kind: ErrorKind::InvalidData,
+            capacity: None,
+            index: Some(index),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn insufficient_capacity(required: usize, provided: usize) -> Self {
+        Error {
+            kind: ErrorKind::InsufficientCapacity,
+            capacity: Some(CapacityError { required, provided }),
+            index: None,
+        }
+    }
+
+    /// Adds `n` bytes, which were written before a nested call, to the sizes of a capacity error.
+    #[inline]
+    pub(crate) fn offset_capacity(mut self, n: usize) -> Self {
+        if let Some(capacity) = &mut self.capacity {
+            capacity.required += n;
+            capacity.provided += n;
+        }
+        self
+    }
+
+    /// Returns the kind of the error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the required and provided sizes if the output buffer is too small.
+    ///
+    /// Returns `None` for the other kinds of errors.
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> Option<CapacityError> {
+        self.capacity
+    }
+
+    /// Returns the index of the first offending character in the input.
+    ///
+    /// Only the functions which document it report the index. The others return `None`.
+    #[inline]
+    #[must_use]
+    pub const fn index(&self) -> Option<usize> {
+        self.index
     }
 }
 
 impl fmt::Debug for Error {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        <str as fmt::Debug>::fmt("Base64Error", f)
+        match (self.capacity, self.index) {
+            (Some(ref capacity), _) => f
+                .debug_struct("Base64Error")
+                .field("kind", &self.kind)
+                .field("capacity", capacity)
+                .finish(),
+            (None, Some(index)) => f
+                .debug_struct("Base64Error")
+                .field("kind", &self.kind)
+                .field("index", &index)
+                .finish(),
+            (None, None) => <str as fmt::Debug>::fmt("Base64Error", f),
+        }
     }
 }
 
 impl fmt::Display for Error {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        <str as fmt::Display>::fmt("Base64Error", f)
+        match (self.capacity, self.index) {
+            (Some(CapacityError { required, provided }), _) => write!(
+                f,
+                "Base64Error: insufficient capacity, {required} bytes required, {provided} bytes provided"
+            ),
+            (None, Some(index)) => write!(f, "Base64Error: invalid character at index {index}"),
+            (None, None) => <str as fmt::Display>::fmt("Base64Error", f),
+        }
     }
 }
 
@@ -34,6 +152,21 @@ macro_rules! ensure {
             return Err($crate::error::Error::new());
         }
     };
+    ($cond:expr, $kind:ident) => {
+        if !$cond {
+            return Err($crate::error::Error::with_kind($crate::error::ErrorKind::$kind));
+        }
+    };
+}
+
+/// Returns an [`ErrorKind::InsufficientCapacity`] error if `provided < required`.
+macro_rules! ensure_capacity {
+    ($provided:expr, $required:expr) => {{
+        let (provided, required): (usize, usize) = ($provided, $required);
+        if provided < required {
+            return Err($crate::error::Error::insufficient_capacity(required, provided));
+        }
+    }};
 }
 
 #[allow(unused_macros)]
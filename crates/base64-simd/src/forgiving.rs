@@ -9,6 +9,19 @@ use core::ptr::copy_nonoverlapping;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+/// Normalizes a base64 string inplace as the first step of forgiving decoding.
+///
+/// This function removes all ASCII whitespace from `data` and returns the remaining part.
+/// Other characters, including padding and the URL-safe characters, are kept as is.
+/// Passing the result to [`STANDARD`](crate::STANDARD) decoding functions
+/// behaves like [`forgiving_decode_inplace`] except that padding is required.
+///
+/// See <https://infra.spec.whatwg.org/#forgiving-base64>
+#[inline]
+pub fn forgiving_normalize(data: &mut [u8]) -> &mut [u8] {
+    remove_ascii_whitespace_inplace(data)
+}
+
 /// Forgiving decodes a base64 string to bytes and writes inplace.
 ///
 /// This function uses the standard charset.
@@ -19,7 +32,7 @@ use alloc::vec::Vec;
 /// This function returns `Err` if the content of `data` is invalid.
 #[inline]
 pub fn forgiving_decode_inplace(data: &mut [u8]) -> Result<&mut [u8], Error> {
-    let data = remove_ascii_whitespace_inplace(data);
+    let data = forgiving_normalize(data);
     STANDARD_FORGIVING.decode_inplace(data)
 }
 
@@ -36,7 +49,7 @@ pub fn forgiving_decode_inplace(data: &mut [u8]) -> Result<&mut [u8], Error> {
 ///
 #[inline]
 pub fn forgiving_decode<'d>(src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
-    ensure!(src.len() <= dst.len());
+    ensure_capacity!(dst.len(), src.len());
 
     let pos = find_non_ascii_whitespace(src);
     debug_assert!(pos <= src.len());
@@ -139,6 +152,13 @@ mod tests {
 
             let mut buf = src.to_owned().into_bytes();
 
+            let normalized = forgiving_normalize(&mut buf);
+            assert!(!normalized.iter().any(u8::is_ascii_whitespace));
+            let ans = crate::STANDARD_FORGIVING.decode_inplace(normalized).unwrap();
+            assert_eq!(ans, expected);
+
+            let mut buf = src.to_owned().into_bytes();
+
             let ans = forgiving_decode_inplace(&mut buf).unwrap();
             assert_eq!(ans, expected);
 
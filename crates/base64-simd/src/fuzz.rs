@@ -0,0 +1,78 @@
+//! Invariant checks for fuzz targets.
+//!
+//! The functions return `false` when an invariant is violated.
+//! A fuzz target is expected to assert on the result.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64_simd::fuzz;
+//!
+//! let data = b"hello world";
+//! assert!(fuzz::round_trip(data, &base64_simd::STANDARD));
+//! assert!(fuzz::decode_matches_reference(data));
+//! ```
+
+use crate::decode::{decode_fallback, decoded_length};
+use crate::{Base64, Config, SORTABLE, STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Checks that decoding the encoded `data` gives back `data`.
+///
+/// The encoded string must also pass [`Base64::check`] and [`Base64::eq_decoded`].
+#[inline]
+#[must_use]
+pub fn round_trip(data: &[u8], base64: &Base64) -> bool {
+    let encoded: Vec<u8> = base64.encode_type(data);
+    if encoded.len() != base64.encoded_length(data.len()) {
+        return false;
+    }
+    if base64.check(&encoded).is_err() || !base64.eq_decoded(&encoded, data) {
+        return false;
+    }
+    match base64.decode_to_vec(&encoded) {
+        Ok(decoded) => decoded == data,
+        Err(_) => false,
+    }
+}
+
+/// Checks that the dispatched decoder agrees with the scalar decoder on arbitrary `input`.
+///
+/// Every built-in variant and its constant-time counterpart are tested.
+/// Both decoders must accept or reject the input together, and produce the same bytes if accepted.
+/// [`Base64::check`] must agree with them.
+#[inline]
+#[must_use]
+pub fn decode_matches_reference(input: &[u8]) -> bool {
+    let variants = [
+        STANDARD,
+        URL_SAFE,
+        STANDARD_NO_PAD,
+        URL_SAFE_NO_PAD,
+        SORTABLE,
+        STANDARD.constant_time(),
+        URL_SAFE.constant_time(),
+        STANDARD_NO_PAD.constant_time(),
+        URL_SAFE_NO_PAD.constant_time(),
+        SORTABLE.constant_time(),
+    ];
+    variants.iter().all(|base64| {
+        let expected = reference_decode(base64, input);
+        let actual = base64.decode_to_vec(input).ok();
+        actual == expected && base64.check(input).is_ok() == expected.is_some()
+    })
+}
+
+/// Decodes with the scalar table-based decoder, which is the reference for all the other paths.
+fn reference_decode(base64: &Base64, input: &[u8]) -> Option<Vec<u8>> {
+    let config = Config {
+        constant_time: false,
+        ..base64.config
+    };
+    let (n, m) = decoded_length(input, config).ok()?;
+    let mut buf = vec![0u8; m];
+    let result = unsafe { decode_fallback(input.as_ptr(), buf.as_mut_ptr(), n, config) };
+    result.ok().map(|()| buf)
+}
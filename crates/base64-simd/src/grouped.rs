@@ -0,0 +1,131 @@
+use crate::encode::encoded_length_unchecked;
+use crate::{Base64, Error, Out};
+
+use vsimd::tools::{slice_mut, slice_parts};
+
+use core::ptr::{copy, copy_nonoverlapping};
+
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+
+impl Base64 {
+    /// Calculates the encoded length with a separator of `sep_len` bytes after every `group` characters.
+    ///
+    /// There is no separator after the last group.
+    ///
+    /// Returns `None` if the length overflows.
+    ///
+    /// # Panics
+    /// This function asserts that `group > 0`.
+    #[inline]
+    #[must_use]
+    pub const fn grouped_encoded_length(&self, n: usize, group: usize, sep_len: usize) -> Option<usize> {
+        assert!(group > 0);
+        let m = match self.checked_encoded_length(n) {
+            Some(m) => m,
+            None => return None,
+        };
+        if m == 0 {
+            return Some(0);
+        }
+        match ((m - 1) / group).checked_mul(sep_len) {
+            Some(k) => m.checked_add(k),
+            None => None,
+        }
+    }
+
+    /// Encodes bytes to a base64 string, inserting `sep` after every `group` characters.
+    ///
+    /// There is no separator after the last group.
+    /// This covers the common wrapping formats:
+    /// + MIME: `group = 76`, `sep = b"\r\n"`
+    /// + PEM: `group = 64`, `sep = b"\n"`
+    /// + space-separated groups, such as `group = 4`, `sep = b" "`
+    ///
+    /// The output length is [`grouped_encoded_length`](Self::grouped_encoded_length).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_simd::{AsOut, STANDARD};
+    ///
+    /// let mut buf = [0u8; 64];
+    /// let ans = STANDARD.encode_grouped(b"hello world", buf.as_mut_slice().as_out(), 4, b" ").unwrap();
+    /// assert_eq!(ans, b"aGVs bG8g d29y bGQ=");
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns `Err` if the length of `dst` is not enough.
+    ///
+    /// # Panics
+    /// This function asserts that `group > 0`.
+    #[inline]
+    pub fn encode_grouped<'d>(
+        &self,
+        src: &[u8],
+        mut dst: Out<'d, [u8]>,
+        group: usize,
+        sep: &[u8],
+    ) -> Result<&'d mut [u8], Error> {
+        let total = self.grouped_encoded_length(src.len(), group, sep.len());
+        let total = match total {
+            Some(total) => total,
+            None => return Err(Error::new()),
+        };
+        ensure_capacity!(dst.len(), total);
+
+        unsafe {
+            let m = encoded_length_unchecked(src.len(), self.config);
+            let dst = dst.as_mut_ptr();
+
+            // Encode to the end of the output, then move the groups forward to their places.
+            // The destination of each group never passes its source,
+            // and the separators after it only overwrite the characters which have been moved.
+            let base = dst.add(total - m);
+            let (src, len) = slice_parts(src);
+            crate::multiversion::encode::auto(src, len, base, self.config);
+
+            if !sep.is_empty() {
+                let mut i = 0;
+                let mut cur = dst;
+                while i < m {
+                    let k = group.min(m - i);
+                    copy(base.add(i), cur, k);
+                    cur = cur.add(k);
+                    i += k;
+                    if i < m {
+                        copy_nonoverlapping(sep.as_ptr(), cur, sep.len());
+                        cur = cur.add(sep.len());
+                    }
+                }
+                debug_assert_eq!(cur, dst.add(total));
+            }
+
+            Ok(slice_mut(dst, total))
+        }
+    }
+
+    /// Encodes bytes to a base64 string, inserting `sep` after every `group` characters,
+    /// and returns a new [`String`].
+    ///
+    /// See [`encode_grouped`](Self::encode_grouped).
+    ///
+    /// # Panics
+    /// This function panics if `group == 0` or the length of the output overflows.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn encode_grouped_to_string(&self, data: impl AsRef<[u8]>, group: usize, sep: &str) -> String {
+        let data = data.as_ref();
+        let total = self.grouped_encoded_length(data.len(), group, sep.len()).unwrap();
+
+        let mut buf: Vec<u8> = Vec::with_capacity(total);
+        let dst = Out::from_uninit_slice(&mut buf.spare_capacity_mut()[..total]);
+        let len = self.encode_grouped(data, dst, group, sep.as_bytes()).unwrap().len();
+        unsafe {
+            buf.set_len(len);
+            String::from_utf8_unchecked(buf)
+        }
+    }
+}
@@ -1,10 +1,10 @@
 use crate::decode::decoded_length;
-use crate::encode::encoded_length_unchecked;
 use crate::{AppendBase64Decode, AppendBase64Encode};
 use crate::{Base64, Error};
+use crate::{DecodeOutput, EncodeOutput};
 use crate::{FromBase64Decode, FromBase64Encode};
 
-use vsimd::tools::{alloc_uninit_bytes, assume_init, boxed_str, slice_parts};
+use vsimd::tools::{alloc_uninit_bytes, assume_init, boxed_str, slice, slice_parts};
 
 use alloc::boxed::Box;
 use alloc::string::String;
@@ -17,8 +17,7 @@ fn encode_to_boxed_str(base64: &Base64, data: &[u8]) -> Box<str> {
     }
 
     unsafe {
-        let m = encoded_length_unchecked(data.len(), base64.config);
-        assert!(m <= usize::MAX / 2);
+        let m = base64.encoded_length(data.len());
 
         let mut buf = alloc_uninit_bytes(m);
 
@@ -32,6 +31,36 @@ fn encode_to_boxed_str(base64: &Base64, data: &[u8]) -> Box<str> {
     }
 }
 
+/// Encodes `data` in chunks and passes each encoded chunk to `update` while it is still in cache.
+#[inline]
+pub(crate) fn encode_to_boxed_str_with(base64: &Base64, data: &[u8], mut update: impl FnMut(&[u8])) -> Box<str> {
+    // A multiple of 3, so that only the last chunk can be padded.
+    const CHUNK: usize = 3 * 1024;
+
+    if data.is_empty() {
+        return Box::from("");
+    }
+
+    unsafe {
+        let m = base64.encoded_length(data.len());
+
+        let mut buf = alloc_uninit_bytes(m);
+
+        {
+            let mut dst: *mut u8 = buf.as_mut_ptr().cast();
+            for chunk in data.chunks(CHUNK) {
+                let (src, len) = slice_parts(chunk);
+                crate::multiversion::encode::auto(src, len, dst, base64.config);
+                let n = base64.encoded_length(len);
+                update(slice(dst, n));
+                dst = dst.add(n);
+            }
+        }
+
+        boxed_str(assume_init(buf))
+    }
+}
+
 #[inline]
 fn encode_append_vec(base64: &Base64, src: &[u8], buf: &mut Vec<u8>) {
     if src.is_empty() {
@@ -39,8 +68,7 @@ fn encode_append_vec(base64: &Base64, src: &[u8], buf: &mut Vec<u8>) {
     }
 
     unsafe {
-        let m = encoded_length_unchecked(src.len(), base64.config);
-        assert!(m <= usize::MAX / 2);
+        let m = base64.encoded_length(src.len());
 
         buf.reserve_exact(m);
         let prev_len = buf.len();
@@ -61,8 +89,19 @@ fn decode_to_boxed_bytes(base64: &Base64, data: &[u8]) -> Result<Box<[u8]>, Erro
         return Ok(Box::from([]));
     }
 
+    if base64.skips_nul(data) {
+        let mut buf = data.to_vec();
+        let len = base64.decode_inplace(&mut buf)?.len();
+        buf.truncate(len);
+        return Ok(buf.into_boxed_slice());
+    }
+
     unsafe {
         let (n, m) = decoded_length(data, base64.config)?;
+        if m == 0 {
+            // the input may stop at a NUL byte
+            return Ok(Box::from([]));
+        }
 
         // safety: 0 < m < isize::MAX
         let mut buf = alloc_uninit_bytes(m);
@@ -83,6 +122,12 @@ fn decode_append_vec(base64: &Base64, src: &[u8], buf: &mut Vec<u8>) -> Result<(
         return Ok(());
     }
 
+    if base64.skips_nul(src) {
+        let mut tmp = src.to_vec();
+        buf.extend_from_slice(base64.decode_inplace(&mut tmp)?);
+        return Ok(());
+    }
+
     unsafe {
         let (n, m) = decoded_length(src, base64.config)?;
 
@@ -173,3 +218,33 @@ impl AppendBase64Decode for Vec<u8> {
         decode_append_vec(base64, src, dst)
     }
 }
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl EncodeOutput for &mut Vec<u8> {
+    type Output = ();
+
+    #[inline]
+    fn encode_output(self, base64: &Base64, src: &[u8]) -> Self::Output {
+        encode_append_vec(base64, src, self);
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl EncodeOutput for &mut String {
+    type Output = ();
+
+    #[inline]
+    fn encode_output(self, base64: &Base64, src: &[u8]) -> Self::Output {
+        unsafe { encode_append_vec(base64, src, self.as_mut_vec()) };
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl DecodeOutput for &mut Vec<u8> {
+    type Output = Result<(), Error>;
+
+    #[inline]
+    fn decode_output(self, base64: &Base64, src: &[u8]) -> Self::Output {
+        decode_append_vec(base64, src, self)
+    }
+}
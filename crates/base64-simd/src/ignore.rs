@@ -0,0 +1,148 @@
+use vsimd::mask::mask8x16_all;
+use vsimd::tools::read;
+use vsimd::vector::V128;
+use vsimd::SIMD256;
+
+/// Selects the bit of a high nibble in `0..8` in the first half of the classifier.
+const HI1: V128 = V128::from_bytes([1, 2, 4, 8, 16, 32, 64, 128, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+/// Selects the bit of a high nibble in `8..16` in the second half of the classifier.
+const HI2: V128 = V128::from_bytes([0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 4, 8, 16, 32, 64, 128]);
+
+/// An ignore table split by nibbles.
+///
+/// The bit `h % 8` of `lo[h / 8][l]` is set if the byte `(h << 4) | l` is ignorable,
+/// so every byte value is classified exactly.
+struct Classifier {
+    lo: [V128; 2],
+}
+
+impl Classifier {
+    #[inline(always)]
+    fn new(ignore: &[bool; 256]) -> Self {
+        let mut lo = [[0u8; 16]; 2];
+        for (x, &ignorable) in ignore.iter().enumerate() {
+            if ignorable {
+                let (h, l) = (x >> 4, x & 0x0f);
+                lo[h / 8][l] |= 1 << (h % 8);
+            }
+        }
+        Self {
+            lo: [V128::from_bytes(lo[0]), V128::from_bytes(lo[1])],
+        }
+    }
+
+    /// Returns `0xff` for the significant bytes of `x` and `0x00` for the ignorable ones.
+    #[inline(always)]
+    fn significant<S: SIMD256>(&self, s: S, x: V128) -> V128 {
+        let nibble = s.u8x16_splat(0x0f);
+        let l = s.v128_and(x, nibble);
+        let h = s.v128_and(s.u16x8_shr::<4>(x), nibble);
+
+        let m1 = s.v128_and(s.u8x16_swizzle(self.lo[0], l), s.u8x16_swizzle(HI1, h));
+        let m2 = s.v128_and(s.u8x16_swizzle(self.lo[1], l), s.u8x16_swizzle(HI2, h));
+        s.u8x16_eq(s.v128_or(m1, m2), s.v128_create_zero())
+    }
+}
+
+#[inline(always)]
+unsafe fn filter_ignoring_short(src: *const u8, len: usize, dst: *mut u8, ignore: &[bool; 256]) -> usize {
+    let mut j = 0;
+    for i in 0..len {
+        let x = read(src, i);
+        if !ignore[x as usize] {
+            dst.add(j).write(x);
+            j += 1;
+        }
+    }
+    j
+}
+
+/// Copies the significant bytes of `src` to `dst` and returns their number.
+#[inline(always)]
+pub(crate) unsafe fn filter_ignoring_fallback(src: *const u8, len: usize, dst: *mut u8, ignore: &[bool; 256]) -> usize {
+    filter_ignoring_short(src, len, dst, ignore)
+}
+
+/// Copies the significant bytes of `src` to `dst` and returns their number.
+///
+/// The blocks without ignorable bytes are copied as a whole.
+/// `dst` must be able to hold all the significant bytes of `src`.
+#[inline(always)]
+pub(crate) unsafe fn filter_ignoring_simd<S: SIMD256>(
+    s: S,
+    src: *const u8,
+    len: usize,
+    dst: *mut u8,
+    ignore: &[bool; 256],
+) -> usize {
+    if len < 16 {
+        return filter_ignoring_short(src, len, dst, ignore);
+    }
+
+    let classifier = Classifier::new(ignore);
+
+    let mut i = 0;
+    let mut j = 0;
+    while len - i >= 16 {
+        let x = s.v128_load_unaligned(src.add(i));
+        if mask8x16_all(s, classifier.significant(s, x)) {
+            s.v128_store_unaligned(dst.add(j), x);
+            j += 16;
+        } else {
+            j += filter_ignoring_short(src.add(i), 16, dst.add(j), ignore);
+        }
+        i += 16;
+    }
+
+    j + filter_ignoring_short(src.add(i), len - i, dst.add(j), ignore)
+}
+
+#[inline(always)]
+unsafe fn count_significant_short(src: *const u8, len: usize, ignore: &[bool; 256]) -> usize {
+    let mut count = 0;
+    for i in 0..len {
+        count += usize::from(!ignore[read(src, i) as usize]);
+    }
+    count
+}
+
+/// Counts the significant bytes of `src`.
+#[inline(always)]
+pub(crate) unsafe fn count_significant_fallback(src: *const u8, len: usize, ignore: &[bool; 256]) -> usize {
+    count_significant_short(src, len, ignore)
+}
+
+/// Counts the significant bytes of `src`.
+///
+/// The lanes of the classifier mask are summed per byte and reduced before they can overflow.
+#[inline(always)]
+pub(crate) unsafe fn count_significant_simd<S: SIMD256>(
+    s: S,
+    src: *const u8,
+    len: usize,
+    ignore: &[bool; 256],
+) -> usize {
+    if len < 16 {
+        return count_significant_short(src, len, ignore);
+    }
+
+    let classifier = Classifier::new(ignore);
+
+    let mut i = 0;
+    let mut count = 0;
+    while len - i >= 16 {
+        let blocks = ((len - i) / 16).min(255);
+
+        let mut acc = s.v128_create_zero();
+        for _ in 0..blocks {
+            let x = s.v128_load_unaligned(src.add(i));
+            // The mask is `-1` in the significant lanes.
+            acc = s.u8x16_sub(acc, classifier.significant(s, x));
+            i += 16;
+        }
+        count += acc.as_bytes().iter().map(|&n| usize::from(n)).sum::<usize>();
+    }
+
+    count + count_significant_short(src.add(i), len - i, ignore)
+}
@@ -0,0 +1,46 @@
+use crate::decode::decoded_length;
+use crate::{Base64, Error};
+
+/// The shape of a valid base64 string, reported by [`Base64::inspect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeShape {
+    /// The number of bytes that the string decodes to.
+    pub decoded_len: usize,
+    /// The number of characters in the string.
+    pub input_len: usize,
+    /// The number of padding characters at the end of the string, which is 0, 1 or 2.
+    pub pad_chars: u8,
+    /// The number of characters which carry data, that is, `input_len - pad_chars`.
+    pub significant_chars: usize,
+}
+
+impl Base64 {
+    /// Validates a base64 string without decoding it, and reports its shape.
+    ///
+    /// The string is checked once. The result tells the exact output length for allocation
+    /// and how the string is padded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let shape = base64_simd::STANDARD.inspect(b"aGVsbG8=").unwrap();
+    /// assert_eq!(shape.decoded_len, 5);
+    /// assert_eq!(shape.input_len, 8);
+    /// assert_eq!(shape.pad_chars, 1);
+    /// assert_eq!(shape.significant_chars, 7);
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid.
+    #[inline]
+    pub fn inspect(&self, data: &[u8]) -> Result<DecodeShape, Error> {
+        let (n, m) = decoded_length(data, self.config)?;
+        unsafe { crate::multiversion::check::auto(data.as_ptr(), n, self.config)? };
+        Ok(DecodeShape {
+            decoded_len: m,
+            input_len: data.len(),
+            pad_chars: (data.len() - n) as u8,
+            significant_chars: n,
+        })
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{AsOut, Base64, Config, Error, NulPolicy};
+
+use core::iter::FusedIterator;
+
+/// An iterator which decodes a base64 string lazily, one quantum at a time.
+///
+/// It yields the decoded bytes without allocating any output buffer.
+/// The first invalid quantum is yielded as an `Err` item, which ends the iteration.
+/// [`position`](Self::position) then returns the offset of that quantum in the input.
+///
+/// The input is split into quanta of 4 characters, so it must not contain whitespace.
+///
+/// # Examples
+///
+/// ```
+/// let base64 = base64_simd::STANDARD;
+///
+/// let bytes: Result<Vec<u8>, _> = base64.decode_iter(b"aGVsbG8=").collect();
+/// assert_eq!(bytes.unwrap(), b"hello");
+///
+/// let mut iter = base64.decode_iter(b"aGVs*G8=");
+/// assert_eq!(iter.by_ref().take(3).collect::<Result<Vec<u8>, _>>().unwrap(), b"hel");
+/// assert!(iter.next().unwrap().is_err());
+/// assert_eq!(iter.position(), 4);
+/// assert!(iter.next().is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DecodeIter<'a> {
+    config: Config,
+    src: &'a [u8],
+    pos: usize,
+    buf: [u8; 3],
+    buf_pos: usize,
+    buf_len: usize,
+    failed: bool,
+}
+
+impl DecodeIter<'_> {
+    /// Returns the offset of the next quantum to decode in the input.
+    ///
+    /// After an `Err` item, it is the offset of the invalid quantum.
+    #[inline]
+    #[must_use]
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Decodes the next quantum into the buffer.
+    fn refill(&mut self) -> Result<(), Error> {
+        let rest = &self.src[self.pos..];
+        let is_last = rest.len() <= 4;
+        let quantum = if is_last { rest } else { &rest[..4] };
+
+        // Padding is only allowed at the end of the input.
+        ensure!(is_last || !quantum.contains(&self.config.pad));
+
+        let base64 = Base64 { config: self.config };
+        let len = base64.decode(quantum, self.buf.as_mut_slice().as_out())?.len();
+
+        self.pos += quantum.len();
+        self.buf_pos = 0;
+        self.buf_len = len;
+        Ok(())
+    }
+}
+
+impl Iterator for DecodeIter<'_> {
+    type Item = Result<u8, Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.buf_pos == self.buf_len {
+            if self.failed || self.pos == self.src.len() {
+                return None;
+            }
+            if let Err(e) = self.refill() {
+                self.failed = true;
+                return Some(Err(e));
+            }
+        }
+        let x = self.buf[self.buf_pos];
+        self.buf_pos += 1;
+        Some(Ok(x))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buf_len - self.buf_pos;
+        if self.failed {
+            return (buffered, Some(buffered));
+        }
+        let rest = self.src.len() - self.pos;
+        let upper = buffered.checked_add((rest / 4) * 3 + (rest % 4).min(3));
+        (buffered, upper)
+    }
+}
+
+impl FusedIterator for DecodeIter<'_> {}
+
+impl Base64 {
+    /// Creates an iterator which decodes `src` lazily.
+    ///
+    /// See [`DecodeIter`].
+    #[inline]
+    #[must_use]
+    pub fn decode_iter<'a>(&self, src: &'a [u8]) -> DecodeIter<'a> {
+        DecodeIter {
+            config: Config {
+                nul: NulPolicy::Error,
+                ..self.config
+            },
+            src,
+            pos: 0,
+            buf: [0; 3],
+            buf_pos: 0,
+            buf_len: 0,
+            failed: false,
+        }
+    }
+}
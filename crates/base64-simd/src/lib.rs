@@ -17,9 +17,33 @@
 //! ```
 //!
 #![doc=vsimd::shared_docs!()]
+//!
+//! # Forced backend
+//!
+//! The backend can be pinned at compile time by one of the mutually exclusive feature flags
+//! `force-avx2`, `force-sse41`, `force-neon` and `force-scalar`.
+//! The APIs then call the chosen code path directly, without runtime detection.
+//!
+//! + `force-avx2` selects AVX2 on x86.
+//! + `force-sse41` selects SSSE3 on x86, which is the best code path not above SSE4.1.
+//! + `force-neon` selects NEON on aarch64 (and arm with the feature flag `unstable`).
+//! + `force-scalar` selects the fallback functions on all targets.
+//!
+//! The flags for other architectures are ignored.
+//! The forced instruction set must be supported by every CPU which runs the program.
+//! Otherwise the program may crash with an illegal instruction.
+//!
+//! # Forgiving decoding
+//!
+//! The feature flag `forgiving` is enabled by default.
+//! It provides the [WHATWG forgiving-base64](https://infra.spec.whatwg.org/#forgiving-base64) functions,
+//! such as `forgiving_decode` and `forgiving_decode_inplace`.
+//! Size-sensitive users can disable the default features to drop them,
+//! while the other encoding and decoding APIs are kept.
 //
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![cfg_attr(feature = "unstable", feature(arm_target_feature))]
+#![cfg_attr(all(feature = "std", feature = "unstable"), feature(core_io_borrowed_buf))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(test, deny(warnings))]
 //
@@ -48,41 +72,157 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(any(
+    all(
+        feature = "force-avx2",
+        any(feature = "force-sse41", feature = "force-neon", feature = "force-scalar")
+    ),
+    all(feature = "force-sse41", any(feature = "force-neon", feature = "force-scalar")),
+    all(feature = "force-neon", feature = "force-scalar"),
+))]
+#[cfg(not(docsrs))]
+compile_error!("The feature flags `force-*` are mutually exclusive");
+
 #[macro_use]
 mod error;
-pub use self::error::Error;
+pub use self::error::{CapacityError, Error, ErrorKind};
 
 mod alsw;
 mod ascii;
 mod check;
+mod ct;
 mod decode;
 mod encode;
+mod grouped;
+mod ignore;
+mod mime;
+mod strided;
+mod text;
+mod transcode;
 
 mod multiversion;
 
+mod decoder;
+pub use self::decoder::{Base64Decoder, Progress};
+
+mod iter;
+pub use self::iter::DecodeIter;
+
+mod inspect;
+pub use self::inspect::DecodeShape;
+
+mod scan;
+pub use self::scan::ScanResult;
+
+mod arena;
+pub use self::arena::Arena;
+
+mod nul;
+pub use self::nul::NulPolicy;
+
+mod capabilities;
+pub use self::capabilities::{Backend, Capabilities};
+
 #[cfg(feature = "alloc")]
 mod heap;
 
+#[cfg(feature = "alloc")]
+mod cursor;
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+pub use self::cursor::Base64Cursor;
+
+#[cfg(feature = "alloc")]
+mod collect;
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+pub use self::collect::Base64String;
+
+#[cfg(feature = "alloc")]
+mod small;
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+pub use self::small::SmallBytes;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+pub mod data_uri;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+pub mod pem;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+pub mod multibase;
+
+pub mod url;
+
 #[cfg(feature = "parallel")]
 mod parallel;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "fuzz")))]
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+#[cfg(feature = "stats")]
+pub mod stats;
+
+#[cfg(feature = "std")]
+mod sizing;
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use self::sizing::SizingWriter;
+
+#[cfg(feature = "std")]
+mod writer;
+
+#[cfg(all(feature = "std", feature = "unstable"))]
+mod borrowed;
+
+#[cfg(feature = "forgiving")]
 mod forgiving;
+#[cfg_attr(docsrs, doc(cfg(feature = "forgiving")))]
+#[cfg(feature = "forgiving")]
 pub use self::forgiving::*;
 
+mod relaxed;
+pub use self::relaxed::Base64Relaxed;
+
+mod pasted;
+
 pub use outref::{AsOut, Out};
 
 // -----------------------------------------------------------------------------
 
-use crate::decode::decoded_length;
-use crate::encode::encoded_length_unchecked;
+use crate::decode::{decode_table_of, decoded_length};
+use crate::encode::{checked_encoded_length, encoded_length_unchecked};
 
 use vsimd::tools::{slice_mut, slice_parts};
 
 #[cfg(feature = "alloc")]
-use alloc::{string::String, vec::Vec};
+use alloc::{boxed::Box, string::String, vec::Vec};
 
 const STANDARD_CHARSET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
 const URL_SAFE_CHARSET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const SORTABLE_CHARSET: &[u8; 64] = b"-0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ_abcdefghijklmnopqrstuvwxyz";
+
+const fn is_ascii_charset(charset: &[u8; 64]) -> bool {
+    let mut i = 0;
+    while i < 64 {
+        if !charset[i].is_ascii() {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+// The encoded output is converted to `str` without checking, which is sound only for ASCII charsets.
+const _: () = assert!(
+    is_ascii_charset(STANDARD_CHARSET) && is_ascii_charset(URL_SAFE_CHARSET) && is_ascii_charset(SORTABLE_CHARSET)
+);
 
 /// Base64 variant
 #[derive(Debug)]
@@ -94,18 +234,35 @@ pub struct Base64 {
 enum Kind {
     Standard,
     UrlSafe,
+    Sortable,
 }
 
 #[derive(Debug, Clone, Copy)]
 struct Config {
     kind: Kind,
     extra: Extra,
+    constant_time: bool,
+    nul: NulPolicy,
+    pad: u8,
+}
+
+impl Config {
+    /// The padding character, or `None` if the encoder does not add padding.
+    #[inline(always)]
+    const fn pad(self) -> Option<u8> {
+        if self.extra.padding() {
+            Some(self.pad)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 enum Extra {
     Pad,
     NoPad,
+    OptionalPad,
     Forgiving,
 }
 
@@ -117,6 +274,7 @@ impl Extra {
         match self {
             Extra::Pad => true,
             Extra::NoPad => false,
+            Extra::OptionalPad => false,
             Extra::Forgiving => true,
         }
     }
@@ -127,6 +285,7 @@ impl Extra {
         match self {
             Extra::Pad => false,
             Extra::NoPad => false,
+            Extra::OptionalPad => false,
             Extra::Forgiving => true,
         }
     }
@@ -137,6 +296,9 @@ pub const STANDARD: Base64 = Base64 {
     config: Config {
         kind: Kind::Standard,
         extra: Extra::Pad,
+        constant_time: false,
+        nul: NulPolicy::Error,
+        pad: b'=',
     },
 };
 
@@ -145,6 +307,9 @@ pub const URL_SAFE: Base64 = Base64 {
     config: Config {
         kind: Kind::UrlSafe,
         extra: Extra::Pad,
+        constant_time: false,
+        nul: NulPolicy::Error,
+        pad: b'=',
     },
 };
 
@@ -153,6 +318,9 @@ pub const STANDARD_NO_PAD: Base64 = Base64 {
     config: Config {
         kind: Kind::Standard,
         extra: Extra::NoPad,
+        constant_time: false,
+        nul: NulPolicy::Error,
+        pad: b'=',
     },
 };
 
@@ -161,6 +329,56 @@ pub const URL_SAFE_NO_PAD: Base64 = Base64 {
     config: Config {
         kind: Kind::UrlSafe,
         extra: Extra::NoPad,
+        constant_time: false,
+        nul: NulPolicy::Error,
+        pad: b'=',
+    },
+};
+
+/// URL-Safe charset with `.` padding.
+///
+/// Some URL-safe schemes pad with `.` instead of `=`, because `=` must be percent-encoded in URLs.
+/// The padding rules are the same as [`URL_SAFE`]:
+/// + the encoder pads the last quantum with one `.` for 2 remaining bytes, or `..` for 1 remaining byte.
+/// + the decoder requires the length to be a multiple of 4, with exactly the padding above.
+///
+/// `=` is invalid in this variant.
+///
+/// # Examples
+///
+/// ```
+/// use base64_simd::URL_SAFE_DOT_PAD;
+/// # #[cfg(feature = "alloc")]
+/// # {
+/// assert_eq!(URL_SAFE_DOT_PAD.encode_to_string(b"\xfb\xff"), "-_8.");
+/// assert_eq!(URL_SAFE_DOT_PAD.decode_to_vec("-_8.").unwrap(), b"\xfb\xff");
+/// assert!(URL_SAFE_DOT_PAD.decode_to_vec("-_8=").is_err());
+/// # }
+/// ```
+pub const URL_SAFE_DOT_PAD: Base64 = Base64 {
+    config: Config {
+        kind: Kind::UrlSafe,
+        extra: Extra::Pad,
+        constant_time: false,
+        nul: NulPolicy::Error,
+        pad: b'.',
+    },
+};
+
+/// Sortable charset without padding.
+///
+/// The charset `-0-9A-Z_a-z` is in ASCII order,
+/// so the encoded strings sort lexicographically in the same order as the raw bytes.
+/// It is useful for keys in range scans.
+///
+/// Padding would break the order, so this variant does not use it.
+pub const SORTABLE: Base64 = Base64 {
+    config: Config {
+        kind: Kind::Sortable,
+        extra: Extra::NoPad,
+        constant_time: false,
+        nul: NulPolicy::Error,
+        pad: b'=',
     },
 };
 
@@ -168,10 +386,83 @@ const STANDARD_FORGIVING: Base64 = Base64 {
     config: Config {
         kind: Kind::Standard,
         extra: Extra::Forgiving,
+        constant_time: false,
+        nul: NulPolicy::Error,
+        pad: b'=',
     },
 };
 
 impl Base64 {
+    /// The number of input bytes encoded by one step of the widest encoder.
+    ///
+    /// The widest kernels encode 24 bytes to 32 characters and decode 32 characters to 24 bytes per step.
+    /// Buffers whose lengths are multiples of these sizes keep the partial quantum,
+    /// which is handled by scalar code, at the end of the whole input instead of each buffer.
+    ///
+    /// The kernels use unaligned loads and stores, so the buffers do not need any alignment.
+    /// A few bytes at the edges of a long input are still handled by scalar code:
+    /// the encoder reads 4 bytes before each SIMD block, and the decoder writes 4 bytes after it,
+    /// so the first and the last blocks may be processed by scalar code or narrower vectors.
+    pub const ENCODE_BLOCK_IN: usize = 24;
+
+    /// The number of characters written by one step of the widest encoder.
+    ///
+    /// See [`ENCODE_BLOCK_IN`](Self::ENCODE_BLOCK_IN).
+    pub const ENCODE_BLOCK_OUT: usize = 32;
+
+    /// The number of characters decoded by one step of the widest decoder.
+    ///
+    /// See [`ENCODE_BLOCK_IN`](Self::ENCODE_BLOCK_IN).
+    pub const DECODE_BLOCK_IN: usize = 32;
+
+    /// The number of bytes written by one step of the widest decoder.
+    ///
+    /// See [`ENCODE_BLOCK_IN`](Self::ENCODE_BLOCK_IN).
+    pub const DECODE_BLOCK_OUT: usize = 24;
+}
+
+impl Base64 {
+    /// Returns a constant-time variant of `self`.
+    ///
+    /// The returned variant never uses input data as a table index or a branch condition
+    /// when mapping between 6-bit values and characters.
+    /// It is suitable for encoding and decoding key material, but it is much slower than the default variant.
+    ///
+    /// The lengths of input and output, and the position of padding characters, are not considered secret.
+    #[inline]
+    #[must_use]
+    pub const fn constant_time(self) -> Self {
+        Self {
+            config: Config {
+                constant_time: true,
+                ..self.config
+            },
+        }
+    }
+
+    /// Returns a variant of `self` which accepts padded input when decoding.
+    ///
+    /// This only affects the variants without padding.
+    /// Their encoders still do not add padding, and their decoders accept the input in two forms:
+    /// + without padding, the length must be `4k`, `4k+2` or `4k+3`.
+    /// + with padding, the length must be `4k`, ending with `=` or `==` which completes the last quantum.
+    ///
+    /// A partial padding such as `Zg=` is rejected.
+    /// The unused bits of the last significant character must still be zero.
+    ///
+    /// The variants with padding always require it, so they are returned unchanged.
+    #[inline]
+    #[must_use]
+    pub const fn accept_optional_padding(self) -> Self {
+        let extra = match self.config.extra {
+            Extra::NoPad => Extra::OptionalPad,
+            extra => extra,
+        };
+        Self {
+            config: Config { extra, ..self.config },
+        }
+    }
+
     /// Returns the character set.
     #[inline]
     #[must_use]
@@ -179,18 +470,51 @@ impl Base64 {
         match self.config.kind {
             Kind::Standard => STANDARD_CHARSET,
             Kind::UrlSafe => URL_SAFE_CHARSET,
+            Kind::Sortable => SORTABLE_CHARSET,
+        }
+    }
+
+    /// Returns the character set as a string.
+    #[inline]
+    #[must_use]
+    pub const fn charset_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(self.charset()) }
+    }
+
+    /// Returns the value (`0..64`) of a character in the character set.
+    ///
+    /// Returns `None` if `byte` is not in the character set. The padding character `=` is not in it.
+    #[inline]
+    #[must_use]
+    pub const fn alphabet_index(&self, byte: u8) -> Option<u8> {
+        match decode_table_of(self.config.kind)[byte as usize] {
+            0xff => None,
+            x => Some(x),
         }
     }
 
     /// Calculates the encoded length.
     ///
     /// # Panics
-    /// This function asserts that `n <= usize::MAX / 2`.
+    /// This function asserts that the encoded length is not greater than `isize::MAX`.
+    /// On 32-bit targets, the limit is reached by an input of about 1.5 GiB.
     #[inline]
     #[must_use]
     pub const fn encoded_length(&self, n: usize) -> usize {
-        assert!(n <= usize::MAX / 2);
-        encoded_length_unchecked(n, self.config)
+        match checked_encoded_length(n, self.config) {
+            Some(m) => m,
+            None => panic!("encoded length overflow"),
+        }
+    }
+
+    /// Calculates the encoded length.
+    ///
+    /// Returns `None` if the encoded length is greater than `isize::MAX`,
+    /// where [`encoded_length`](Self::encoded_length) would panic.
+    #[inline]
+    #[must_use]
+    pub const fn checked_encoded_length(&self, n: usize) -> Option<usize> {
+        checked_encoded_length(n, self.config)
     }
 
     /// Estimates the decoded length.
@@ -210,6 +534,9 @@ impl Base64 {
     /// This function returns `Err` if the content of `data` is partially invalid.
     #[inline]
     pub fn decoded_length(&self, data: &[u8]) -> Result<usize, Error> {
+        if self.skips_nul(data) {
+            return self.decoded_length_skipping_nul(data);
+        }
         let (_, m) = decoded_length(data, self.config)?;
         Ok(m)
     }
@@ -220,19 +547,67 @@ impl Base64 {
     /// This function returns `Err` if the content of `data` is invalid.
     #[inline]
     pub fn check(&self, data: &[u8]) -> Result<(), Error> {
+        if self.skips_nul(data) {
+            return self.check_skipping_nul(data);
+        }
         let (n, _) = decoded_length(data, self.config)?;
         unsafe { crate::multiversion::check::auto(data.as_ptr(), n, self.config) }
     }
 
+    /// Checks whether `b64` is a base64 string which is decoded to `bytes`.
+    ///
+    /// This function decodes `b64` block by block without allocation and stops at the first mismatch.
+    ///
+    /// Returns `false` if `b64` is invalid or the lengths do not match.
+    #[inline]
+    #[must_use]
+    pub fn eq_decoded(&self, b64: &[u8], bytes: &[u8]) -> bool {
+        const CHUNK: usize = 256;
+
+        if self.skips_nul(b64) {
+            return self.eq_decoded_skipping_nul(b64, bytes);
+        }
+
+        let (mut n, m) = match decoded_length(b64, self.config) {
+            Ok(x) => x,
+            Err(_) => return false,
+        };
+        if m != bytes.len() {
+            return false;
+        }
+
+        let mut buf = [0u8; CHUNK / 4 * 3];
+        let mut src = b64;
+        let mut expected = bytes;
+
+        while n > 0 {
+            let k = if n > CHUNK { CHUNK } else { n };
+            let len = if n > CHUNK { CHUNK / 4 * 3 } else { expected.len() };
+
+            let result = unsafe { self::multiversion::decode::auto(src.as_ptr(), buf.as_mut_ptr(), k, self.config) };
+            if result.is_err() || buf[..len] != expected[..len] {
+                return false;
+            }
+
+            src = &src[k..];
+            expected = &expected[len..];
+            n -= k;
+        }
+
+        true
+    }
+
     /// Encodes bytes to a base64 string.
     ///
     /// # Errors
     /// This function returns `Err` if the length of `dst` is not enough.
+    /// It is also the case when the encoded length is greater than `isize::MAX`,
+    /// which no buffer can hold.
     #[inline]
     pub fn encode<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
         unsafe {
             let m = encoded_length_unchecked(src.len(), self.config);
-            ensure!(dst.len() >= m);
+            ensure_capacity!(dst.len(), m);
 
             let (src, len) = slice_parts(src);
             let dst = dst.as_mut_ptr();
@@ -254,15 +629,24 @@ impl Base64 {
 
     /// Decodes a base64 string to bytes.
     ///
+    /// Non-canonical encodings are rejected by all the built-in variants:
+    /// the unused bits of the last character must be zero,
+    /// and padding must be present or absent exactly as the variant requires.
+    /// So each byte sequence has exactly one accepted encoding,
+    /// which is what JWS/JWT implementations need for [`URL_SAFE_NO_PAD`].
+    ///
     /// # Errors
     /// This function returns `Err` if
     /// + the length of `dst` is not enough.
     /// + the content of `src` is invalid.
     #[inline]
     pub fn decode<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        if self.skips_nul(src) {
+            return self.decode_skipping_nul(src, dst);
+        }
         unsafe {
             let (n, m) = decoded_length(src, self.config)?;
-            ensure!(dst.len() >= m);
+            ensure_capacity!(dst.len(), m);
 
             let src = src.as_ptr();
             let dst = dst.as_mut_ptr();
@@ -272,12 +656,417 @@ impl Base64 {
         }
     }
 
+    /// Decodes a base64 string to a byte slice and returns the number of bytes written.
+    ///
+    /// This is the same as [`decode`](Self::decode) for a plain `&mut [u8]`.
+    /// [`decoded_length`](Self::decoded_length) gives the exact length that `dst` needs.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid.
+    #[inline]
+    pub fn decode_slice(&self, src: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
+        let ans = self.decode(src, dst.as_out())?;
+        Ok(ans.len())
+    }
+
+    /// Decodes a base64 string which must end with exactly `expected_pad` padding characters.
+    ///
+    /// Binary formats often know the decoded length of a field, and thus its padding.
+    /// A truncated or corrupted field may still decode, but its padding usually differs.
+    ///
+    /// The NUL bytes ignored by the [`NulPolicy`] are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_simd::{AsOut, ErrorKind, STANDARD};
+    ///
+    /// let mut buf = [0u8; 6];
+    /// let ans = STANDARD.decode_exact_padding(b"aGVsbG8=", buf.as_mut_slice().as_out(), 1).unwrap();
+    /// assert_eq!(ans, b"hello");
+    ///
+    /// let err = STANDARD.decode_exact_padding(b"aGVsbA==", buf.as_mut_slice().as_out(), 1).unwrap_err();
+    /// assert_eq!(err.kind(), ErrorKind::InvalidLength);
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the number of padding characters is not `expected_pad`.
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid.
+    #[inline]
+    pub fn decode_exact_padding<'d>(
+        &self,
+        src: &[u8],
+        dst: Out<'d, [u8]>,
+        expected_pad: u8,
+    ) -> Result<&'d mut [u8], Error> {
+        let data = match self.config.nul {
+            NulPolicy::Stop => crate::nul::truncate_at_nul(src),
+            NulPolicy::Error | NulPolicy::Skip => src,
+        };
+        let pad = data
+            .iter()
+            .rev()
+            .filter(|&&x| x != 0)
+            .take_while(|&&x| x == self.config.pad)
+            .count();
+        ensure!(pad == usize::from(expected_pad), InvalidLength);
+        self.decode(src, dst)
+    }
+
+    /// Decodes a base64 string to an array of exactly `N` bytes.
+    ///
+    /// This is a `const fn` with a scalar implementation,
+    /// so it can decode fixed inputs at compile time.
+    /// At runtime, [`decode`](Self::decode) is faster.
+    ///
+    /// The result is the same as [`decode`](Self::decode), except that
+    /// + NUL bytes are always invalid, regardless of the [`NulPolicy`].
+    /// + the implementation is not constant-time, even if [`constant_time`](Self::constant_time) is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_simd::STANDARD;
+    ///
+    /// const HELLO: [u8; 5] = match STANDARD.decode_array(b"aGVsbG8=") {
+    ///     Ok(bytes) => bytes,
+    ///     Err(_) => panic!("invalid base64"),
+    /// };
+    /// assert_eq!(&HELLO, b"hello");
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the decoded length is not `N`.
+    /// + the content of `src` is invalid.
+    #[inline]
+    pub const fn decode_array<const N: usize>(&self, src: &[u8]) -> Result<[u8; N], Error> {
+        crate::decode::decode_const(src, self.config)
+    }
+
+    /// Decodes a base64 string to bytes in passes of `chunk_chars` characters.
+    ///
+    /// The result is the same as [`decode`](Self::decode).
+    /// Each pass reads `chunk_chars` characters and writes `chunk_chars / 4 * 3` bytes,
+    /// which bounds the working set when decoding a huge input.
+    /// The last pass handles the remaining characters, including the final partial quantum.
+    /// An input with NUL bytes to skip is decoded in fixed passes, as [`decode`](Self::decode) does.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid.
+    ///
+    /// # Panics
+    /// This function asserts that `chunk_chars` is a non-zero multiple of 4.
+    #[inline]
+    pub fn decode_chunked<'d>(
+        &self,
+        src: &[u8],
+        mut dst: Out<'d, [u8]>,
+        chunk_chars: usize,
+    ) -> Result<&'d mut [u8], Error> {
+        assert!(chunk_chars != 0 && chunk_chars % 4 == 0);
+
+        if self.skips_nul(src) {
+            return self.decode_skipping_nul(src, dst);
+        }
+
+        let (mut n, m) = decoded_length(src, self.config)?;
+        ensure_capacity!(dst.len(), m);
+
+        unsafe {
+            let mut src = src.as_ptr();
+            let dst = dst.as_mut_ptr();
+            let mut cur = dst;
+
+            while n > 0 {
+                let k = if n > chunk_chars { chunk_chars } else { n };
+                self::multiversion::decode::auto(src, cur, k, self.config)?;
+
+                src = src.add(k);
+                cur = cur.add(k / 4 * 3);
+                n -= k;
+            }
+
+            Ok(slice_mut(dst, m))
+        }
+    }
+
+    /// Decodes a base64 string to bytes and reports the progress to `cb`.
+    ///
+    /// The result is the same as [`decode`](Self::decode).
+    /// The input is decoded in passes which write about `every` bytes each.
+    /// After each pass, `cb` is called with the total number of bytes written so far,
+    /// so the last call reports the decoded length.
+    /// The cadence is block-granular: a pass covers whole quanta and writes at least 3 bytes.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid.
+    ///
+    /// # Panics
+    /// This function asserts that `every > 0`.
+    #[inline]
+    pub fn decode_with_progress<'d>(
+        &self,
+        src: &[u8],
+        mut dst: Out<'d, [u8]>,
+        every: usize,
+        cb: &mut dyn FnMut(usize),
+    ) -> Result<&'d mut [u8], Error> {
+        assert!(every > 0);
+
+        if self.skips_nul(src) {
+            let ans = self.decode_skipping_nul(src, dst)?;
+            cb(ans.len());
+            return Ok(ans);
+        }
+
+        let (mut n, m) = decoded_length(src, self.config)?;
+        ensure_capacity!(dst.len(), m);
+
+        let chunk_chars = (every / 3).max(1).saturating_mul(4);
+
+        unsafe {
+            let mut src = src.as_ptr();
+            let dst = dst.as_mut_ptr();
+            let mut written = 0;
+
+            while n > 0 {
+                let k = if n > chunk_chars { chunk_chars } else { n };
+                self::multiversion::decode::auto(src, dst.add(written), k, self.config)?;
+
+                src = src.add(k);
+                written += if k == n { m - written } else { k / 4 * 3 };
+                n -= k;
+                cb(written);
+            }
+
+            Ok(slice_mut(dst, m))
+        }
+    }
+
+    /// Decodes a base64 string given as two slices `a` and `b`, as if they were concatenated.
+    ///
+    /// The result is the same as [`decode`](Self::decode) on the concatenation, without copying the input.
+    /// Only the quantum which straddles the boundary is copied to a small buffer.
+    /// The bulk of each slice is decoded as usual.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `a` followed by `b` is invalid.
+    #[inline]
+    pub fn decode_chained<'d>(&self, a: &[u8], b: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        if b.is_empty() {
+            return self.decode(a, dst);
+        }
+
+        let (head, tail) = a.split_at(a.len() / 4 * 4);
+
+        unsafe {
+            let cap = dst.len();
+            let dst = dst.as_mut_ptr();
+
+            let mut written = head.len() / 4 * 3;
+            ensure_capacity!(cap, written);
+            self::multiversion::decode::auto(head.as_ptr(), dst, head.len(), self.config)?;
+
+            let mut rest = b;
+            if !tail.is_empty() {
+                let k = (4 - tail.len()).min(b.len());
+                let mut buf = [0u8; 4];
+                buf[..tail.len()].copy_from_slice(tail);
+                buf[tail.len()..tail.len() + k].copy_from_slice(&b[..k]);
+                let bridge = &buf[..tail.len() + k];
+                rest = &b[k..];
+
+                if rest.is_empty() {
+                    let out = slice_mut(dst.add(written), cap - written).as_out();
+                    let n = self.decode(bridge, out).map_err(|e| e.offset_capacity(written))?.len();
+                    return Ok(slice_mut(dst, written + n));
+                }
+
+                ensure_capacity!(cap, written + 3);
+                self::multiversion::decode::auto(bridge.as_ptr(), dst.add(written), 4, self.config)?;
+                written += 3;
+            }
+
+            let out = slice_mut(dst.add(written), cap - written).as_out();
+            let n = self.decode(rest, out).map_err(|e| e.offset_capacity(written))?.len();
+            Ok(slice_mut(dst, written + n))
+        }
+    }
+
+    /// Decodes a base64 string to bytes, accepting both the standard and the URL-safe charset.
+    ///
+    /// `+` and `-` are both decoded as 62, `/` and `_` are both decoded as 63.
+    /// The two charsets may be mixed in the same input.
+    /// The padding rule of `self` is still enforced.
+    ///
+    /// This function is intentionally permissive.
+    /// Use [`decode`](Self::decode) when the charset is known.
+    ///
+    /// [`SORTABLE`] has no alternative charset, so it is decoded strictly as [`decode`](Self::decode) does.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid.
+    #[inline]
+    pub fn decode_any<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        if matches!(self.config.kind, Kind::Sortable) {
+            return self.decode(src, dst);
+        }
+        if self.skips_nul(src) {
+            return self.decode_skipping_nul_with(src, dst, self::multiversion::decode_any::auto);
+        }
+        unsafe {
+            let (n, m) = decoded_length(src, self.config)?;
+            ensure_capacity!(dst.len(), m);
+
+            let src = src.as_ptr();
+            let dst = dst.as_mut_ptr();
+            self::multiversion::decode_any::auto(src, dst, n, self.config)?;
+
+            Ok(slice_mut(dst, m))
+        }
+    }
+
+    /// Decodes a base64 string to bytes, skipping ignorable bytes.
+    ///
+    /// `ignore[b as usize]` specifies whether the byte `b` is ignorable filler,
+    /// such as whitespace, commas or other separators of a specific format.
+    /// Ignorable bytes are removed before decoding. The other bytes must form a valid base64 string.
+    /// The blocks of 16 bytes without ignorable bytes are found by SIMD code and copied as a whole.
+    ///
+    /// If `src` contains ignorable bytes, `dst` is also used as a scratch buffer for the filtered input,
+    /// so it must be able to hold all the significant bytes of `src`.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid.
+    #[inline]
+    pub fn decode_ignoring<'d>(
+        &self,
+        src: &[u8],
+        mut dst: Out<'d, [u8]>,
+        ignore: &[bool; 256],
+    ) -> Result<&'d mut [u8], Error> {
+        let len = self.count_significant_chars(src, ignore);
+        if len == src.len() {
+            return self.decode(src, dst);
+        }
+        ensure_capacity!(dst.len(), len);
+
+        unsafe {
+            let (src, n) = slice_parts(src);
+            let dst = dst.as_mut_ptr();
+            let count = self::multiversion::filter_ignoring::auto(src, n, dst, ignore);
+            debug_assert_eq!(count, len);
+
+            self.decode_inplace(slice_mut(dst, len))
+        }
+    }
+
+    /// Counts the bytes of `data` which are not ignorable.
+    ///
+    /// `ignore` has the same meaning as in [`decode_ignoring`](Self::decode_ignoring).
+    /// The result is the scratch space needed by [`decode_ignoring`](Self::decode_ignoring),
+    /// and [`estimated_decoded_length`](Self::estimated_decoded_length) of it
+    /// bounds the decoded length without counting the ignorable bytes.
+    ///
+    /// The bytes are classified by SIMD code in blocks of 16 bytes.
+    #[inline]
+    #[must_use]
+    pub fn count_significant_chars(&self, data: &[u8], ignore: &[bool; 256]) -> usize {
+        let (src, len) = slice_parts(data);
+        unsafe { self::multiversion::count_significant::auto(src, len, ignore) }
+    }
+
+    /// Decodes the longest valid prefix of a base64 string to bytes.
+    ///
+    /// If `src` is valid and `dst` is large enough, the whole string is decoded.
+    /// Otherwise, the complete quanta of 4 characters before the first invalid character are decoded,
+    /// as many as `dst` can hold.
+    ///
+    /// Returns the decoded bytes and the number of characters consumed from `src`.
+    /// This function never fails. The caller can inspect `src` at the returned offset to find out what went wrong.
+    #[inline]
+    #[must_use]
+    pub fn decode_valid_prefix<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> (&'d mut [u8], usize) {
+        if let Ok((n, m)) = decoded_length(src, self.config) {
+            if dst.len() >= m {
+                let result =
+                    unsafe { self::multiversion::decode::auto(src.as_ptr(), dst.as_mut_ptr(), n, self.config) };
+                if result.is_ok() {
+                    return (unsafe { slice_mut(dst.as_mut_ptr(), m) }, src.len());
+                }
+            }
+        }
+
+        let table = decode_table_of(self.config.kind);
+        let valid = src.iter().position(|&x| table[x as usize] == 0xff).unwrap_or(src.len());
+
+        let mut n = valid / 4 * 4;
+        if n == src.len() {
+            // The last quantum is malformed although its characters are valid.
+            n = n.saturating_sub(4);
+        }
+        n = n.min(dst.len() / 3 * 4);
+
+        unsafe {
+            let dst = dst.as_mut_ptr();
+            let result = self::multiversion::decode::auto(src.as_ptr(), dst, n, self.config);
+            debug_assert!(result.is_ok());
+            (slice_mut(dst, n / 4 * 3), n)
+        }
+    }
+
+    /// Decodes a base64 string to bytes without validation.
+    ///
+    /// This function skips the per-character validation of [`decode`](Self::decode).
+    /// It is intended for inputs which have been validated by [`check`](Self::check).
+    ///
+    /// # Panics
+    /// This function panics if the length of `dst` is not enough.
+    ///
+    /// # Safety
+    /// Behavior is undefined if `src` is not a valid base64 string of this variant.
+    /// In other words, [`check`](Self::check) must return `Ok` for `src`.
+    /// The NUL bytes are treated according to the [`NulPolicy`] like [`check`](Self::check) does.
+    #[inline]
+    #[must_use]
+    pub unsafe fn decode_trusted<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> &'d mut [u8] {
+        if self.skips_nul(src) {
+            return self.decode_trusted_skipping_nul(src, dst);
+        }
+        let (n, m) = decoded_length(src, self.config).unwrap_unchecked();
+        assert!(dst.len() >= m);
+
+        let src = src.as_ptr();
+        let dst = dst.as_mut_ptr();
+        self::multiversion::decode_trusted::auto(src, dst, n, self.config);
+
+        slice_mut(dst, m)
+    }
+
     /// Decodes a base64 string to bytes and writes inplace.
     ///
     /// # Errors
     /// This function returns `Err` if the content of `data` is invalid.
     #[inline]
     pub fn decode_inplace<'d>(&self, data: &'d mut [u8]) -> Result<&'d mut [u8], Error> {
+        if self.skips_nul(data) {
+            return self.decode_inplace_skipping_nul(data);
+        }
         unsafe {
             let (n, m) = decoded_length(data, self.config)?;
 
@@ -289,6 +1078,64 @@ impl Base64 {
         }
     }
 
+    /// Re-encodes a base64 string from the variant `from` to the variant `to` and writes inplace.
+    ///
+    /// The input is `data[..len]` and the rest of `data` is spare capacity.
+    /// The characters `+` `/` and `-` `_` are swapped between the standard and the URL-safe charsets,
+    /// and every character is mapped through the charsets if either of them is [`SORTABLE`].
+    /// The padding is added or stripped as `to` requires. The bytes are never decoded.
+    ///
+    /// The output length is `to.encoded_length(m)`, where `m` is the decoded length of the input.
+    /// + It needs up to 2 bytes of extra capacity when `to` adds padding that the input lacks.
+    /// + It shrinks by up to 2 bytes when `to` strips the padding of the input.
+    /// + Otherwise it is the same as `len`.
+    ///
+    /// The [`NulPolicy`] of `from` is not applied. NUL bytes before the end of the input are rejected.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the content of `data[..len]` is invalid for `from`.
+    /// + the length of `data` is not enough for the output.
+    ///
+    /// # Panics
+    /// This function asserts that `len <= data.len()`.
+    #[inline]
+    pub fn transcode_inplace<'d>(
+        data: &'d mut [u8],
+        len: usize,
+        from: &Base64,
+        to: &Base64,
+    ) -> Result<&'d mut [u8], Error> {
+        assert!(len <= data.len());
+
+        let src = &data[..len];
+        let (n, m) = decoded_length(src, from.config)?;
+        // The kernel rejects NUL bytes, which `check` may skip.
+        unsafe { self::multiversion::check::auto(src.as_ptr(), n, from.config)? };
+
+        let out_len = to.encoded_length(m);
+        ensure_capacity!(data.len(), out_len);
+
+        unsafe {
+            let dst = data.as_mut_ptr();
+            match (from.config.kind, to.config.kind) {
+                (Kind::Standard, Kind::Standard)
+                | (Kind::UrlSafe, Kind::UrlSafe)
+                | (Kind::Sortable, Kind::Sortable) => {}
+                (Kind::Standard, Kind::UrlSafe) | (Kind::UrlSafe, Kind::Standard) => {
+                    let (c62, c63) = (from.charset()[62], from.charset()[63]);
+                    self::multiversion::swap_charset::auto(dst, n, c62, c63);
+                }
+                _ => {
+                    let table = decode_table_of(from.config.kind);
+                    crate::transcode::remap_charset(dst, n, table, to.charset());
+                }
+            }
+            core::ptr::write_bytes(dst.add(n), to.config.pad, out_len - n);
+            Ok(slice_mut(dst, out_len))
+        }
+    }
+
     /// Encodes bytes to a base64 string and returns a specified type.
     #[inline]
     #[must_use]
@@ -320,7 +1167,62 @@ impl Base64 {
         T::append_base64_decode(self, src.as_ref(), dst)
     }
 
+    /// Encodes bytes to a base64 string and writes to an output target.
+    ///
+    /// The output target decides how the result is stored and what is returned.
+    /// See [`EncodeOutput`] for the supported targets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_simd::{AsOut, STANDARD};
+    ///
+    /// let mut buf = [0u8; 8];
+    /// assert_eq!(STANDARD.encode_into("hello", buf.as_mut_slice()).unwrap(), b"aGVsbG8=");
+    /// assert_eq!(STANDARD.encode_into("hello", buf.as_mut_slice().as_out()).unwrap(), b"aGVsbG8=");
+    ///
+    /// # #[cfg(feature = "alloc")]
+    /// # {
+    /// let mut s = String::from("data:");
+    /// STANDARD.encode_into("hello", &mut s);
+    /// assert_eq!(s, "data:aGVsbG8=");
+    /// # }
+    /// ```
+    #[inline]
+    pub fn encode_into<O: EncodeOutput>(&self, data: impl AsRef<[u8]>, out: O) -> O::Output {
+        out.encode_output(self, data.as_ref())
+    }
+
+    /// Decodes a base64 string to bytes and writes to an output target.
+    ///
+    /// The output target decides how the result is stored and what is returned.
+    /// See [`DecodeOutput`] for the supported targets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_simd::STANDARD;
+    ///
+    /// let mut buf = [0u8; 5];
+    /// assert_eq!(STANDARD.decode_into("aGVsbG8=", buf.as_mut_slice()).unwrap(), b"hello");
+    ///
+    /// # #[cfg(feature = "alloc")]
+    /// # {
+    /// let mut vec = Vec::from(*b"say ");
+    /// STANDARD.decode_into("aGVsbG8=", &mut vec).unwrap();
+    /// assert_eq!(vec, b"say hello");
+    /// # }
+    /// ```
+    #[inline]
+    pub fn decode_into<O: DecodeOutput>(&self, data: impl AsRef<[u8]>, out: O) -> O::Output {
+        out.decode_output(self, data.as_ref())
+    }
+
     /// Encodes bytes to a base64 string.
+    ///
+    /// # Panics
+    /// This function panics if the encoded length is greater than `isize::MAX`.
+    /// See [`checked_encoded_length`](Self::checked_encoded_length).
     #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
     #[cfg(feature = "alloc")]
     #[inline]
@@ -329,6 +1231,39 @@ impl Base64 {
         self.encode_type(data)
     }
 
+    /// Encodes bytes to a base64 string and feeds the encoded text to `update` in the same pass.
+    ///
+    /// The text is passed in order as consecutive chunks of a few kilobytes,
+    /// each right after it is encoded, so hashing the text does not need a second sweep over the memory.
+    /// This is useful for the integrity schemes which hash the base64 text instead of the raw bytes.
+    /// `update` is typically the update method of a hasher, such as `Sha256::update`.
+    ///
+    /// `update` is not called if `data` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")]
+    /// # {
+    /// use base64_simd::STANDARD;
+    ///
+    /// let mut text = Vec::new();
+    /// let encoded = STANDARD.encode_and_update(b"hello", |chunk| text.extend_from_slice(chunk));
+    /// assert_eq!(&*encoded, "aGVsbG8=");
+    /// assert_eq!(text, encoded.as_bytes());
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    /// This function panics if the encoded length is greater than `isize::MAX`.
+    /// See [`checked_encoded_length`](Self::checked_encoded_length).
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn encode_and_update(&self, data: impl AsRef<[u8]>, update: impl FnMut(&[u8])) -> Box<str> {
+        crate::heap::encode_to_boxed_str_with(self, data.as_ref(), update)
+    }
+
     /// Decodes a base64 string to bytes.
     ///
     /// # Errors
@@ -356,6 +1291,73 @@ pub trait FromBase64Decode: Sized {
     fn from_base64_decode(base64: &Base64, data: &[u8]) -> Result<Self, Error>;
 }
 
+/// Output targets of [`Base64::encode_into`].
+///
+/// | target | result |
+/// | --- | --- |
+/// | [`Out<'_, [u8]>`](Out) | the encoded part of the buffer, or `Err` if it is too small |
+/// | `&mut [u8]` | the same as [`Out<'_, [u8]>`](Out) |
+/// | `&mut Vec<u8>` | `()`, the string is appended |
+/// | `&mut String` | `()`, the string is appended |
+pub trait EncodeOutput {
+    /// The result of encoding.
+    type Output;
+
+    /// Encodes bytes to a base64 string and writes to the self target.
+    fn encode_output(self, base64: &Base64, src: &[u8]) -> Self::Output;
+}
+
+/// Output targets of [`Base64::decode_into`].
+///
+/// | target | result |
+/// | --- | --- |
+/// | [`Out<'_, [u8]>`](Out) | the decoded part of the buffer, or `Err` if it is too small or the input is invalid |
+/// | `&mut [u8]` | the same as [`Out<'_, [u8]>`](Out) |
+/// | `&mut Vec<u8>` | `Ok(())` if the bytes are appended, or `Err` if the input is invalid |
+pub trait DecodeOutput {
+    /// The result of decoding.
+    type Output;
+
+    /// Decodes a base64 string to bytes and writes to the self target.
+    fn decode_output(self, base64: &Base64, src: &[u8]) -> Self::Output;
+}
+
+impl<'d> EncodeOutput for Out<'d, [u8]> {
+    type Output = Result<&'d mut [u8], Error>;
+
+    #[inline]
+    fn encode_output(self, base64: &Base64, src: &[u8]) -> Self::Output {
+        base64.encode(src, self)
+    }
+}
+
+impl<'d> EncodeOutput for &'d mut [u8] {
+    type Output = Result<&'d mut [u8], Error>;
+
+    #[inline]
+    fn encode_output(self, base64: &Base64, src: &[u8]) -> Self::Output {
+        base64.encode(src, self.as_out())
+    }
+}
+
+impl<'d> DecodeOutput for Out<'d, [u8]> {
+    type Output = Result<&'d mut [u8], Error>;
+
+    #[inline]
+    fn decode_output(self, base64: &Base64, src: &[u8]) -> Self::Output {
+        base64.decode(src, self)
+    }
+}
+
+impl<'d> DecodeOutput for &'d mut [u8] {
+    type Output = Result<&'d mut [u8], Error>;
+
+    #[inline]
+    fn decode_output(self, base64: &Base64, src: &[u8]) -> Self::Output {
+        base64.decode(src, self.as_out())
+    }
+}
+
 /// Types that can append a base64 string.
 pub trait AppendBase64Encode: FromBase64Encode {
     /// Encodes bytes to a base64 string and appends into the self type.
@@ -0,0 +1,162 @@
+use crate::{AsOut, Base64, Error, ErrorKind, Out, ScanResult};
+
+use vsimd::tools::slice_mut;
+
+/// The maximum line length of MIME base64, excluding the line break.
+const LINE_CHARS: usize = 76;
+
+/// The size of the scratch buffer for irregular input, in characters.
+const SCRATCH_CHARS: usize = 256;
+
+/// Returns the index of the first byte which breaks the line structure of RFC 2045.
+///
+/// Lines are terminated by `\r\n`, except the last one, and are not empty.
+/// Whitespace is only allowed in the line breaks.
+fn find_line_violation(src: &[u8]) -> Option<usize> {
+    let mut line_start = 0;
+    let mut i = 0;
+    while i < src.len() {
+        match src[i] {
+            b'\r' => {
+                if i == line_start || src.get(i + 1) != Some(&b'\n') {
+                    return Some(i);
+                }
+                i += 2;
+                line_start = i;
+            }
+            x if x.is_ascii_whitespace() => return Some(i),
+            _ => {
+                if i - line_start == LINE_CHARS {
+                    return Some(i);
+                }
+                i += 1;
+            }
+        }
+    }
+    None
+}
+
+impl Base64 {
+    /// Decodes a MIME base64 string to bytes.
+    ///
+    /// MIME wraps base64 lines at 76 characters with `\r\n` ([RFC 2045](https://www.rfc-editor.org/rfc/rfc2045#section-6.8)).
+    /// Full lines followed by `\r\n` are decoded in place without filtering.
+    /// The rest of the input, starting from the first irregular line, has its ASCII whitespace removed
+    /// through a small stack buffer before decoding.
+    ///
+    /// Any other character between the groups is rejected.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid after removing the line breaks.
+    #[inline]
+    pub fn decode_mime<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        unsafe {
+            let cap = dst.len();
+            let dst = dst.as_mut_ptr();
+            let mut written = 0;
+
+            let mut rest = src;
+            while rest.len() >= LINE_CHARS + 2 {
+                let (line, tail) = rest.split_at(LINE_CHARS);
+                // A padded line must be the last line, which is left to the slow path.
+                if tail[..2] != *b"\r\n"
+                    || line[LINE_CHARS - 1] == self.config.pad
+                    || cap - written < LINE_CHARS / 4 * 3
+                {
+                    break;
+                }
+
+                // Shorter lines may also put a line break at this position.
+                // Such a chunk is invalid as a whole, and is also left to the slow path.
+                let line_dst = dst.add(written);
+                if crate::multiversion::decode::auto(line.as_ptr(), line_dst, LINE_CHARS, self.config).is_err() {
+                    break;
+                }
+                written += LINE_CHARS / 4 * 3;
+                rest = &tail[2..];
+            }
+
+            let mut buf = [0u8; SCRATCH_CHARS];
+            let mut len = 0;
+            let mut chars = rest.iter().copied().filter(|x| !x.is_ascii_whitespace()).peekable();
+            loop {
+                while len < SCRATCH_CHARS {
+                    match chars.next() {
+                        Some(x) => {
+                            buf[len] = x;
+                            len += 1;
+                        }
+                        None => break,
+                    }
+                }
+
+                if len < SCRATCH_CHARS || chars.peek().is_none() {
+                    let out = slice_mut(dst.add(written), cap - written).as_out();
+                    let n = self
+                        .decode(&buf[..len], out)
+                        .map_err(|e| e.offset_capacity(written))?
+                        .len();
+                    return Ok(slice_mut(dst, written + n));
+                }
+
+                ensure_capacity!(cap, written + SCRATCH_CHARS / 4 * 3);
+                crate::multiversion::decode::auto(buf.as_ptr(), dst.add(written), SCRATCH_CHARS, self.config)?;
+                written += SCRATCH_CHARS / 4 * 3;
+                len = 0;
+            }
+        }
+    }
+
+    /// Decodes a MIME base64 string to bytes, enforcing the line structure of RFC 2045.
+    ///
+    /// Unlike [`decode_mime`](Self::decode_mime), which skips any ASCII whitespace,
+    /// every line must be terminated by `\r\n`, except that the last one may be unterminated,
+    /// and must contain 1 to 76 base64 characters.
+    /// Padding is only allowed at the end of the last line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_simd::{AsOut, STANDARD};
+    ///
+    /// let mut buf = [0u8; 16];
+    /// let ans = STANDARD.decode_mime_strict(b"Zm9v\r\nYmFy\r\n", buf.as_mut_slice().as_out()).unwrap();
+    /// assert_eq!(ans, b"foobar");
+    ///
+    /// let err = STANDARD.decode_mime_strict(b"Zm9v\nYmFy", buf.as_mut_slice().as_out()).unwrap_err();
+    /// assert_eq!(err.index(), Some(4));
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + a line is not well-formed. [`Error::index`] returns the index of the first offending byte.
+    /// + the content of `src` is invalid after removing the line breaks.
+    ///   If a character is misplaced, [`Error::index`] returns its index in `src`.
+    #[inline]
+    pub fn decode_mime_strict<'d>(&self, src: &[u8], dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        if let Some(pos) = find_line_violation(src) {
+            return Err(Error::at(pos));
+        }
+        self.decode_mime(src, dst).map_err(|e| {
+            if e.kind() == ErrorKind::InsufficientCapacity {
+                return e;
+            }
+            let mut offset = 0;
+            let mut lines = src.split(|&x| x == b'\n').peekable();
+            while let Some(line) = lines.next() {
+                let content = line.strip_suffix(b"\r").unwrap_or(line);
+                let is_last = lines.peek().map_or(true, |next| next.is_empty());
+                match self.scan(content) {
+                    ScanResult::Invalid(pos) => return Error::at(offset + pos),
+                    ScanResult::Padding(pos) if !is_last => return Error::at(offset + pos),
+                    _ => {}
+                }
+                offset += line.len() + 1;
+            }
+            e
+        })
+    }
+}
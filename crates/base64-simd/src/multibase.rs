@@ -0,0 +1,121 @@
+//! Multibase strings with base64 payloads.
+//!
+//! A multibase string starts with a single character which identifies its base.
+//! The base64 variants are
+//!
+//! | prefix | name           | variant                                       |
+//! | ------ | -------------- | --------------------------------------------- |
+//! | `m`    | `base64`       | [`STANDARD_NO_PAD`](crate::STANDARD_NO_PAD)   |
+//! | `M`    | `base64pad`    | [`STANDARD`](crate::STANDARD)                 |
+//! | `u`    | `base64url`    | [`URL_SAFE_NO_PAD`](crate::URL_SAFE_NO_PAD)   |
+//! | `U`    | `base64urlpad` | [`URL_SAFE`](crate::URL_SAFE)                 |
+//!
+//! See <https://github.com/multiformats/multibase>
+//!
+//! # Examples
+//!
+//! ```
+//! use base64_simd::multibase::{self, Variant};
+//!
+//! let s = multibase::encode(Variant::Base64Url, b"hello");
+//! assert_eq!(s, "uaGVsbG8");
+//!
+//! let (variant, data) = multibase::decode(&s).unwrap();
+//! assert_eq!(variant, Variant::Base64Url);
+//! assert_eq!(&*data, b"hello");
+//! ```
+
+use crate::{Base64, Error, STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+
+use alloc::boxed::Box;
+use alloc::string::String;
+
+/// The base64 variants of multibase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variant {
+    /// `m`: standard charset without padding.
+    Base64,
+    /// `M`: standard charset with padding.
+    Base64Pad,
+    /// `u`: URL-safe charset without padding.
+    Base64Url,
+    /// `U`: URL-safe charset with padding.
+    Base64UrlPad,
+}
+
+impl Variant {
+    /// Returns the prefix character of the variant.
+    #[inline]
+    #[must_use]
+    pub const fn prefix(self) -> char {
+        match self {
+            Self::Base64 => 'm',
+            Self::Base64Pad => 'M',
+            Self::Base64Url => 'u',
+            Self::Base64UrlPad => 'U',
+        }
+    }
+
+    /// Returns the variant identified by a prefix character.
+    ///
+    /// Returns `None` if `prefix` does not identify a base64 variant.
+    #[inline]
+    #[must_use]
+    pub const fn from_prefix(prefix: u8) -> Option<Self> {
+        match prefix {
+            b'm' => Some(Self::Base64),
+            b'M' => Some(Self::Base64Pad),
+            b'u' => Some(Self::Base64Url),
+            b'U' => Some(Self::Base64UrlPad),
+            _ => None,
+        }
+    }
+
+    /// Returns the base64 variant used by the payload.
+    #[inline]
+    #[must_use]
+    pub const fn base64(self) -> Base64 {
+        match self {
+            Self::Base64 => STANDARD_NO_PAD,
+            Self::Base64Pad => STANDARD,
+            Self::Base64Url => URL_SAFE_NO_PAD,
+            Self::Base64UrlPad => URL_SAFE,
+        }
+    }
+}
+
+/// Encodes `data` to a multibase string of `variant`.
+///
+/// The allocation is sized exactly for the prefix and the encoded payload.
+#[inline]
+#[must_use]
+pub fn encode(variant: Variant, data: &[u8]) -> String {
+    let base64 = variant.base64();
+    let len = 1 + base64.encoded_length(data.len());
+    let mut s = String::with_capacity(len);
+    s.push(variant.prefix());
+    base64.encode_append(data, &mut s);
+    debug_assert_eq!(s.len(), len);
+    s
+}
+
+/// Decodes a multibase string with a base64 payload.
+///
+/// Returns the variant identified by the prefix and the decoded payload.
+/// The payload must match the padding of the variant exactly.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + `s` is empty, or its prefix does not identify a base64 variant.
+///   The error reports the index `0`.
+/// + the payload is invalid.
+#[inline]
+pub fn decode(s: &str) -> Result<(Variant, Box<[u8]>), Error> {
+    let s = s.as_bytes();
+    let variant = match s.first().copied().and_then(Variant::from_prefix) {
+        Some(variant) => variant,
+        None => return Err(Error::at(0)),
+    };
+    let data = variant.base64().decode_type::<Box<[u8]>>(&s[1..])?;
+    Ok((variant, data))
+}
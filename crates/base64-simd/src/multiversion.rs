@@ -1,4 +1,4 @@
-use crate::{Config, Error};
+use crate::{Config, Error, Kind};
 
 vsimd::dispatch!(
     name        = {encode},
@@ -7,6 +7,7 @@ vsimd::dispatch!(
     simd        = {crate::encode::encode_simd},
     targets     = {"avx2", "ssse3", "neon", "simd128"},
     fastest     = {"avx2", "neon", "simd128"},
+    forced      = {features},
 );
 
 vsimd::dispatch!(
@@ -16,6 +17,27 @@ vsimd::dispatch!(
     simd        = {crate::decode::decode_simd},
     targets     = {"avx2", "ssse3", "neon", "simd128"},
     fastest     = {"avx2", "neon", "simd128"},
+    forced      = {features},
+);
+
+vsimd::dispatch!(
+    name        = {decode_any},
+    signature   = {pub(crate) unsafe fn(src: *const u8, dst: *mut u8, n: usize, config: Config) -> Result<(), Error>},
+    fallback    = {crate::decode::decode_any_fallback},
+    simd        = {crate::decode::decode_any_simd},
+    targets     = {"avx2", "ssse3", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+    forced      = {features},
+);
+
+vsimd::dispatch!(
+    name        = {decode_trusted},
+    signature   = {pub(crate) unsafe fn(src: *const u8, dst: *mut u8, n: usize, config: Config) -> ()},
+    fallback    = {crate::decode::decode_trusted_fallback},
+    simd        = {crate::decode::decode_trusted_simd},
+    targets     = {"avx2", "ssse3", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+    forced      = {features},
 );
 
 vsimd::dispatch!(
@@ -25,6 +47,7 @@ vsimd::dispatch!(
     simd        = {crate::check::check_simd},
     targets     = {"avx2", "ssse3", "neon", "simd128"},
     fastest     = {"avx2", "neon", "simd128"},
+    forced      = {features},
 );
 
 vsimd::dispatch!(
@@ -34,4 +57,45 @@ vsimd::dispatch!(
     simd        = {crate::ascii::find_non_ascii_whitespace_simd},
     targets     = {"avx2", "sse2", "neon", "simd128"},
     fastest     = {"avx2", "neon", "simd128"},
+    forced      = {features},
+);
+
+vsimd::dispatch!(
+    name        = {swap_charset},
+    signature   = {pub(crate) unsafe fn(data: *mut u8, len: usize, c62: u8, c63: u8) -> ()},
+    fallback    = {crate::transcode::swap_charset_fallback},
+    simd        = {crate::transcode::swap_charset_simd},
+    targets     = {"avx2", "sse2", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+    forced      = {features},
+);
+
+vsimd::dispatch!(
+    name        = {scan},
+    signature   = {pub(crate) unsafe fn(src: *const u8, len: usize, kind: Kind) -> usize},
+    fallback    = {crate::scan::scan_fallback},
+    simd        = {crate::scan::scan_simd},
+    targets     = {"avx2", "ssse3", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+    forced      = {features},
+);
+
+vsimd::dispatch!(
+    name        = {filter_ignoring},
+    signature   = {pub(crate) unsafe fn(src: *const u8, len: usize, dst: *mut u8, ignore: &[bool; 256]) -> usize},
+    fallback    = {crate::ignore::filter_ignoring_fallback},
+    simd        = {crate::ignore::filter_ignoring_simd},
+    targets     = {"avx2", "ssse3", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+    forced      = {features},
+);
+
+vsimd::dispatch!(
+    name        = {count_significant},
+    signature   = {pub(crate) unsafe fn(src: *const u8, len: usize, ignore: &[bool; 256]) -> usize},
+    fallback    = {crate::ignore::count_significant_fallback},
+    simd        = {crate::ignore::count_significant_simd},
+    targets     = {"avx2", "ssse3", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+    forced      = {features},
 );
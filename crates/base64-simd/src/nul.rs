@@ -0,0 +1,227 @@
+use crate::decode::decoded_length;
+use crate::{Base64, Config, Error, Out};
+
+use vsimd::tools::slice_mut;
+
+/// How the decoding functions treat NUL bytes (`0x00`) in the input.
+///
+/// See [`Base64::on_nul`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NulPolicy {
+    /// A NUL byte is an invalid character. This is the default.
+    Error,
+    /// The first NUL byte ends the input, as in a C string or a zero-filled record field.
+    Stop,
+    /// NUL bytes are removed before decoding.
+    Skip,
+}
+
+/// The signature of the decoding kernels in [`crate::multiversion`].
+pub(crate) type DecodeKernel = unsafe fn(*const u8, *mut u8, usize, Config) -> Result<(), Error>;
+
+/// Returns the part of `data` before the first NUL byte.
+#[inline(always)]
+pub(crate) fn truncate_at_nul(data: &[u8]) -> &[u8] {
+    match data.iter().position(|&x| x == 0) {
+        Some(pos) => &data[..pos],
+        None => data,
+    }
+}
+
+/// Removes NUL bytes from `data` inplace and returns the remaining part.
+#[inline(always)]
+fn remove_nul_inplace(data: &mut [u8]) -> &mut [u8] {
+    let mut j = 0;
+    for i in 0..data.len() {
+        let x = data[i];
+        if x != 0 {
+            data[j] = x;
+            j += 1;
+        }
+    }
+    &mut data[..j]
+}
+
+/// The number of non-NUL bytes in a chunk of [`for_each_chunk`].
+const CHUNK: usize = 256;
+
+/// Calls `f` with the non-NUL bytes of `data` in chunks.
+///
+/// Every chunk except the last one is full, so its length is a multiple of 4.
+/// The last chunk is empty only if there is no non-NUL byte.
+#[inline(always)]
+pub(crate) fn for_each_chunk(data: &[u8], mut f: impl FnMut(&[u8], bool) -> Result<(), Error>) -> Result<(), Error> {
+    let mut buf = [0u8; CHUNK];
+    let mut len = 0;
+    for &x in data {
+        if x == 0 {
+            continue;
+        }
+        if len == CHUNK {
+            f(&buf, false)?;
+            len = 0;
+        }
+        buf[len] = x;
+        len += 1;
+    }
+    f(&buf[..len], true)
+}
+
+impl Base64 {
+    /// Returns a variant of `self` which treats NUL bytes in the input according to `policy`.
+    ///
+    /// C-string buffers and fixed-size record fields are often filled with NUL bytes after the data.
+    /// + [`NulPolicy::Error`] rejects NUL bytes. This is the behavior of the built-in variants.
+    /// + [`NulPolicy::Stop`] decodes the part before the first NUL byte and ignores the rest.
+    /// + [`NulPolicy::Skip`] decodes the input with all NUL bytes removed.
+    ///
+    /// The policy is applied by [`decoded_length`](Self::decoded_length), [`check`](Self::check),
+    /// [`eq_decoded`](Self::eq_decoded), [`decode`](Self::decode), [`decode_slice`](Self::decode_slice),
+    /// [`decode_inplace`](Self::decode_inplace), [`decode_chunked`](Self::decode_chunked),
+    /// [`decode_with_progress`](Self::decode_with_progress), [`decode_any`](Self::decode_any),
+    /// [`decode_strided`](Self::decode_strided), [`decode_trusted`](Self::decode_trusted)
+    /// and the allocating decoding functions.
+    /// The other decoding functions, including [`decoder`](Self::decoder), reject NUL bytes.
+    ///
+    /// With [`NulPolicy::Skip`], [`decode`](Self::decode) decodes the non-NUL bytes in chunks,
+    /// so `dst` only needs [`decoded_length`](Self::decoded_length) bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_simd::{AsOut, NulPolicy, STANDARD};
+    ///
+    /// let field = b"Zm9vYmFy\0\0\0\0";
+    /// let mut buf = [0u8; 12];
+    ///
+    /// assert!(STANDARD.decode(field, buf.as_mut_slice().as_out()).is_err());
+    ///
+    /// let base64 = STANDARD.on_nul(NulPolicy::Stop);
+    /// assert_eq!(base64.decode(field, buf.as_mut_slice().as_out()).unwrap(), b"foobar");
+    ///
+    /// let base64 = STANDARD.on_nul(NulPolicy::Skip);
+    /// assert_eq!(base64.decode(b"Zm9v\0YmFy", buf.as_mut_slice().as_out()).unwrap(), b"foobar");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn on_nul(self, policy: NulPolicy) -> Self {
+        Self {
+            config: Config {
+                nul: policy,
+                ..self.config
+            },
+        }
+    }
+
+    /// Whether `data` contains NUL bytes which should be skipped.
+    #[inline(always)]
+    pub(crate) fn skips_nul(&self, data: &[u8]) -> bool {
+        matches!(self.config.nul, NulPolicy::Skip) && data.contains(&0)
+    }
+
+    #[inline]
+    pub(crate) fn decoded_length_skipping_nul(&self, data: &[u8]) -> Result<usize, Error> {
+        let mut m = 0;
+        for_each_chunk(data, |chunk, last| {
+            if last {
+                m += decoded_length(chunk, self.config)?.1;
+            } else {
+                m += chunk.len() / 4 * 3;
+            }
+            Ok(())
+        })?;
+        Ok(m)
+    }
+
+    #[inline]
+    pub(crate) fn check_skipping_nul(&self, data: &[u8]) -> Result<(), Error> {
+        for_each_chunk(data, |chunk, last| {
+            let n = if last {
+                decoded_length(chunk, self.config)?.0
+            } else {
+                chunk.len()
+            };
+            unsafe { crate::multiversion::check::auto(chunk.as_ptr(), n, self.config) }
+        })
+    }
+
+    #[inline]
+    pub(crate) fn decode_skipping_nul<'d>(&self, src: &[u8], dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        self.decode_skipping_nul_with(src, dst, crate::multiversion::decode::auto)
+    }
+
+    /// Decodes the non-NUL bytes of a string which has been checked, without validation.
+    #[inline]
+    pub(crate) unsafe fn decode_trusted_skipping_nul<'d>(&self, src: &[u8], dst: Out<'d, [u8]>) -> &'d mut [u8] {
+        #[allow(clippy::unnecessary_wraps)]
+        unsafe fn kernel(src: *const u8, dst: *mut u8, n: usize, config: Config) -> Result<(), Error> {
+            crate::multiversion::decode_trusted::auto(src, dst, n, config);
+            Ok(())
+        }
+
+        let m = self.decoded_length_skipping_nul(src).unwrap_unchecked();
+        assert!(dst.len() >= m);
+        self.decode_skipping_nul_with(src, dst, kernel).unwrap_unchecked()
+    }
+
+    /// Decodes the non-NUL bytes of `src` chunk by chunk with `kernel`,
+    /// so `dst` only needs the decoded length.
+    #[inline]
+    pub(crate) fn decode_skipping_nul_with<'d>(
+        &self,
+        src: &[u8],
+        mut dst: Out<'d, [u8]>,
+        kernel: DecodeKernel,
+    ) -> Result<&'d mut [u8], Error> {
+        let m = self.decoded_length_skipping_nul(src)?;
+        ensure_capacity!(dst.len(), m);
+
+        let dst = dst.as_mut_ptr();
+        let mut written = 0;
+        for_each_chunk(src, |chunk, last| {
+            let (n, k) = if last {
+                decoded_length(chunk, self.config)?
+            } else {
+                (chunk.len(), chunk.len() / 4 * 3)
+            };
+            if n > 0 {
+                unsafe { kernel(chunk.as_ptr(), dst.add(written), n, self.config)? };
+            }
+            written += k;
+            Ok(())
+        })?;
+        debug_assert_eq!(written, m);
+
+        unsafe { Ok(slice_mut(dst, m)) }
+    }
+
+    #[inline]
+    pub(crate) fn eq_decoded_skipping_nul(&self, b64: &[u8], bytes: &[u8]) -> bool {
+        match self.decoded_length_skipping_nul(b64) {
+            Ok(m) if m == bytes.len() => {}
+            _ => return false,
+        }
+
+        let mut buf = [0u8; CHUNK / 4 * 3];
+        let mut expected = bytes;
+        let result = for_each_chunk(b64, |chunk, last| {
+            let (n, k) = if last {
+                decoded_length(chunk, self.config)?
+            } else {
+                (chunk.len(), chunk.len() / 4 * 3)
+            };
+            if n > 0 {
+                unsafe { crate::multiversion::decode::auto(chunk.as_ptr(), buf.as_mut_ptr(), n, self.config)? };
+            }
+            ensure!(buf[..k] == expected[..k]);
+            expected = &expected[k..];
+            Ok(())
+        });
+        result.is_ok()
+    }
+
+    #[inline]
+    pub(crate) fn decode_inplace_skipping_nul<'d>(&self, data: &'d mut [u8]) -> Result<&'d mut [u8], Error> {
+        self.decode_inplace(remove_nul_inplace(data))
+    }
+}
@@ -31,8 +31,8 @@ impl Base64 {
 
         if self.config.extra.padding() {
             let no_pad = Config {
-                kind: self.config.kind,
                 extra: Extra::NoPad,
+                ..self.config
             };
             src_chunks.zip(dst_chunks).for_each(|(s, d)| unsafe {
                 let len = s.len();
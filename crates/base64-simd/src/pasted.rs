@@ -0,0 +1,86 @@
+use crate::{Base64, Error, ErrorKind, Out, ScanResult};
+
+#[cfg(feature = "alloc")]
+use crate::AsOut;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+const MARKER: &[u8] = b"base64,";
+
+/// Strips the copy-paste cruft around a payload and returns the offset and the payload.
+#[inline(always)]
+fn strip_cruft(src: &[u8]) -> (usize, &[u8]) {
+    let trim_start = |s: &[u8]| s.iter().position(|&x| x != b',').unwrap_or(s.len());
+
+    let mut offset = trim_start(src);
+    let rest = &src[offset..];
+    if rest.len() >= MARKER.len() && rest[..MARKER.len()].eq_ignore_ascii_case(MARKER) {
+        offset += MARKER.len();
+        offset += trim_start(&src[offset..]);
+    }
+
+    let rest = &src[offset..];
+    let len = rest.iter().rposition(|&x| x != b',').map_or(0, |i| i + 1);
+    (offset, &rest[..len])
+}
+
+impl Base64 {
+    /// Decodes a base64 string copied from a data URL or a similar context.
+    ///
+    /// The common copy-paste cruft is removed before a strict decoding:
+    /// + leading and trailing commas.
+    /// + a leading `base64,` marker, which is matched case-insensitively.
+    ///
+    /// Anything else, including whitespace, is decoded as is.
+    /// Use [`data_uri::decode`](crate::data_uri::decode) for complete data URLs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_simd::{AsOut, STANDARD};
+    ///
+    /// let mut buf = [0u8; 8];
+    /// let ans = STANDARD.decode_pasted(b",base64,aGVsbG8=", buf.as_mut_slice().as_out()).unwrap();
+    /// assert_eq!(ans, b"hello");
+    ///
+    /// let err = STANDARD.decode_pasted(b"base64,aGV*bG8=", buf.as_mut_slice().as_out()).unwrap_err();
+    /// assert_eq!(err.index(), Some(10));
+    /// ```
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid after removing the cruft.
+    ///   If an invalid character is found, [`Error::index`] returns its index in `src`.
+    #[inline]
+    pub fn decode_pasted<'d>(&self, src: &[u8], dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        let (offset, payload) = strip_cruft(src);
+        self.decode(payload, dst).map_err(|e| {
+            if e.kind() == ErrorKind::InsufficientCapacity {
+                return e;
+            }
+            match self.scan(payload) {
+                ScanResult::Invalid(pos) => Error::at(offset + pos),
+                ScanResult::Valid | ScanResult::Padding(_) => e,
+            }
+        })
+    }
+
+    /// Decodes a base64 string copied from a data URL or a similar context and returns a new [`Vec`].
+    ///
+    /// See [`decode_pasted`](Self::decode_pasted).
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid after removing the cruft.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn decode_pasted_to_vec(&self, data: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
+        let src = data.as_ref();
+        let mut buf = alloc::vec![0; self.estimated_decoded_length(src.len())];
+        let len = self.decode_pasted(src, buf.as_mut_slice().as_out())?.len();
+        buf.truncate(len);
+        Ok(buf)
+    }
+}
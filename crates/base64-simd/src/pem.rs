@@ -0,0 +1,144 @@
+//! PEM objects.
+//!
+//! A PEM object is a base64 payload between a `-----BEGIN <label>-----` line
+//! and a matching `-----END <label>-----` line.
+//! The payload uses the [`STANDARD`](crate::STANDARD) variant and is usually wrapped at 64 characters.
+//!
+//! Text outside the objects, such as blank lines and comments, is ignored.
+//! Explanatory headers inside an object ([RFC 1421](https://datatracker.ietf.org/doc/html/rfc1421)) are not supported.
+//!
+//! See <https://datatracker.ietf.org/doc/html/rfc7468>
+//!
+//! # Examples
+//!
+//! ```
+//! use base64_simd::pem;
+//!
+//! let chain = concat!(
+//!     "# leaf\n",
+//!     "-----BEGIN CERTIFICATE-----\n",
+//!     "aGVsbG8=\n",
+//!     "-----END CERTIFICATE-----\n",
+//!     "\n",
+//!     "# root\n",
+//!     "-----BEGIN CERTIFICATE-----\n",
+//!     "d29y\n",
+//!     "bGQ=\n",
+//!     "-----END CERTIFICATE-----\n",
+//! );
+//!
+//! let objects = pem::decode_all(chain).unwrap();
+//! assert_eq!(objects.len(), 2);
+//! assert_eq!(objects[0].0, "CERTIFICATE");
+//! assert_eq!(&*objects[0].1, b"hello");
+//! assert_eq!(&*objects[1].1, b"world");
+//!
+//! let (label, data) = pem::decode(chain).unwrap();
+//! assert_eq!((label.as_str(), &*data), ("CERTIFICATE", &b"hello"[..]));
+//! ```
+
+use crate::{Error, STANDARD};
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+const BEGIN: &str = "-----BEGIN ";
+const END: &str = "-----END ";
+const DASHES: &str = "-----";
+
+/// Decodes the first PEM object in `data`.
+///
+/// Returns the label and the decoded payload. The text after the object is ignored.
+/// Use [`decode_all`] for certificate chains.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + there is no object in `data`.
+/// + the first object is not terminated by a matching `END` line.
+/// + the payload of the first object is invalid.
+#[inline]
+pub fn decode(data: &str) -> Result<(String, Box<[u8]>), Error> {
+    match next_object(data)? {
+        Some(obj) => Ok((obj.label.into(), decode_payload(obj.payload)?)),
+        None => Err(Error::new()),
+    }
+}
+
+/// Decodes all PEM objects in `data`, such as a certificate chain.
+///
+/// Returns the labels and the decoded payloads in order.
+/// The result is empty if there is no object in `data`.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + an object is not terminated by a matching `END` line.
+/// + the payload of an object is invalid.
+#[allow(clippy::type_complexity)]
+#[inline]
+pub fn decode_all(data: &str) -> Result<Vec<(String, Box<[u8]>)>, Error> {
+    let mut ans = Vec::new();
+    let mut rest = data;
+    while let Some(obj) = next_object(rest)? {
+        ans.push((obj.label.into(), decode_payload(obj.payload)?));
+        rest = obj.rest;
+    }
+    Ok(ans)
+}
+
+/// Splits the first line from `data`, including the line break.
+fn split_line(data: &str) -> (&str, &str) {
+    let end = data.find('\n').map_or(data.len(), |i| i + 1);
+    data.split_at(end)
+}
+
+/// Returns the label if `line` is a boundary line starting with `prefix`.
+fn parse_boundary<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    let label = line.trim_end().strip_prefix(prefix)?.strip_suffix(DASHES)?;
+    label.bytes().all(|x| matches!(x, 0x20..=0x7e)).then_some(label)
+}
+
+struct Object<'a> {
+    label: &'a str,
+    payload: &'a str,
+    /// The text after the object.
+    rest: &'a str,
+}
+
+/// Finds the next object in `data`.
+fn next_object(data: &str) -> Result<Option<Object<'_>>, Error> {
+    let mut rest = data;
+    let label = loop {
+        if rest.is_empty() {
+            return Ok(None);
+        }
+        let (line, tail) = split_line(rest);
+        rest = tail;
+        if let Some(label) = parse_boundary(line, BEGIN) {
+            break label;
+        }
+    };
+
+    let body = rest;
+    while !rest.is_empty() {
+        let (line, tail) = split_line(rest);
+        if line.starts_with(END) {
+            ensure!(parse_boundary(line, END) == Some(label));
+            let payload = &body[..body.len() - rest.len()];
+            return Ok(Some(Object {
+                label,
+                payload,
+                rest: tail,
+            }));
+        }
+        rest = tail;
+    }
+    Err(Error::new())
+}
+
+fn decode_payload(payload: &str) -> Result<Box<[u8]>, Error> {
+    let mut buf = payload.as_bytes().to_vec();
+    let len = STANDARD.decode_text_inplace(&mut buf)?.len();
+    buf.truncate(len);
+    Ok(buf.into_boxed_slice())
+}
@@ -0,0 +1,124 @@
+use crate::ascii::*;
+use crate::decode::decoded_length;
+use crate::{Base64, Error, Out, STANDARD_FORGIVING};
+
+use vsimd::tools::slice_mut;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// A maximally permissive base64 decoder.
+///
+/// It accepts anything which looks like base64:
+/// + both the standard and the URL-safe charsets, which may be mixed.
+/// + ASCII whitespace anywhere, which is removed.
+/// + any number of trailing `=`, including none.
+/// + non-zero unused bits of the last character, which are discarded.
+///
+/// A trailing single character can not form a byte, so it is still rejected.
+///
+/// This is meant for ingesting messy input. The strict variants are recommended when the format is known.
+///
+/// # Examples
+///
+/// ```
+/// let relaxed = base64_simd::Base64::relaxed();
+///
+/// let mut buf = *b" Zm9v-_8_\r\nYx=== ";
+/// assert_eq!(relaxed.decode_inplace(&mut buf).unwrap(), b"foo\xfb\xff\x3fc");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Base64Relaxed(());
+
+impl Base64 {
+    /// Returns a [`Base64Relaxed`] decoder.
+    #[inline]
+    #[must_use]
+    pub const fn relaxed() -> Base64Relaxed {
+        Base64Relaxed(())
+    }
+}
+
+/// Removes the trailing `=` characters.
+#[inline(always)]
+fn strip_padding(data: &mut [u8]) -> &mut [u8] {
+    let len = data.iter().rposition(|&x| x != b'=').map_or(0, |i| i + 1);
+    &mut data[..len]
+}
+
+impl Base64Relaxed {
+    /// Decodes a base64-ish string to bytes and writes inplace.
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid after normalization.
+    #[inline]
+    pub fn decode_inplace<'d>(&self, data: &'d mut [u8]) -> Result<&'d mut [u8], Error> {
+        let data = strip_padding(remove_ascii_whitespace_inplace(data));
+        decode_normalized_inplace(data)
+    }
+
+    /// Decodes a base64-ish string to bytes.
+    ///
+    /// `dst` is also used as a scratch buffer for the normalized input.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + `src.len() > dst.len()`
+    /// + the content of `src` is invalid after normalization.
+    #[inline]
+    pub fn decode<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        ensure_capacity!(dst.len(), src.len());
+
+        unsafe {
+            let dst = dst.as_mut_ptr();
+            let len = remove_ascii_whitespace_fallback(src.as_ptr(), src.len(), dst);
+            debug_assert!(len <= src.len());
+
+            let data = strip_padding(slice_mut(dst, len));
+            decode_normalized_inplace(data)
+        }
+    }
+
+    /// Decodes a base64-ish string to bytes and returns a new [`Box<[u8]>`](Box).
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid after normalization.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn decode_to_boxed_bytes(&self, data: impl AsRef<[u8]>) -> Result<Box<[u8]>, Error> {
+        self.decode_to_vec(data).map(Vec::into_boxed_slice)
+    }
+
+    /// Decodes a base64-ish string to bytes and returns a new [`Vec<u8>`](Vec).
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid after normalization.
+    #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn decode_to_vec(&self, data: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
+        let mut buf = data.as_ref().to_vec();
+        let len = self.decode_inplace(&mut buf)?.len();
+        buf.truncate(len);
+        Ok(buf)
+    }
+}
+
+/// Decodes a string without whitespace and padding inplace, accepting both charsets.
+#[inline(always)]
+fn decode_normalized_inplace(data: &mut [u8]) -> Result<&mut [u8], Error> {
+    unsafe {
+        let config = STANDARD_FORGIVING.config;
+        let (n, m) = decoded_length(data, config)?;
+
+        let dst: *mut u8 = data.as_mut_ptr();
+        let src: *const u8 = dst;
+        crate::multiversion::decode_any::auto(src, dst, n, config)?;
+
+        Ok(slice_mut(dst, m))
+    }
+}
@@ -0,0 +1,93 @@
+use crate::alsw::{SORTABLE_ALSW_CHECK_X2, STANDARD_ALSW_CHECK_X2, URL_SAFE_ALSW_CHECK_X2};
+use crate::decode::{SORTABLE_DECODE_TABLE, STANDARD_DECODE_TABLE, URL_SAFE_DECODE_TABLE};
+use crate::{Base64, Kind};
+
+use vsimd::tools::read;
+use vsimd::SIMD256;
+
+/// The result of [`Base64::scan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanResult {
+    /// Every byte is an alphabet character.
+    Valid,
+    /// The alphabet characters stop at this index, and the rest of the input is a run of padding characters.
+    Padding(usize),
+    /// The byte at this index is neither an alphabet character nor a part of the trailing padding.
+    Invalid(usize),
+}
+
+/// Returns the index of the first byte which is not an alphabet character, or `len` if there is none.
+#[inline(always)]
+pub(crate) unsafe fn scan_fallback(src: *const u8, len: usize, kind: Kind) -> usize {
+    let table = match kind {
+        Kind::Standard => STANDARD_DECODE_TABLE.as_ptr(),
+        Kind::UrlSafe => URL_SAFE_DECODE_TABLE.as_ptr(),
+        Kind::Sortable => SORTABLE_DECODE_TABLE.as_ptr(),
+    };
+
+    let mut i = 0;
+    while i < len {
+        if read(table, read(src, i) as usize) == 0xff {
+            break;
+        }
+        i += 1;
+    }
+    i
+}
+
+#[inline(always)]
+pub(crate) unsafe fn scan_simd<S: SIMD256>(s: S, src: *const u8, len: usize, kind: Kind) -> usize {
+    let check_lut = match kind {
+        Kind::Standard => STANDARD_ALSW_CHECK_X2,
+        Kind::UrlSafe => URL_SAFE_ALSW_CHECK_X2,
+        Kind::Sortable => SORTABLE_ALSW_CHECK_X2,
+    };
+
+    // Skip the valid blocks, then locate the invalid byte in the first invalid block.
+    let mut i = 0;
+    while len - i >= 32 {
+        let x = s.v256_load_unaligned(src.add(i));
+        if !vsimd::alsw::check_ascii_xn(s, x, check_lut) {
+            break;
+        }
+        i += 32;
+    }
+
+    i + scan_fallback(src.add(i), len - i, kind)
+}
+
+impl Base64 {
+    /// Scans `data` for the first byte which is not an alphabet character.
+    ///
+    /// The input is checked in 32-byte blocks with SIMD instructions.
+    /// A trailing run of padding characters is reported as [`ScanResult::Padding`].
+    /// Any other byte, including a padding character which is followed by a non-padding byte,
+    /// is reported as [`ScanResult::Invalid`].
+    ///
+    /// Only the characters are checked. The length and the number of padding characters
+    /// are not validated, so a string may be scanned in chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_simd::{ScanResult, STANDARD};
+    ///
+    /// assert_eq!(STANDARD.scan(b"aGVsbG8"), ScanResult::Valid);
+    /// assert_eq!(STANDARD.scan(b"aGVsbG8="), ScanResult::Padding(7));
+    /// assert_eq!(STANDARD.scan(b"aGVs bG8="), ScanResult::Invalid(4));
+    /// assert_eq!(STANDARD.scan(b"aGVsbG8=a"), ScanResult::Invalid(7));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn scan(&self, data: &[u8]) -> ScanResult {
+        let pos = unsafe { crate::multiversion::scan::auto(data.as_ptr(), data.len(), self.config.kind) };
+        if pos == data.len() {
+            return ScanResult::Valid;
+        }
+        if data[pos..].iter().all(|&x| x == self.config.pad) {
+            ScanResult::Padding(pos)
+        } else {
+            ScanResult::Invalid(pos)
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::encode::{checked_encoded_length, encoded_length_unchecked};
+use crate::{Base64, Config};
+
+use std::io;
+
+/// A writer that computes the encoded length of everything written to it, without encoding.
+///
+/// It can be used as a sizing pass before allocating an exact output buffer.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+///
+/// let base64 = base64_simd::STANDARD;
+/// let mut sizing = base64.sizing_writer();
+/// sizing.write_all(b"hello ").unwrap();
+/// sizing.write_all(b"world").unwrap();
+///
+/// assert_eq!(sizing.encoded_length(), base64.encode_to_string("hello world").len());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SizingWriter {
+    config: Config,
+    len: usize,
+}
+
+impl SizingWriter {
+    /// Returns the number of input bytes written so far.
+    #[inline]
+    #[must_use]
+    pub fn input_length(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the encoded length of the input bytes written so far.
+    ///
+    /// The writer rejects the bytes which would make the encoded length greater than `isize::MAX`,
+    /// so the result is always the length of a valid buffer.
+    #[inline]
+    #[must_use]
+    pub fn encoded_length(&self) -> usize {
+        encoded_length_unchecked(self.len, self.config)
+    }
+}
+
+impl io::Write for SizingWriter {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.len.checked_add(buf.len()) {
+            Some(len) if checked_encoded_length(len, self.config).is_some() => {
+                self.len = len;
+                Ok(buf.len())
+            }
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "input is too long")),
+        }
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Base64 {
+    /// Creates a [`SizingWriter`] which computes the encoded length of the bytes written to it.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[inline]
+    #[must_use]
+    pub fn sizing_writer(&self) -> SizingWriter {
+        SizingWriter {
+            config: self.config,
+            len: 0,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{AsOut, Base64, Error, FromBase64Decode};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use core::fmt;
+use core::ops::Deref;
+
+/// Decoded bytes which are stored inline if there are at most `N` of them.
+///
+/// Larger payloads spill to a heap allocation.
+/// Many base64 tokens, such as keys, nonces and UUIDs, decode to at most 32 bytes,
+/// so decoding them to `SmallBytes` does not allocate.
+///
+/// It implements [`FromBase64Decode`], so other inline sizes can be decoded by [`Base64::decode_type`].
+///
+/// # Examples
+///
+/// ```
+/// use base64_simd::{SmallBytes, STANDARD};
+///
+/// let key = STANDARD.decode_to_small_bytes("aGVsbG8=").unwrap();
+/// assert_eq!(&*key, b"hello");
+/// assert!(key.is_inline());
+///
+/// let key: SmallBytes<4> = STANDARD.decode_type("aGVsbG8=").unwrap();
+/// assert_eq!(&*key, b"hello");
+/// assert!(!key.is_inline());
+/// ```
+#[derive(Clone)]
+pub struct SmallBytes<const N: usize = 32>(Repr<N>);
+
+#[derive(Clone)]
+enum Repr<const N: usize> {
+    Inline { buf: [u8; N], len: usize },
+    Heap(Box<[u8]>),
+}
+
+impl<const N: usize> SmallBytes<N> {
+    /// Returns the bytes.
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        match &self.0 {
+            Repr::Inline { buf, len } => &buf[..*len],
+            Repr::Heap(b) => b,
+        }
+    }
+
+    /// Returns `true` if the bytes are stored inline.
+    #[inline]
+    #[must_use]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+
+    /// Converts the bytes into a [`Vec`].
+    #[inline]
+    #[must_use]
+    pub fn into_vec(self) -> Vec<u8> {
+        match self.0 {
+            Repr::Inline { buf, len } => buf[..len].to_vec(),
+            Repr::Heap(b) => b.into_vec(),
+        }
+    }
+}
+
+impl<const N: usize> Deref for SmallBytes<N> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> AsRef<[u8]> for SmallBytes<N> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize, const M: usize> PartialEq<SmallBytes<M>> for SmallBytes<N> {
+    #[inline]
+    fn eq(&self, other: &SmallBytes<M>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const N: usize> Eq for SmallBytes<N> {}
+
+impl<const N: usize> fmt::Debug for SmallBytes<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<const N: usize> FromBase64Decode for SmallBytes<N> {
+    #[inline]
+    fn from_base64_decode(base64: &Base64, data: &[u8]) -> Result<Self, Error> {
+        let m = base64.decoded_length(data)?;
+        if m > N {
+            return Ok(Self(Repr::Heap(base64.decode_type(data)?)));
+        }
+        let mut buf = [0; N];
+        let len = base64.decode(data, buf.as_mut_slice().as_out())?.len();
+        Ok(Self(Repr::Inline { buf, len }))
+    }
+}
+
+impl Base64 {
+    /// Decodes a base64 string to bytes which are stored inline if there are at most 32 of them.
+    ///
+    /// See [`SmallBytes`].
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid.
+    #[inline]
+    pub fn decode_to_small_bytes(&self, data: impl AsRef<[u8]>) -> Result<SmallBytes, Error> {
+        self.decode_type(data)
+    }
+}
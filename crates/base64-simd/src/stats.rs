@@ -0,0 +1,65 @@
+//! Counters of the input handled by SIMD and scalar code.
+//!
+//! The counters show whether the input sizes of a workload keep the work in the SIMD loops.
+//! Many tiny inputs are mostly handled by the scalar code around the loops,
+//! which suggests batching the inputs or using larger buffers.
+//!
+//! + the encoders count input bytes.
+//! + the decoders count input characters, excluding padding.
+//!
+//! The counters are global and shared by all threads.
+//! Only the encoding and decoding functions are counted.
+//!
+//! This module is only available with the feature flag `stats`.
+//! Without it, the kernels have no instrumentation.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64_simd::{stats, AsOut, STANDARD};
+//!
+//! stats::reset();
+//!
+//! let mut buf = [0u8; 4];
+//! STANDARD.encode(b"abc", buf.as_mut_slice().as_out()).unwrap();
+//! assert_eq!(stats::bulk_bytes() + stats::tail_bytes(), 3);
+//! ```
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+static BULK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static TAIL_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the number of input bytes handled by SIMD code.
+#[inline]
+#[must_use]
+pub fn bulk_bytes() -> usize {
+    BULK_BYTES.load(Ordering::Relaxed)
+}
+
+/// Returns the number of input bytes handled by scalar code.
+///
+/// It includes the partial blocks at the edges of the SIMD loops,
+/// the constant-time variants, and the whole input if no SIMD instruction set is available.
+#[inline]
+#[must_use]
+pub fn tail_bytes() -> usize {
+    TAIL_BYTES.load(Ordering::Relaxed)
+}
+
+/// Resets both counters to zero.
+#[inline]
+pub fn reset() {
+    BULK_BYTES.store(0, Ordering::Relaxed);
+    TAIL_BYTES.store(0, Ordering::Relaxed);
+}
+
+#[inline(always)]
+pub(crate) fn add_bulk(n: usize) {
+    BULK_BYTES.fetch_add(n, Ordering::Relaxed);
+}
+
+#[inline(always)]
+pub(crate) fn add_tail(n: usize) {
+    TAIL_BYTES.fetch_add(n, Ordering::Relaxed);
+}
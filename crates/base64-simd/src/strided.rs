@@ -0,0 +1,113 @@
+use crate::decode::decoded_length;
+use crate::nul::for_each_chunk;
+use crate::{Base64, Error};
+
+/// The size of the scratch buffer, in characters.
+const SCRATCH_CHARS: usize = 256;
+
+/// Returns the length of `dst` needed to write `m` bytes from `offset` with `stride`,
+/// or `usize::MAX` if it overflows.
+#[inline(always)]
+fn required_len(m: usize, stride: usize, offset: usize) -> usize {
+    if m == 0 {
+        return 0;
+    }
+    (m - 1)
+        .checked_mul(stride)
+        .and_then(|x| x.checked_add(offset))
+        .and_then(|x| x.checked_add(1))
+        .unwrap_or(usize::MAX)
+}
+
+impl Base64 {
+    /// Decodes a base64 string and writes the `i`-th decoded byte to `dst[offset + i * stride]`.
+    ///
+    /// The other bytes of `dst` are left untouched,
+    /// so several planes of an interleaved buffer can be filled without a separate de-interleaving pass.
+    /// The input is decoded by SIMD code in chunks, and each chunk is scattered to `dst` from a stack buffer.
+    ///
+    /// With `stride == 1`, this is the same as [`decode_slice`](Self::decode_slice) on `dst[offset..]`.
+    ///
+    /// Returns the number of decoded bytes.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough for the last decoded byte.
+    /// + the content of `src` is invalid.
+    ///
+    /// # Panics
+    /// This function asserts that `stride > 0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_simd::STANDARD;
+    ///
+    /// let mut rgb = [0u8; 6];
+    /// assert_eq!(STANDARD.decode_strided(b"AQI=", &mut rgb, 3, 0).unwrap(), 2); // [1, 2]
+    /// assert_eq!(STANDARD.decode_strided(b"AwQ=", &mut rgb, 3, 1).unwrap(), 2); // [3, 4]
+    /// assert_eq!(STANDARD.decode_strided(b"BQY=", &mut rgb, 3, 2).unwrap(), 2); // [5, 6]
+    /// assert_eq!(rgb, [1, 3, 5, 2, 4, 6]);
+    /// ```
+    #[inline]
+    pub fn decode_strided(&self, src: &[u8], dst: &mut [u8], stride: usize, offset: usize) -> Result<usize, Error> {
+        assert!(stride > 0);
+
+        if stride == 1 && offset <= dst.len() {
+            let dst = &mut dst[offset..];
+            return self.decode_slice(src, dst).map_err(|e| e.offset_capacity(offset));
+        }
+
+        let skips_nul = self.skips_nul(src);
+        let (mut n, m) = if skips_nul {
+            (0, self.decoded_length_skipping_nul(src)?)
+        } else {
+            decoded_length(src, self.config)?
+        };
+        let required = required_len(m, stride, offset);
+        ensure_capacity!(dst.len(), required);
+
+        if m == 0 {
+            return Ok(0);
+        }
+
+        let mut buf = [0u8; SCRATCH_CHARS / 4 * 3];
+        let mut pos = offset;
+        // Decodes `len` characters from `chunk` to `count` bytes and scatters them.
+        let mut put = |chunk: *const u8, len: usize, count: usize| -> Result<(), Error> {
+            if len == 0 {
+                return Ok(());
+            }
+            unsafe { crate::multiversion::decode::auto(chunk, buf.as_mut_ptr(), len, self.config)? };
+            for &x in &buf[..count] {
+                dst[pos] = x;
+                pos += stride;
+            }
+            Ok(())
+        };
+
+        if skips_nul {
+            for_each_chunk(src, |chunk, last| {
+                let (len, count) = if last {
+                    decoded_length(chunk, self.config)?
+                } else {
+                    (chunk.len(), chunk.len() / 4 * 3)
+                };
+                put(chunk.as_ptr(), len, count)
+            })?;
+        } else {
+            let mut src = src.as_ptr();
+            let mut written = 0;
+            while n > 0 {
+                let len = if n > SCRATCH_CHARS { SCRATCH_CHARS } else { n };
+                let count = if len == n { m - written } else { len / 4 * 3 };
+                put(src, len, count)?;
+                src = unsafe { src.add(len) };
+                written += count;
+                n -= len;
+            }
+        }
+
+        Ok(m)
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{Base64, Error, Out};
+
+use vsimd::tools::{read, slice_mut, write};
+
+/// Returns the length of the line separator or BOM at `src[i..len]`, or 0 if there is none.
+///
+/// + U+2028 LINE SEPARATOR: `E2 80 A8`
+/// + U+2029 PARAGRAPH SEPARATOR: `E2 80 A9`
+/// + U+FEFF BYTE ORDER MARK: `EF BB BF`
+#[inline(always)]
+unsafe fn unicode_separator_len(src: *const u8, i: usize, len: usize) -> usize {
+    if len - i < 3 {
+        return 0;
+    }
+    match [read(src, i), read(src, i + 1), read(src, i + 2)] {
+        [0xe2, 0x80, 0xa8 | 0xa9] | [0xef, 0xbb, 0xbf] => 3,
+        _ => 0,
+    }
+}
+
+/// Removes ASCII whitespace and the UTF-8 sequences of U+2028, U+2029 and U+FEFF.
+///
+/// Writes the result to `dst` and returns its length, which is not greater than `len`.
+/// `src` and `dst` may be the same pointer.
+#[inline(always)]
+unsafe fn remove_text_whitespace(src: *const u8, len: usize, dst: *mut u8) -> usize {
+    let mut i = 0;
+    let mut j = 0;
+    while i < len {
+        let x = read(src, i);
+        if x.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        if x >= 0x80 {
+            let k = unicode_separator_len(src, i, len);
+            if k > 0 {
+                i += k;
+                continue;
+            }
+        }
+        write(dst, j, x);
+        i += 1;
+        j += 1;
+    }
+    j
+}
+
+#[inline(always)]
+fn find_text_whitespace(data: &[u8]) -> Option<usize> {
+    data.iter()
+        .position(|&x| x.is_ascii_whitespace() || x == 0xe2 || x == 0xef)
+}
+
+impl Base64 {
+    /// Decodes a base64 string extracted from text to bytes and writes inplace.
+    ///
+    /// ASCII whitespace and the UTF-8 sequences of U+2028 LINE SEPARATOR, U+2029 PARAGRAPH SEPARATOR
+    /// and U+FEFF BYTE ORDER MARK are removed before a strict decoding.
+    /// Other non-ASCII bytes are rejected.
+    ///
+    /// # Errors
+    /// This function returns `Err` if the content of `data` is invalid after removing the separators.
+    #[inline]
+    pub fn decode_text_inplace<'d>(&self, data: &'d mut [u8]) -> Result<&'d mut [u8], Error> {
+        let pos = match find_text_whitespace(data) {
+            Some(pos) => pos,
+            None => return self.decode_inplace(data),
+        };
+
+        unsafe {
+            let len = data.len();
+            let dst = data.as_mut_ptr();
+            let rem = remove_text_whitespace(dst.add(pos), len - pos, dst.add(pos));
+            self.decode_inplace(slice_mut(dst, pos + rem))
+        }
+    }
+
+    /// Decodes a base64 string extracted from text to bytes.
+    ///
+    /// See [`decode_text_inplace`](Self::decode_text_inplace) for the removed separators.
+    /// If `src` contains separators, `dst` is also used as a scratch buffer for the filtered input.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the length of `dst` is not enough.
+    /// + the content of `src` is invalid after removing the separators.
+    #[inline]
+    pub fn decode_text<'d>(&self, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+        let pos = match find_text_whitespace(src) {
+            Some(pos) => pos,
+            None => return self.decode(src, dst),
+        };
+        ensure_capacity!(dst.len(), src.len());
+
+        unsafe {
+            let len = src.len();
+            let src = src.as_ptr();
+            let dst = dst.as_mut_ptr();
+
+            core::ptr::copy_nonoverlapping(src, dst, pos);
+            let rem = remove_text_whitespace(src.add(pos), len - pos, dst.add(pos));
+            self.decode_inplace(slice_mut(dst, pos + rem))
+        }
+    }
+}
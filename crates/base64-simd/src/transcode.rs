@@ -0,0 +1,69 @@
+use vsimd::isa::AVX2;
+use vsimd::tools::{read, write};
+use vsimd::{matches_isa, Scalable, POD, SIMD256};
+
+// '+' (0x2b) <-> '-' (0x2d)
+const XOR_62: u8 = b'+' ^ b'-';
+
+// '/' (0x2f) <-> '_' (0x5f)
+const XOR_63: u8 = b'/' ^ b'_';
+
+#[inline(always)]
+fn swap_charset<S: Scalable<V>, V: POD>(s: S, x: V, c62: u8, c63: u8) -> V {
+    let m62 = s.u8xn_eq(x, s.u8xn_splat(c62));
+    let m63 = s.u8xn_eq(x, s.u8xn_splat(c63));
+    let delta = s.or(s.and(m62, s.u8xn_splat(XOR_62)), s.and(m63, s.u8xn_splat(XOR_63)));
+    s.xor(x, delta)
+}
+
+/// Replaces the characters 62 and 63 of the source charset with those of the other charset.
+#[inline(always)]
+pub unsafe fn swap_charset_fallback(mut data: *mut u8, len: usize, c62: u8, c63: u8) {
+    let end = data.add(len);
+    while data < end {
+        let x = data.read();
+        if x == c62 {
+            data.write(x ^ XOR_62);
+        } else if x == c63 {
+            data.write(x ^ XOR_63);
+        }
+        data = data.add(1);
+    }
+}
+
+#[inline(always)]
+pub unsafe fn swap_charset_simd<S: SIMD256>(s: S, mut data: *mut u8, mut len: usize, c62: u8, c63: u8) {
+    if matches_isa!(S, AVX2) {
+        while len >= 32 {
+            let x = s.v256_load_unaligned(data);
+            let y = swap_charset(s, x, c62, c63);
+            s.v256_store_unaligned(data, y);
+            data = data.add(32);
+            len -= 32;
+        }
+    }
+
+    while len >= 16 {
+        let x = s.v128_load_unaligned(data);
+        let y = swap_charset(s, x, c62, c63);
+        s.v128_store_unaligned(data, y);
+        data = data.add(16);
+        len -= 16;
+    }
+
+    swap_charset_fallback(data, len, c62, c63);
+}
+
+/// Maps every character through `table` and `charset`.
+///
+/// The characters should be valid for `table`. An invalid character is mapped to an unspecified character.
+#[inline]
+pub unsafe fn remap_charset(data: *mut u8, len: usize, table: &[u8; 256], charset: &[u8; 64]) {
+    let mut i = 0;
+    while i < len {
+        let x = table[read(data, i) as usize];
+        debug_assert!(x < 64);
+        write(data, i, charset[(x & 0x3f) as usize]);
+        i += 1;
+    }
+}
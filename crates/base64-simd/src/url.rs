@@ -0,0 +1,100 @@
+//! Base64 strings embedded in URL components.
+//!
+//! A base64 string which has been through URL encoding, such as a token extracted from a query string,
+//! may contain `%3D` for `=`, `%2B` for `+` and `%2F` for `/`.
+//! The functions here decode these percent sequences together with the base64 string,
+//! without a separate URL-decoding pass.
+//! The hex digits of the sequences are case-insensitive. Any other percent sequence is an error.
+//!
+//! # Examples
+//!
+//! ```
+//! use base64_simd::{url, AsOut, STANDARD};
+//!
+//! let mut buf = [0u8; 32];
+//! let ans = url::decode(&STANDARD, b"%2B%2Fx%2B%2Fw%3D%3D", buf.as_mut_slice().as_out()).unwrap();
+//! assert_eq!(ans, [0xfb, 0xfc, 0x7e, 0xff]);
+//! ```
+
+use crate::{Base64, Error, Out};
+
+use vsimd::tools::{slice_mut, write};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Writes `src` to `dst` with the percent sequences decoded and returns the length.
+///
+/// `dst` must be valid for writing `src.len()` bytes.
+#[inline(always)]
+unsafe fn unescape(src: &[u8], dst: *mut u8) -> Result<usize, Error> {
+    let mut i = 0;
+    let mut j = 0;
+    while i < src.len() {
+        let mut x = src[i];
+        i += 1;
+        if x == b'%' {
+            ensure!(src.len() - i >= 2);
+            x = match (src[i], src[i + 1].to_ascii_uppercase()) {
+                (b'3', b'D') => b'=',
+                (b'2', b'B') => b'+',
+                (b'2', b'F') => b'/',
+                _ => return Err(Error::new()),
+            };
+            i += 2;
+        }
+        write(dst, j, x);
+        j += 1;
+    }
+    Ok(j)
+}
+
+/// Decodes a URL-encoded base64 string to bytes.
+///
+/// If `src` contains percent sequences, `dst` is also used as a scratch buffer for the unescaped input,
+/// so its length must not be less than the length of `src`.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + the length of `dst` is not enough.
+/// + `src` contains a percent sequence other than `%3D`, `%2B` and `%2F`.
+/// + the content of `src` is invalid after decoding the percent sequences.
+#[inline]
+pub fn decode<'d>(base64: &Base64, src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+    if !src.contains(&b'%') {
+        return base64.decode(src, dst);
+    }
+
+    ensure_capacity!(dst.len(), src.len());
+
+    unsafe {
+        let dst = dst.as_mut_ptr();
+        let len = unescape(src, dst)?;
+        base64.decode_inplace(slice_mut(dst, len))
+    }
+}
+
+/// Decodes a URL-encoded base64 string to bytes and returns a new [`Vec<u8>`](Vec).
+///
+/// # Errors
+/// This function returns `Err` if
+/// + `src` contains a percent sequence other than `%3D`, `%2B` and `%2F`.
+/// + the content of `src` is invalid after decoding the percent sequences.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn decode_to_vec(base64: &Base64, src: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
+    let src = src.as_ref();
+    if !src.contains(&b'%') {
+        return base64.decode_type::<Vec<u8>>(src);
+    }
+
+    let mut buf: Vec<u8> = Vec::with_capacity(src.len());
+    unsafe {
+        let len = unescape(src, buf.as_mut_ptr())?;
+        buf.set_len(len);
+    }
+    let len = base64.decode_inplace(&mut buf)?.len();
+    buf.truncate(len);
+    Ok(buf)
+}
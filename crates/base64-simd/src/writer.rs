@@ -0,0 +1,55 @@
+use crate::decode::decoded_length;
+use crate::{Base64, Error};
+
+use std::io;
+
+#[inline]
+fn invalid_data(err: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+impl Base64 {
+    /// Decodes a base64 string and writes the bytes to `w` in blocks of `block_size` bytes.
+    ///
+    /// Only one block is held in memory. Every block except the last one is exactly `block_size` bytes,
+    /// so the writes stay aligned when `block_size` is a multiple of the page size of the destination,
+    /// such as a memory-mapped file.
+    ///
+    /// Returns the number of bytes written.
+    ///
+    /// # Errors
+    /// This function returns `Err` if
+    /// + the content of `src` is invalid, with [`io::ErrorKind::InvalidData`].
+    ///   The blocks before the invalid one may have been written.
+    /// + `w` returns an error.
+    ///
+    /// # Panics
+    /// This function asserts that `block_size` is a non-zero multiple of 3.
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn decode_to_writer<W: io::Write>(&self, src: &[u8], mut w: W, block_size: usize) -> io::Result<usize> {
+        assert!(block_size != 0 && block_size % 3 == 0);
+
+        let (mut n, m) = decoded_length(src, self.config).map_err(invalid_data)?;
+        let chunk_chars = block_size / 3 * 4;
+
+        let mut buf = vec![0u8; block_size.min(m)];
+        let mut src = src.as_ptr();
+        let mut written = 0;
+
+        while n > 0 {
+            let k = n.min(chunk_chars);
+            let len = if k == n { m - written } else { block_size };
+            unsafe {
+                crate::multiversion::decode::auto(src, buf.as_mut_ptr(), k, self.config).map_err(invalid_data)?;
+                src = src.add(k);
+            }
+            w.write_all(&buf[..len])?;
+            written += len;
+            n -= k;
+        }
+
+        debug_assert_eq!(written, m);
+        Ok(m)
+    }
+}
@@ -1,3 +1,5 @@
+#![cfg_attr(all(feature = "std", feature = "unstable"), feature(core_io_borrowed_buf, read_buf))]
+
 use base64_simd::{AsOut, Base64};
 use base64_simd::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
 
@@ -127,6 +129,552 @@ fn random() {
     }
 }
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_any() {
+    use base64::Engine as _;
+
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+
+        let cases = {
+            use base64::engine::general_purpose as gp;
+            [
+                (STANDARD, gp::STANDARD),
+                (URL_SAFE, gp::URL_SAFE),
+                (STANDARD, gp::URL_SAFE),
+                (URL_SAFE, gp::STANDARD),
+                (STANDARD_NO_PAD, gp::STANDARD_NO_PAD),
+                (URL_SAFE_NO_PAD, gp::URL_SAFE_NO_PAD),
+                (STANDARD_NO_PAD, gp::URL_SAFE_NO_PAD),
+                (URL_SAFE_NO_PAD, gp::STANDARD_NO_PAD),
+            ]
+        };
+
+        for (base64, config) in cases {
+            let encoded = config.encode(&bytes);
+            let mut buf = vec![0u8; n];
+            let ans = base64.decode_any(encoded.as_bytes(), buf.as_out()).unwrap();
+            assert_eq!(ans, bytes);
+        }
+
+        // mixed charsets
+        let mut mixed = STANDARD.encode_to_string(&bytes).into_bytes();
+        for (i, c) in mixed.iter_mut().enumerate() {
+            if i % 2 == 0 {
+                match *c {
+                    b'+' => *c = b'-',
+                    b'/' => *c = b'_',
+                    _ => {}
+                }
+            }
+        }
+        let mut buf = vec![0u8; n];
+        let ans = STANDARD.decode_any(&mixed, buf.as_out()).unwrap();
+        assert_eq!(ans, bytes);
+
+        // the sortable charset is decoded strictly
+        let sortable = base64_simd::SORTABLE;
+        let encoded = sortable.encode_to_string(&bytes);
+        let ans = sortable.decode_any(encoded.as_bytes(), buf.as_out()).unwrap();
+        assert_eq!(ans, bytes);
+    }
+
+    let sortable = base64_simd::SORTABLE;
+    let mut buf = [0u8; 64];
+    let encoded = sortable.encode_to_string(b"hello world!");
+    let ans = sortable
+        .decode_any(encoded.as_bytes(), buf.as_mut_slice().as_out())
+        .unwrap();
+    assert_eq!(ans, b"hello world!");
+    assert!(sortable.decode_any(b"Zm9v+A==", buf.as_mut_slice().as_out()).is_err());
+
+    let mut buf = [0u8; 64];
+    for bad in ["Zm9v*A==", "Zm9vYg=", "Zm9v.mFy"] {
+        assert!(STANDARD
+            .decode_any(bad.as_bytes(), buf.as_mut_slice().as_out())
+            .is_err());
+    }
+    let long = "-_+/".repeat(16) + "Zm9!";
+    assert!(STANDARD_NO_PAD
+        .decode_any(long.as_bytes(), buf.as_mut_slice().as_out())
+        .is_err());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn constant_time() {
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let mut expected = vec![0u8; base64.encoded_length(n)];
+            let expected = base64.encode(&bytes, expected.as_out()).unwrap();
+
+            let base64 = base64.constant_time();
+
+            let mut encoded = vec![0u8; base64.encoded_length(n)];
+            let encoded = base64.encode(&bytes, encoded.as_out()).unwrap();
+            assert_eq!(encoded, expected);
+            assert!(base64.check(encoded).is_ok());
+
+            let mut decoded = vec![0u8; n];
+            let decoded = base64.decode(encoded, decoded.as_out()).unwrap();
+            assert_eq!(decoded, bytes);
+
+            let mut decoded = vec![0u8; n];
+            let decoded = base64.decode_any(encoded, decoded.as_out()).unwrap();
+            assert_eq!(decoded, bytes);
+
+            if n > 0 {
+                let mut invalid = encoded.to_vec();
+                invalid[0] = b'*';
+                let mut buf = vec![0u8; n];
+                assert!(base64.check(&invalid).is_err());
+                assert!(base64.decode(&invalid, buf.as_out()).is_err());
+            }
+        }
+    }
+
+    let base64 = STANDARD.constant_time();
+    let mut buf = [0u8; 64];
+    for bad in ["SGVsbG9=", "SGVsbA=", "Zm9v-A==", "Zm9v_mFy"] {
+        assert!(base64.decode(bad.as_bytes(), buf.as_mut_slice().as_out()).is_err());
+    }
+    assert!(URL_SAFE
+        .constant_time()
+        .decode(b"Zm9v+mFy", buf.as_mut_slice().as_out())
+        .is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn url_safe_dot_pad() {
+    use base64_simd::{ScanResult, URL_SAFE_DOT_PAD};
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        let expected: Vec<u8> = URL_SAFE
+            .encode_type::<Vec<u8>>(&bytes)
+            .iter()
+            .map(|&x| if x == b'=' { b'.' } else { x })
+            .collect();
+
+        for base64 in [URL_SAFE_DOT_PAD, URL_SAFE_DOT_PAD.constant_time()] {
+            let encoded = base64.encode_type::<Vec<u8>>(&bytes);
+            assert_eq!(encoded, expected);
+            assert_eq!(base64.encoded_length(n), expected.len());
+            assert!(base64.check(&encoded).is_ok());
+            assert_eq!(base64.decode_to_vec(&encoded).unwrap(), bytes);
+
+            let decoded: Vec<u8> = base64.decode_iter(&encoded).collect::<Result<_, _>>().unwrap();
+            assert_eq!(decoded, bytes);
+
+            if n % 3 != 0 {
+                let pad = 3 - n % 3;
+                assert_eq!(base64.scan(&encoded), ScanResult::Padding(encoded.len() - pad));
+
+                let with_eq = URL_SAFE.encode_type::<Vec<u8>>(&bytes);
+                assert!(base64.decode_to_vec(&with_eq).is_err());
+                assert!(URL_SAFE.decode_to_vec(&encoded).is_err());
+            }
+        }
+
+        let mut buf = URL_SAFE.encode_type::<Vec<u8>>(&bytes);
+        let len = buf.len();
+        let ans = Base64::transcode_inplace(&mut buf, len, &URL_SAFE, &URL_SAFE_DOT_PAD).unwrap();
+        assert_eq!(ans, expected);
+    }
+
+    let mut buf = [0u8; 4];
+    for bad in ["Zg.", "Zg=.", "Zm9.v", ".AAA"] {
+        assert!(URL_SAFE_DOT_PAD
+            .decode(bad.as_bytes(), buf.as_mut_slice().as_out())
+            .is_err());
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn encode_and_update() {
+    for n in [0, 1, 2, 3, 100, 3071, 3072, 3073, 10000] {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let mut chunks: Vec<Vec<u8>> = Vec::new();
+            let encoded = base64.encode_and_update(&bytes, |chunk| chunks.push(chunk.to_vec()));
+            assert_eq!(&*encoded, base64.encode_to_string(&bytes));
+            assert_eq!(chunks.concat(), encoded.as_bytes());
+
+            if let Some((_, init)) = chunks.split_last() {
+                assert!(init.iter().all(|c| c.len() == 4096));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn sizing_writer() {
+    use std::io::Write;
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let mut sizing = base64.sizing_writer();
+            for chunk in bytes.chunks(7) {
+                sizing.write_all(chunk).unwrap();
+            }
+            assert_eq!(sizing.input_length(), n);
+            assert_eq!(sizing.encoded_length(), base64.encode_to_string(&bytes).len());
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_trusted() {
+    use base64::Engine as _;
+
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+
+        let cases = {
+            use base64::engine::general_purpose as gp;
+            [
+                (STANDARD, gp::STANDARD),
+                (URL_SAFE, gp::URL_SAFE),
+                (STANDARD_NO_PAD, gp::STANDARD_NO_PAD),
+                (URL_SAFE_NO_PAD, gp::URL_SAFE_NO_PAD),
+            ]
+        };
+
+        for (base64, config) in cases {
+            let encoded = config.encode(&bytes);
+            assert!(base64.check(encoded.as_bytes()).is_ok());
+
+            let mut buf = vec![0u8; n];
+            let ans = unsafe { base64.decode_trusted(encoded.as_bytes(), buf.as_out()) };
+            assert_eq!(ans, bytes);
+        }
+    }
+
+    {
+        use base64_simd::NulPolicy;
+
+        let mut buf = [0u8; 8];
+
+        let stop = STANDARD.on_nul(NulPolicy::Stop);
+        assert!(stop.check(b"aGk=\0AAAA").is_ok());
+        let ans = unsafe { stop.decode_trusted(b"aGk=\0AAAA", buf.as_mut_slice().as_out()) };
+        assert_eq!(ans, b"hi");
+
+        let skip = STANDARD.on_nul(NulPolicy::Skip);
+        for (src, expected) in [
+            (&b"aGk=\0"[..], &b"hi"[..]),
+            (b"\0aG\0k=", b"hi"),
+            (b"Zm9v\0YmFy\0\0", b"foobar"),
+        ] {
+            assert!(skip.check(src).is_ok());
+            let ans = unsafe { skip.decode_trusted(src, buf.as_mut_slice().as_out()) };
+            assert_eq!(ans, expected);
+        }
+
+        let bytes = rand_bytes(1000);
+        let mut noisy = Vec::new();
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+        for (i, &c) in encoded.as_bytes().iter().enumerate() {
+            if i % 7 == 0 {
+                noisy.push(0);
+            }
+            noisy.push(c);
+        }
+        assert!(skip.check(&noisy).is_ok());
+        let mut buf = vec![0u8; bytes.len()];
+        let ans = unsafe { skip.decode_trusted(&noisy, buf.as_out()) };
+        assert_eq!(ans, bytes);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn eq_decoded() {
+    for n in [0, 1, 2, 3, 4, 5, 31, 32, 33, 191, 192, 193, 500, 1000] {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+            assert!(base64.eq_decoded(encoded.as_bytes(), &bytes));
+
+            if n > 0 {
+                let mut other = bytes.clone();
+                other[n - 1] ^= 1;
+                assert!(!base64.eq_decoded(encoded.as_bytes(), &other));
+                assert!(!base64.eq_decoded(encoded.as_bytes(), &bytes[..n - 1]));
+
+                let mut invalid = encoded.clone().into_bytes();
+                invalid[0] = b'*';
+                assert!(!base64.eq_decoded(&invalid, &bytes));
+            }
+        }
+    }
+
+    assert!(!STANDARD.eq_decoded(b"Zm9", b"fo"));
+    assert!(!STANDARD.eq_decoded(b"Zm9=", b"fo"));
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_chunked() {
+    for n in [0, 1, 2, 3, 4, 5, 31, 32, 33, 191, 192, 193, 500, 1000] {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+            for chunk_chars in [4, 8, 64, 256, 4096] {
+                let mut buf = vec![0u8; n];
+                let ans = base64
+                    .decode_chunked(encoded.as_bytes(), buf.as_out(), chunk_chars)
+                    .unwrap();
+                assert_eq!(ans, bytes);
+
+                if n > 0 {
+                    let mut invalid = encoded.clone().into_bytes();
+                    invalid[0] = b'*';
+                    let mut buf = vec![0u8; n];
+                    assert!(base64.decode_chunked(&invalid, buf.as_out(), chunk_chars).is_err());
+
+                    let mut buf = vec![0u8; n - 1];
+                    assert!(base64
+                        .decode_chunked(encoded.as_bytes(), buf.as_out(), chunk_chars)
+                        .is_err());
+                }
+            }
+        }
+    }
+
+    let mut buf = [0u8; 8];
+    assert!(STANDARD
+        .decode_chunked(b"Zm9vYmFyYh==", buf.as_mut_slice().as_out(), 4)
+        .is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_ignoring() {
+    let mut ignore = [false; 256];
+    for &x in b" ,|\r\n" {
+        ignore[x as usize] = true;
+    }
+
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+
+            let mut buf = vec![0u8; n];
+            let ans = base64
+                .decode_ignoring(encoded.as_bytes(), buf.as_out(), &ignore)
+                .unwrap();
+            assert_eq!(ans, bytes);
+
+            let mut noisy = Vec::new();
+            for (i, &c) in encoded.as_bytes().iter().enumerate() {
+                if i % 5 == 0 {
+                    noisy.extend_from_slice(b", ");
+                }
+                noisy.push(c);
+                if i % 7 == 3 {
+                    noisy.extend_from_slice(b"|\r\n");
+                }
+            }
+
+            assert_eq!(base64.count_significant_chars(&noisy, &ignore), encoded.len());
+            assert_eq!(
+                base64.count_significant_chars(encoded.as_bytes(), &ignore),
+                encoded.len()
+            );
+
+            let mut buf = vec![0u8; encoded.len()];
+            let ans = base64.decode_ignoring(&noisy, buf.as_out(), &ignore).unwrap();
+            assert_eq!(ans, bytes);
+        }
+    }
+
+    // Every high nibble and long runs of significant blocks
+    let mut ignore = [false; 256];
+    for x in (0..=255u8).filter(|&x| !(x.is_ascii_alphanumeric() || b"+/=".contains(&x)) && x % 3 == 0) {
+        ignore[x as usize] = true;
+    }
+    let fillers: Vec<u8> = (0..=255u8).filter(|&x| ignore[x as usize]).collect();
+    for n in [48, 300, 1000, 5000] {
+        let bytes = rand_bytes(n);
+        let encoded = STANDARD.encode_to_string(&bytes);
+        for step in [1, 15, 16, 17, 64] {
+            let mut noisy = Vec::new();
+            for (i, &c) in encoded.as_bytes().iter().enumerate() {
+                if i % step == step - 1 {
+                    noisy.push(fillers[i % fillers.len()]);
+                }
+                noisy.push(c);
+            }
+            assert_eq!(STANDARD.count_significant_chars(&noisy, &ignore), encoded.len());
+
+            let mut buf = vec![0u8; encoded.len()];
+            let ans = STANDARD.decode_ignoring(&noisy, buf.as_out(), &ignore).unwrap();
+            assert_eq!(ans, bytes);
+        }
+    }
+
+    let mut ignore = [false; 256];
+    for &x in b" ,|\r\n" {
+        ignore[x as usize] = true;
+    }
+    let mut buf = [0u8; 64];
+    assert!(STANDARD
+        .decode_ignoring(b"Zm9v, Y\tmFy", buf.as_mut_slice().as_out(), &ignore)
+        .is_err());
+    let ans = STANDARD
+        .decode_ignoring(b"Zm9v, YmFy", buf.as_mut_slice().as_out(), &ignore)
+        .unwrap();
+    assert_eq!(ans, b"foobar");
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_valid_prefix() {
+    for n in [0, 1, 2, 3, 4, 5, 31, 32, 33, 100] {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+
+            let mut buf = vec![0u8; n];
+            let (ans, pos) = base64.decode_valid_prefix(encoded.as_bytes(), buf.as_out());
+            assert_eq!(ans, bytes);
+            assert_eq!(pos, encoded.len());
+
+            for i in 0..encoded.len() {
+                let mut corrupted = encoded.clone().into_bytes();
+                corrupted[i] = b'*';
+
+                let mut buf = vec![0u8; n];
+                let (ans, pos) = base64.decode_valid_prefix(&corrupted, buf.as_out());
+                assert_eq!(pos, i / 4 * 4);
+                assert_eq!(ans, &bytes[..pos / 4 * 3]);
+            }
+        }
+    }
+
+    let mut buf = [0u8; 64];
+    let (ans, pos) = STANDARD.decode_valid_prefix(b"Zm9vYmFyYg=x", buf.as_mut_slice().as_out());
+    assert_eq!((&*ans, pos), (&b"foobar"[..], 8));
+
+    let (ans, pos) = STANDARD.decode_valid_prefix(b"Zm9vYmFyYh==", buf.as_mut_slice().as_out());
+    assert_eq!((&*ans, pos), (&b"foobar"[..], 8));
+
+    let (ans, pos) = STANDARD.decode_valid_prefix(b"Zm9vYmFyYg", buf.as_mut_slice().as_out());
+    assert_eq!((&*ans, pos), (&b"foobar"[..], 8));
+
+    let (ans, pos) = STANDARD.decode_valid_prefix(b"Zm9vYmFyYg==", buf[..4].as_out());
+    assert_eq!((&*ans, pos), (&b"foo"[..], 4));
+}
+
+#[cfg(all(feature = "stats", feature = "alloc"))]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn stats() {
+    use base64_simd::stats;
+
+    // The counters are shared with the other tests, so only the lower bounds are checked.
+    let total = || stats::bulk_bytes() + stats::tail_bytes();
+
+    for n in [0, 1, 5, 100, 1000] {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE_NO_PAD, STANDARD.constant_time()] {
+            let mut encoded = vec![0u8; base64.encoded_length(n)];
+            let (bulk, tail) = (stats::bulk_bytes(), stats::tail_bytes());
+            let encoded = base64.encode(&bytes, encoded.as_out()).unwrap();
+            assert!(total() - (bulk + tail) >= n);
+
+            let significant = encoded.iter().filter(|&&x| x != b'=').count();
+            let mut decoded = vec![0u8; n];
+            let (bulk, tail) = (stats::bulk_bytes(), stats::tail_bytes());
+            base64.decode(encoded, decoded.as_out()).unwrap();
+            assert!(total() - (bulk + tail) >= significant);
+        }
+
+        // The constant-time variants are scalar.
+        let base64 = STANDARD.constant_time();
+        let encoded = base64.encode_type::<Vec<u8>>(&bytes);
+        let tail = stats::tail_bytes();
+        base64.decode_to_vec(&encoded).unwrap();
+        assert!(stats::tail_bytes() - tail >= encoded.iter().filter(|&&x| x != b'=').count());
+    }
+}
+
+#[cfg(feature = "fuzz")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn fuzz() {
+    use base64_simd::fuzz;
+
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            assert!(fuzz::round_trip(&bytes, &base64));
+
+            let encoded = base64.encode_to_string(&bytes);
+            assert!(fuzz::decode_matches_reference(encoded.as_bytes()));
+        }
+        assert!(fuzz::decode_matches_reference(&bytes));
+    }
+
+    for input in ["", "Zg==", "Zg", "Zh==", "Zm9=", "Zm9v*A==", "-_+/", "===="] {
+        assert!(fuzz::decode_matches_reference(input.as_bytes()));
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn cursor() {
+    for n in [0, 1, 2, 3, 4, 5, 31, 32, 33, 100] {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+
+            let mut cursor = base64.cursor(encoded.as_bytes());
+            let mut decoded = Vec::new();
+            while !cursor.is_empty() {
+                let k = cursor.remaining().len().min(8);
+                decoded.extend_from_slice(&cursor.decode_next(k).unwrap());
+            }
+            assert_eq!(decoded, bytes);
+            assert_eq!(cursor.position(), encoded.len());
+        }
+    }
+
+    let mut cursor = STANDARD.cursor(b"Zm9vYg==:YmFy");
+    assert!(cursor.decode_next(3).is_err());
+    assert!(cursor.decode_next(14).is_err());
+    assert_eq!(cursor.position(), 0);
+
+    assert_eq!(&*cursor.decode_next(8).unwrap(), b"foob");
+    assert_eq!(cursor.remaining(), b":YmFy");
+
+    assert!(cursor.decode_next(4).is_err());
+    assert_eq!(cursor.position(), 8);
+
+    cursor.advance(1);
+    assert_eq!(&*cursor.decode_next(4).unwrap(), b"bar");
+    assert!(cursor.is_empty());
+    assert_eq!(&*cursor.decode_next(0).unwrap(), b"");
+}
+
 /// <https://eprint.iacr.org/2022/361>
 #[cfg_attr(not(target_arch = "wasm32"), test)]
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
@@ -157,6 +705,107 @@ fn canonicity() {
     }
 }
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn optional_padding() {
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for (padded, unpadded) in [(STANDARD, STANDARD_NO_PAD), (URL_SAFE, URL_SAFE_NO_PAD)] {
+            let with_pad = padded.encode_to_string(&bytes);
+            let without_pad = unpadded.encode_to_string(&bytes);
+
+            let base64 = unpadded.accept_optional_padding();
+            assert_eq!(base64.encode_to_string(&bytes), without_pad);
+            assert_eq!(base64.decode_to_vec(&with_pad).unwrap(), bytes);
+            assert_eq!(base64.decode_to_vec(&without_pad).unwrap(), bytes);
+            assert!(base64.check(with_pad.as_bytes()).is_ok());
+            assert!(base64.constant_time().decode_to_vec(&with_pad).is_ok());
+        }
+    }
+
+    let base64 = URL_SAFE_NO_PAD.accept_optional_padding();
+    for bad in ["Zg=", "Zh==", "Zh", "Zm9=", "Z===", "====", "Zg==Zg==", "Zg==="] {
+        assert!(base64.decode_to_vec(bad).is_err(), "{bad}");
+    }
+
+    let base64 = STANDARD.accept_optional_padding();
+    assert!(base64.decode_to_vec("Zg").is_err());
+    assert_eq!(base64.encode_to_string("f"), "Zg==");
+}
+
+/// <https://www.rfc-editor.org/rfc/rfc7515#appendix-A.1>
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn jws_canonicity() {
+    let header = "eyJ0eXAiOiJKV1QiLA0KICJhbGciOiJIUzI1NiJ9";
+    let payload = "eyJpc3MiOiJqb2UiLA0KICJleHAiOjEzMDA4MTkzODAsDQogImh0dHA6Ly9leGFtcGxlLmNvbS9pc19yb290Ijp0cnVlfQ";
+    let signature = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+    let base64 = URL_SAFE_NO_PAD;
+
+    assert_eq!(
+        base64.decode_to_vec(header).unwrap(),
+        b"{\"typ\":\"JWT\",\r\n \"alg\":\"HS256\"}"
+    );
+    assert_eq!(
+        base64.decode_to_vec(payload).unwrap(),
+        b"{\"iss\":\"joe\",\r\n \"exp\":1300819380,\r\n \"http://example.com/is_root\":true}"
+    );
+    assert_eq!(
+        base64.decode_to_vec(signature).unwrap(),
+        const_str::hex!("7418dfb49799e0254ffa607dd8adbbba16d4254d69d6bff05b58055853848d79")
+    );
+
+    // The same tokens with non-zero unused bits in the last character are rejected.
+    let malleated = [
+        payload.replace("fQ", "fR"),
+        payload.replace("fQ", "fZ"),
+        signature.replace("Xk", "Xl"),
+        signature.replace("Xk", "Xn"),
+        format!("{payload}=="),
+        format!("{signature}="),
+    ];
+    for token in malleated {
+        assert!(base64.check(token.as_bytes()).is_err());
+        assert!(base64.decode_to_vec(&token).is_err());
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn capabilities() {
+    use base64_simd::Backend;
+
+    let caps = Base64::capabilities();
+    let expected_width = match caps.backend {
+        Backend::Avx2 => 32,
+        Backend::Fallback => 0,
+        _ => 16,
+    };
+    assert_eq!(caps.vector_width, expected_width);
+    assert_eq!(caps, Base64::capabilities());
+
+    #[cfg(all(
+        feature = "detect",
+        target_arch = "x86_64",
+        not(any(feature = "force-sse41", feature = "force-scalar"))
+    ))]
+    if std::arch::is_x86_feature_detected!("avx2") {
+        assert_eq!(caps.backend, Backend::Avx2);
+    }
+
+    #[cfg(all(feature = "force-avx2", target_arch = "x86_64"))]
+    assert_eq!(caps.backend, Backend::Avx2);
+
+    #[cfg(all(feature = "force-sse41", target_arch = "x86_64"))]
+    assert_eq!(caps.backend, Backend::Ssse3);
+
+    #[cfg(feature = "force-scalar")]
+    assert_eq!(caps.backend, Backend::Fallback);
+}
+
 // RUSTFLAGS=-Zsanitizer=address cargo test -p base64-simd --features=parallel -- --include-ignored parallel_encode
 #[cfg(all(not(miri), feature = "parallel"))]
 #[test]
@@ -249,11 +898,61 @@ fn precise_decoded_length() {
 }
 
 #[test]
-fn estimated_decoded_length() {
-    let cases = [
-        (0, 0), //
-        (1, 3), //
-        (2, 3), //
+fn length_residues() {
+    use base64_simd::ErrorKind;
+
+    let mut buf = [0u8; 64];
+
+    // (input, no-pad result)
+    let cases: &[(&str, Option<&[u8]>)] = &[
+        ("Zm9v", Some(b"foo")),      // 4k
+        ("Zm9vY", None),             // 4k+1
+        ("Zm9vYg", Some(b"foob")),   // 4k+2
+        ("Zm9vYmE", Some(b"fooba")), // 4k+3
+    ];
+
+    for &(input, expected) in cases {
+        for base64 in [STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let ans = base64.decode(input.as_bytes(), buf.as_mut_slice().as_out());
+            match expected {
+                Some(expected) => assert_eq!(ans.unwrap(), expected),
+                None => assert_eq!(ans.unwrap_err().kind(), ErrorKind::InvalidLength),
+            }
+            match expected {
+                Some(expected) => assert_eq!(base64.decoded_length(input.as_bytes()).unwrap(), expected.len()),
+                None => assert_eq!(
+                    base64.decoded_length(input.as_bytes()).unwrap_err().kind(),
+                    ErrorKind::InvalidLength
+                ),
+            }
+        }
+
+        // padded variants require a multiple of 4
+        for base64 in [STANDARD, URL_SAFE] {
+            let ans = base64.decode(input.as_bytes(), buf.as_mut_slice().as_out());
+            if input.len() % 4 == 0 {
+                assert!(ans.is_ok());
+            } else {
+                assert_eq!(ans.unwrap_err().kind(), ErrorKind::InvalidLength);
+            }
+        }
+    }
+
+    assert_eq!(
+        STANDARD
+            .decode(b"Zm9v=A==", buf.as_mut_slice().as_out())
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn estimated_decoded_length() {
+    let cases = [
+        (0, 0), //
+        (1, 3), //
+        (2, 3), //
         (3, 3), //
         (4, 3), //
         (5, 6), //
@@ -272,3 +971,1560 @@ fn estimated_decoded_length() {
 
     // let _ = base64::decoded_len_estimate(usize::MAX); // it panics
 }
+
+#[test]
+fn checked_encoded_length() {
+    for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+        for n in [0, 1, 2, 3, 4, 100, isize::MAX as usize / 4 * 3] {
+            assert_eq!(base64.checked_encoded_length(n), Some(base64.encoded_length(n)));
+        }
+        assert_eq!(base64.checked_encoded_length(isize::MAX as usize / 4 * 3 + 3), None);
+        assert_eq!(base64.checked_encoded_length(usize::MAX / 2), None);
+        assert_eq!(base64.checked_encoded_length(usize::MAX), None);
+    }
+
+    const LEN: Option<usize> = STANDARD.checked_encoded_length(5);
+    assert_eq!(LEN, Some(8));
+}
+
+#[cfg(target_pointer_width = "32")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn encoded_length_32bit() {
+    // 0x5fff_fffd bytes are encoded to 0x7fff_fffc characters.
+    for base64 in [STANDARD, STANDARD_NO_PAD] {
+        assert_eq!(base64.checked_encoded_length(0x5fff_fffd), Some(0x7fff_fffc));
+        assert_eq!(base64.checked_encoded_length(0x6000_0000), None);
+    }
+    assert_eq!(STANDARD.checked_encoded_length(0x5fff_fffe), None);
+    assert_eq!(STANDARD_NO_PAD.checked_encoded_length(0x5fff_fffe), Some(0x7fff_fffe));
+    assert_eq!(STANDARD_NO_PAD.checked_encoded_length(0x5fff_ffff), Some(0x7fff_ffff));
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn transcode_inplace() {
+    let variants = [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD];
+
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+        for from in &variants {
+            for to in &variants {
+                let src = from.encode_to_string(&bytes);
+                let expected = to.encode_to_string(&bytes);
+
+                let mut buf = src.clone().into_bytes();
+                buf.extend_from_slice(b"##");
+                let ans = Base64::transcode_inplace(&mut buf, src.len(), from, to).unwrap();
+                assert_eq!(ans, expected.as_bytes(), "n = {n}");
+
+                let mut buf = src.clone().into_bytes();
+                let result = Base64::transcode_inplace(&mut buf, src.len(), from, to);
+                if expected.len() > src.len() {
+                    assert!(result.is_err());
+                } else {
+                    assert_eq!(result.unwrap(), expected.as_bytes());
+                }
+            }
+        }
+    }
+
+    let mut buf = *b"+/+/a-__";
+    assert!(Base64::transcode_inplace(&mut buf, 8, &STANDARD, &URL_SAFE).is_err());
+
+    let mut buf = *b"+/9=";
+    assert!(Base64::transcode_inplace(&mut buf, 4, &STANDARD, &URL_SAFE_NO_PAD).is_err());
+
+    let mut buf = *b"+/8=";
+    let ans = Base64::transcode_inplace(&mut buf, 4, &STANDARD, &URL_SAFE_NO_PAD).unwrap();
+    assert_eq!(ans, b"-_8");
+
+    let mut buf = *b"+/8z=";
+    let ans = Base64::transcode_inplace(&mut buf, 4, &STANDARD, &URL_SAFE).unwrap();
+    assert_eq!(ans, b"-_8z");
+
+    // NUL bytes are rejected even if `from` skips them.
+    {
+        use base64_simd::{NulPolicy, SORTABLE};
+
+        let skip = STANDARD.on_nul(NulPolicy::Skip);
+        for to in [&SORTABLE, &URL_SAFE, &STANDARD] {
+            let mut buf = *b"aGVs\0\0\0\0bG8=";
+            assert!(Base64::transcode_inplace(&mut buf, 12, &skip, to).is_err());
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decoder() {
+    fn decode_by_steps(base64: &Base64, src: &[u8], in_step: usize, out_step: usize) -> Result<Vec<u8>, ()> {
+        let mut decoder = base64.decoder();
+        let mut ans = Vec::new();
+        let mut buf = vec![0; out_step];
+        let mut input = src;
+        loop {
+            let chunk = &input[..in_step.min(input.len())];
+            let p = decoder.consume(chunk, &mut buf).map_err(|_| ())?;
+            ans.extend_from_slice(&buf[..p.written]);
+            input = &input[p.read..];
+            if input.is_empty() {
+                break;
+            }
+        }
+        loop {
+            let p = decoder.finish(&mut buf).map_err(|_| ())?;
+            ans.extend_from_slice(&buf[..p.written]);
+            if p.done {
+                break;
+            }
+        }
+        assert!(decoder.is_done());
+        Ok(ans)
+    }
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+            for in_step in [1, 3, 4, 7, 64] {
+                for out_step in [3, 5, 64] {
+                    let ans = decode_by_steps(&base64, encoded.as_bytes(), in_step, out_step).unwrap();
+                    assert_eq!(ans, bytes, "n = {n}, in_step = {in_step}, out_step = {out_step}");
+                }
+            }
+        }
+    }
+
+    let mut decoder = STANDARD.decoder();
+    let mut buf = [0; 16];
+    let p = decoder.consume(b"Zm8=", &mut buf).unwrap();
+    assert_eq!((p.read, p.written, p.done), (4, 2, true));
+    assert!(decoder.consume(b"Zm9v", &mut buf).is_err());
+
+    let bad: &[&[u8]] = &[b"Zm8=Zm9v", b"Zm9", b"Zm9v!A==", b"Zm9="];
+    for &src in bad {
+        assert!(decode_by_steps(&STANDARD, src, 64, 64).is_err());
+    }
+    assert!(decode_by_steps(&STANDARD_NO_PAD, b"Zm8=", 64, 64).is_err());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn alphabet_index() {
+    for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+        assert_eq!(base64.charset_str().as_bytes(), base64.charset());
+
+        for (i, &c) in base64.charset().iter().enumerate() {
+            assert_eq!(base64.alphabet_index(c), Some(i as u8));
+        }
+
+        let count = (0..=255u8).filter(|&c| base64.alphabet_index(c).is_some()).count();
+        assert_eq!(count, 64);
+        assert_eq!(base64.alphabet_index(b'='), None);
+    }
+
+    assert_eq!(STANDARD.alphabet_index(b'+'), Some(62));
+    assert_eq!(STANDARD.alphabet_index(b'-'), None);
+    assert_eq!(URL_SAFE.alphabet_index(b'_'), Some(63));
+    assert_eq!(URL_SAFE.alphabet_index(b'/'), None);
+    assert!(URL_SAFE.charset_str().ends_with("-_"));
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_slice() {
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_type::<Vec<u8>>(&bytes);
+
+            let mut buf = vec![0u8; base64.decoded_length(&encoded).unwrap()];
+            assert_eq!(base64.decode_slice(&encoded, &mut buf).unwrap(), n);
+            assert_eq!(buf, bytes);
+
+            let mut buf = vec![0u8; n + 3];
+            assert_eq!(base64.decode_slice(&encoded, &mut buf).unwrap(), n);
+            assert_eq!(buf[..n], bytes);
+
+            if n > 0 {
+                let mut buf = vec![0u8; n - 1];
+                assert!(base64.decode_slice(&encoded, &mut buf).is_err());
+            }
+        }
+    }
+
+    let mut buf = [0u8; 8];
+    assert!(STANDARD.decode_slice(b"Zm9v!", &mut buf).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn small_bytes() {
+    use base64_simd::SmallBytes;
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+
+            let ans = base64.decode_to_small_bytes(&encoded).unwrap();
+            assert_eq!(&*ans, bytes);
+            assert_eq!(ans.is_inline(), n <= 32);
+
+            let ans: SmallBytes<8> = base64.decode_type(&encoded).unwrap();
+            assert_eq!(ans.as_slice(), bytes);
+            assert_eq!(ans.is_inline(), n <= 8);
+            assert_eq!(ans.clone().into_vec(), bytes);
+            assert_eq!(ans, base64.decode_to_small_bytes(&encoded).unwrap());
+        }
+    }
+
+    // NUL bytes to skip do not need inline capacity.
+    {
+        use base64_simd::NulPolicy;
+
+        let base64 = STANDARD.on_nul(NulPolicy::Skip);
+        let bytes = rand_bytes(30);
+        let mut encoded = STANDARD.encode_type::<Vec<u8>>(&bytes);
+        encoded.insert(7, 0);
+        let ans = base64.decode_to_small_bytes(&encoded).unwrap();
+        assert_eq!(&*ans, bytes);
+        assert!(ans.is_inline());
+        assert_eq!(ans.into_vec(), base64.decode_to_vec(&encoded).unwrap());
+    }
+
+    assert!(STANDARD.decode_to_small_bytes("aGVsbG8").is_err());
+    assert!(STANDARD.decode_to_small_bytes("aGVs*G8=").is_err());
+    assert!(STANDARD.decode_to_small_bytes("aGVs".repeat(20) + "*G8=").is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_exact_padding() {
+    use base64_simd::{ErrorKind, NulPolicy};
+
+    for n in 0..32 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_type::<Vec<u8>>(&bytes);
+            let pad = encoded.iter().rev().take_while(|&&x| x == b'=').count() as u8;
+
+            let mut buf = vec![0u8; n];
+            let ans = base64
+                .decode_exact_padding(&encoded, buf.as_mut_slice().as_out(), pad)
+                .unwrap();
+            assert_eq!(ans, bytes);
+
+            for wrong in (0..=2).filter(|&p| p != pad) {
+                let err = base64
+                    .decode_exact_padding(&encoded, buf.as_mut_slice().as_out(), wrong)
+                    .unwrap_err();
+                assert_eq!(err.kind(), ErrorKind::InvalidLength);
+            }
+        }
+    }
+
+    let mut buf = [0u8; 8];
+    let optional = STANDARD_NO_PAD.accept_optional_padding();
+    assert!(optional
+        .decode_exact_padding(b"Zg==", buf.as_mut_slice().as_out(), 2)
+        .is_ok());
+    assert!(optional
+        .decode_exact_padding(b"Zg", buf.as_mut_slice().as_out(), 2)
+        .is_err());
+
+    let stop = STANDARD.on_nul(NulPolicy::Stop);
+    assert!(stop
+        .decode_exact_padding(b"Zg==\0AAAA", buf.as_mut_slice().as_out(), 2)
+        .is_ok());
+    let skip = STANDARD.on_nul(NulPolicy::Skip);
+    assert!(skip
+        .decode_exact_padding(b"Zg=\0=\0", buf.as_mut_slice().as_out(), 2)
+        .is_ok());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_chained() {
+    let variants = [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD];
+
+    for n in 0..40 {
+        let bytes = rand_bytes(n);
+        for base64 in &variants {
+            let encoded = base64.encode_type::<Vec<u8>>(&bytes);
+
+            let mut inputs = vec![encoded.clone()];
+            if !encoded.is_empty() {
+                let mut bad = encoded.clone();
+                bad[encoded.len() / 2] = b'*';
+                inputs.push(bad);
+                inputs.push(encoded[..encoded.len() - 1].to_vec());
+            }
+
+            for input in &inputs {
+                let expected = base64.decode_to_vec(input);
+                for i in 0..=input.len() {
+                    let (a, b) = input.split_at(i);
+                    let mut buf = vec![0u8; n];
+                    let ans = base64.decode_chained(a, b, buf.as_out());
+                    match &expected {
+                        Ok(expected) => assert_eq!(ans.unwrap(), expected, "n = {n}, i = {i}"),
+                        Err(_) => assert!(ans.is_err(), "n = {n}, i = {i}"),
+                    }
+                }
+            }
+
+            if n > 0 {
+                for i in 0..=encoded.len() {
+                    let (a, b) = encoded.split_at(i);
+                    let mut buf = vec![0u8; n - 1];
+                    assert!(base64.decode_chained(a, b, buf.as_out()).is_err());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn multibase() {
+    use base64_simd::multibase::{self, Variant};
+
+    let variants = [
+        (Variant::Base64, STANDARD_NO_PAD),
+        (Variant::Base64Pad, STANDARD),
+        (Variant::Base64Url, URL_SAFE_NO_PAD),
+        (Variant::Base64UrlPad, URL_SAFE),
+    ];
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for &(variant, ref base64) in &variants {
+            let s = multibase::encode(variant, &bytes);
+            assert_eq!(s.len(), s.capacity());
+            assert_eq!(s[..1], *variant.prefix().encode_utf8(&mut [0; 4]));
+            assert_eq!(s[1..], *base64.encode_to_string(&bytes));
+            assert_eq!(Variant::from_prefix(s.as_bytes()[0]), Some(variant));
+
+            let (v, data) = multibase::decode(&s).unwrap();
+            assert_eq!(v, variant);
+            assert_eq!(&*data, bytes);
+        }
+    }
+
+    // the examples of the multibase spec
+    let cases = [
+        ("mRGVjZW50cmFsaXplIGV2ZXJ5dGhpbmchIQ", Variant::Base64),
+        ("MRGVjZW50cmFsaXplIGV2ZXJ5dGhpbmchIQ==", Variant::Base64Pad),
+        ("uRGVjZW50cmFsaXplIGV2ZXJ5dGhpbmchIQ", Variant::Base64Url),
+        ("URGVjZW50cmFsaXplIGV2ZXJ5dGhpbmchIQ==", Variant::Base64UrlPad),
+    ];
+    for (s, variant) in cases {
+        let (v, data) = multibase::decode(s).unwrap();
+        assert_eq!(v, variant);
+        assert_eq!(&*data, b"Decentralize everything!!");
+    }
+
+    for bad in ["", "z", "f6869", "\u{e9}aGk", "maGk=", "MaGk", "u+/8", "U+/8="] {
+        assert!(multibase::decode(bad).is_err(), "{bad:?}");
+    }
+    assert_eq!(multibase::decode("baGk").unwrap_err().index(), Some(0));
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn data_uri() {
+    use base64_simd::data_uri;
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for mime in ["", "image/png", "text/plain;charset=utf-8"] {
+            let uri = data_uri::encode(mime, &bytes);
+            assert!(uri.starts_with("data:"));
+            assert_eq!(uri.len(), uri.capacity());
+
+            let (m, data) = data_uri::decode(&uri).unwrap();
+            assert_eq!(m, mime);
+            assert_eq!(&*data, bytes);
+        }
+    }
+
+    let (mime, data) = data_uri::decode("DATA:text/plain;BASE64,aGk=").unwrap();
+    assert_eq!((mime.as_str(), &*data), ("text/plain", &b"hi"[..]));
+
+    let (mime, data) = data_uri::decode("data:text/plain;name=\u{e9};base64,").unwrap();
+    assert_eq!((mime.as_str(), &*data), ("text/plain;name=\u{e9}", &b""[..]));
+
+    let bad = [
+        "",
+        "data",
+        "data:text/plain,aGk=",
+        "data:text/plain;base64aGk=",
+        "http:text/plain;base64,aGk=",
+        "data:text/plain;base64,aGk",
+        "data:;base6\u{e9},aGk=",
+    ];
+    for uri in bad {
+        assert!(data_uri::decode(uri).is_err(), "uri = {uri:?}");
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn sortable() {
+    use base64_simd::SORTABLE;
+
+    let charset = SORTABLE.charset();
+    assert!(charset.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(SORTABLE.encode_to_string([0x00, 0x00, 0x00]), "----");
+    assert_eq!(SORTABLE.encode_to_string([0xff, 0xff, 0xff]), "zzzz");
+    assert_eq!(SORTABLE.encode_to_string([0x00]), "--");
+
+    let ct = SORTABLE.constant_time();
+    for n in 0..200 {
+        let bytes = rand_bytes(n);
+        let encoded = SORTABLE.encode_to_string(&bytes);
+        assert_eq!(encoded, ct.encode_to_string(&bytes));
+
+        let expected: String = STANDARD_NO_PAD
+            .encode_to_string(&bytes)
+            .bytes()
+            .map(|c| charset[STANDARD_NO_PAD.alphabet_index(c).unwrap() as usize] as char)
+            .collect();
+        assert_eq!(encoded, expected);
+
+        assert!(SORTABLE.check(encoded.as_bytes()).is_ok());
+        assert_eq!(SORTABLE.decode_to_vec(&encoded).unwrap(), bytes);
+        assert_eq!(ct.decode_to_vec(&encoded).unwrap(), bytes);
+
+        for i in 0..encoded.len() {
+            for bad in [b'+', b'/', b'=', b'.'] {
+                let mut invalid = encoded.clone().into_bytes();
+                invalid[i] = bad;
+                assert!(SORTABLE.check(&invalid).is_err());
+                assert!(SORTABLE.decode_to_vec(&invalid).is_err());
+                assert!(ct.decode_to_vec(&invalid).is_err());
+            }
+        }
+
+        let mut buf = STANDARD.encode_to_string(&bytes).into_bytes();
+        let len = buf.len();
+        let ans = Base64::transcode_inplace(&mut buf, len, &STANDARD, &SORTABLE).unwrap();
+        assert_eq!(ans, encoded.as_bytes());
+    }
+
+    let rand_key = || -> Vec<u8> {
+        let n = rand::random::<usize>() % 8;
+        (0..n)
+            .map(|_| [0x00, 0x01, 0x7f, 0xff][rand::random::<usize>() % 4])
+            .collect()
+    };
+    for _ in 0..10000 {
+        let (a, b) = (rand_key(), rand_key());
+        let (ea, eb) = (SORTABLE.encode_to_string(&a), SORTABLE.encode_to_string(&b));
+        assert_eq!(a.cmp(&b), ea.cmp(&eb), "a = {a:?}, b = {b:?}");
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_mime() {
+    fn wrap(encoded: &str, line: usize, sep: &[u8]) -> Vec<u8> {
+        let mut ans = Vec::new();
+        for chunk in encoded.as_bytes().chunks(line) {
+            ans.extend_from_slice(chunk);
+            ans.extend_from_slice(sep);
+        }
+        ans
+    }
+
+    for n in (0..600).step_by(7) {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, STANDARD_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+            let mut buf = vec![0u8; n];
+
+            for src in [
+                encoded.clone().into_bytes(),
+                wrap(&encoded, 76, b"\r\n"),
+                wrap(&encoded, 64, b"\r\n"),
+                wrap(&encoded, 76, b"\n"),
+                wrap(&encoded, 5, b" \t"),
+                wrap(&encoded, 4, b"\r\n"),
+            ] {
+                let ans = base64.decode_mime(&src, buf.as_mut_slice().as_out()).unwrap();
+                assert_eq!(ans, bytes);
+            }
+
+            if n > 0 {
+                let src = wrap(&encoded, 76, b"\r\n");
+                assert!(base64.decode_mime(&src, buf[..n - 1].as_out()).is_err());
+            }
+        }
+    }
+
+    let mut buf = [0u8; 64];
+    let ans = STANDARD
+        .decode_mime(b"Zm9v\r\nYmFy\r\n", buf.as_mut_slice().as_out())
+        .unwrap();
+    assert_eq!(ans, b"foobar");
+    assert!(STANDARD.decode_mime(b"Zm9v,YmFy", buf.as_mut_slice().as_out()).is_err());
+    assert!(STANDARD
+        .decode_mime(b"Zm9=\r\nYmFy", buf.as_mut_slice().as_out())
+        .is_err());
+
+    let line = "QUJD".repeat(19);
+    let src = format!("{line}\r\n{line}\r\n");
+    let ans = STANDARD.decode_mime(src.as_bytes(), buf.as_mut_slice().as_out());
+    assert!(ans.is_err());
+    let mut buf = [0u8; 114];
+    let ans = STANDARD
+        .decode_mime(src.as_bytes(), buf.as_mut_slice().as_out())
+        .unwrap();
+    assert_eq!(ans, "ABC".repeat(38).as_bytes());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn relaxed() {
+    let relaxed = Base64::relaxed();
+
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_type::<Vec<u8>>(&bytes);
+
+            let mut noisy = Vec::new();
+            for (i, &c) in encoded.iter().enumerate() {
+                noisy.push(c);
+                if i % 13 == 5 {
+                    noisy.extend_from_slice(b"\r\n");
+                }
+            }
+            noisy.extend_from_slice(b"=== \n");
+
+            assert_eq!(relaxed.decode_to_vec(&encoded).unwrap(), bytes);
+            assert_eq!(&*relaxed.decode_to_boxed_bytes(&noisy).unwrap(), bytes);
+
+            let mut buf = vec![0u8; noisy.len()];
+            assert_eq!(relaxed.decode(&noisy, buf.as_out()).unwrap(), bytes);
+        }
+    }
+
+    let cases: &[(&str, &[u8])] = &[
+        ("Zm9vYg==", b"foob"),
+        ("Zm9vYg", b"foob"),
+        ("Zm9vYh=", b"foob"),
+        ("Zm9v\tYm+/", b"foobo\xbf"),
+        ("Zm9v Ym-_", b"foobo\xbf"),
+        ("Zm+v_w", b"fo\xaf\xff"),
+        ("", b""),
+        (" = \n", b""),
+    ];
+    for &(input, output) in cases {
+        assert_eq!(relaxed.decode_to_vec(input).unwrap(), output);
+    }
+
+    for input in ["Zm9vY", "Zm9v*mFy", "Zm=9v"] {
+        assert!(relaxed.decode_to_vec(input).is_err());
+    }
+
+    let mut buf = [0u8; 4];
+    assert!(relaxed.decode(b"Zm9vYg", buf.as_mut_slice().as_out()).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_text() {
+    let separators: [&[u8]; 5] = [
+        b"\r\n",
+        b" ",
+        "\u{2028}".as_bytes(),
+        "\u{2029}".as_bytes(),
+        "\u{feff}".as_bytes(),
+    ];
+
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_type::<Vec<u8>>(&bytes);
+
+            let mut text = Vec::from("\u{feff}".as_bytes());
+            for (i, &c) in encoded.iter().enumerate() {
+                text.push(c);
+                text.extend_from_slice(separators[i % separators.len()]);
+            }
+
+            let mut buf = vec![0u8; text.len()];
+            assert_eq!(
+                base64.decode_text(&encoded, buf.as_mut_slice().as_out()).unwrap(),
+                bytes
+            );
+            assert_eq!(base64.decode_text(&text, buf.as_mut_slice().as_out()).unwrap(), bytes);
+            assert_eq!(base64.decode_text_inplace(&mut text).unwrap(), bytes);
+        }
+    }
+
+    let mut buf = [0u8; 64];
+    let ans = STANDARD.decode_text("Zm9v\u{2028}YmFy".as_bytes(), buf.as_mut_slice().as_out());
+    assert_eq!(ans.unwrap(), b"foobar");
+
+    for src in [
+        "Zm9v\u{2027}YmFy",
+        "Zm9v\u{a0}YmFy",
+        "Zm9v\u{e2}YmFy",
+        "Zm9vYmFy\u{feff}x",
+    ] {
+        assert!(STANDARD
+            .decode_text(src.as_bytes(), buf.as_mut_slice().as_out())
+            .is_err());
+    }
+    let mut truncated = *b"Zm9vYmFy\xe2\x80";
+    assert!(STANDARD.decode_text_inplace(&mut truncated).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn output_targets() {
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+
+            let mut buf = vec![0u8; encoded.len()];
+            assert_eq!(
+                base64.encode_into(&bytes, buf.as_mut_slice()).unwrap(),
+                encoded.as_bytes()
+            );
+            assert_eq!(base64.encode_into(&bytes, buf.as_out()).unwrap(), encoded.as_bytes());
+
+            let mut vec = b"x".to_vec();
+            base64.encode_into(&bytes, &mut vec);
+            assert_eq!(vec[1..], *encoded.as_bytes());
+
+            let mut string = String::from("x");
+            base64.encode_into(&bytes, &mut string);
+            assert_eq!(string[1..], encoded);
+
+            let mut buf = vec![0u8; n];
+            assert_eq!(base64.decode_into(&encoded, buf.as_mut_slice()).unwrap(), bytes);
+            assert_eq!(base64.decode_into(&encoded, buf.as_out()).unwrap(), bytes);
+
+            let mut vec = b"x".to_vec();
+            base64.decode_into(&encoded, &mut vec).unwrap();
+            assert_eq!(vec[1..], bytes);
+
+            if n > 0 {
+                let mut buf = vec![0u8; n - 1];
+                assert!(base64.decode_into(&encoded, buf.as_mut_slice()).is_err());
+                let mut buf = vec![0u8; encoded.len() - 1];
+                assert!(base64.encode_into(&bytes, buf.as_mut_slice()).is_err());
+            }
+        }
+    }
+
+    let mut vec = Vec::new();
+    assert!(STANDARD.decode_into("Zm9v*", &mut vec).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn inspect() {
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE, STANDARD_NO_PAD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_type::<Vec<u8>>(&bytes);
+            let shape = base64.inspect(&encoded).unwrap();
+
+            let pad_chars = encoded.iter().rev().take_while(|&&x| x == b'=').count();
+            assert_eq!(shape.decoded_len, n);
+            assert_eq!(shape.input_len, encoded.len());
+            assert_eq!(usize::from(shape.pad_chars), pad_chars);
+            assert_eq!(shape.significant_chars, encoded.len() - pad_chars);
+
+            assert_eq!(base64.decoded_length(&encoded).unwrap(), shape.decoded_len);
+            assert!(base64.check(&encoded).is_ok());
+        }
+    }
+
+    let optional = STANDARD_NO_PAD.accept_optional_padding();
+    let shape = optional.inspect(b"Zg==").unwrap();
+    assert_eq!((shape.decoded_len, shape.pad_chars, shape.significant_chars), (1, 2, 2));
+    let shape = optional.inspect(b"Zg").unwrap();
+    assert_eq!((shape.decoded_len, shape.pad_chars, shape.significant_chars), (1, 0, 2));
+
+    for bad in ["Zg=", "Zh==", "Z===", "Zm9v*mFy", "Zm9vY"] {
+        assert!(STANDARD.inspect(bad.as_bytes()).is_err());
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn encode_grouped() {
+    fn naive(encoded: &str, group: usize, sep: &str) -> String {
+        let chunks: Vec<&str> = encoded
+            .as_bytes()
+            .chunks(group)
+            .map(|c| std::str::from_utf8(c).unwrap())
+            .collect();
+        chunks.join(sep)
+    }
+
+    for n in 0..200 {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+            for (group, sep) in [
+                (76, "\r\n"),
+                (64, "\n"),
+                (4, " "),
+                (1, ":"),
+                (5, ""),
+                (7, "--"),
+                (1000, "\n"),
+            ] {
+                let expected = naive(&encoded, group, sep);
+
+                let len = base64.grouped_encoded_length(n, group, sep.len());
+                assert_eq!(len, Some(expected.len()));
+
+                assert_eq!(base64.encode_grouped_to_string(&bytes, group, sep), expected);
+
+                let mut buf = vec![0u8; expected.len()];
+                let ans = base64.encode_grouped(&bytes, buf.as_out(), group, sep.as_bytes());
+                assert_eq!(ans.unwrap(), expected.as_bytes());
+
+                if !expected.is_empty() {
+                    let mut buf = vec![0u8; expected.len() - 1];
+                    assert!(base64
+                        .encode_grouped(&bytes, buf.as_out(), group, sep.as_bytes())
+                        .is_err());
+                }
+            }
+        }
+    }
+
+    assert_eq!(STANDARD.grouped_encoded_length(usize::MAX / 4, 1, usize::MAX), None);
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn collect_string() {
+    use base64_simd::Base64String;
+
+    for n in [0, 1, 2, 3, 4, 767, 768, 769, 1536, 2000] {
+        let bytes = rand_bytes(n);
+        let expected = STANDARD.encode_to_string(&bytes);
+
+        let ans: Base64String = bytes.iter().collect();
+        assert_eq!(ans.as_str(), expected);
+
+        let ans: Base64String = bytes.iter().copied().filter(|_| true).collect();
+        assert_eq!(&*ans, expected);
+        assert_eq!(ans.to_string(), expected);
+        assert_eq!(String::from(ans), expected);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn reference() {
+    use vsimd::reference::{decode, encode};
+
+    let variants = [
+        (STANDARD, Some(b'=')),
+        (URL_SAFE, Some(b'=')),
+        (STANDARD_NO_PAD, None),
+        (URL_SAFE_NO_PAD, None),
+    ];
+
+    for n in 0..256 {
+        let bytes = rand_bytes(n);
+        for (base64, pad) in &variants {
+            let charset = base64.charset();
+
+            let encoded = encode(&bytes, charset, *pad);
+            assert_eq!(base64.encode_type::<Vec<u8>>(&bytes), encoded);
+            assert_eq!(base64.decode_to_vec(&encoded).unwrap(), bytes);
+
+            if encoded.is_empty() {
+                continue;
+            }
+            let pos = rand::random::<usize>() % encoded.len();
+            for x in [b'A', b'/', b'_', b'=', b'*', 0x80] {
+                let mut corrupted = encoded.clone();
+                corrupted[pos] = x;
+                let expected = decode(&corrupted, charset, *pad);
+                assert_eq!(base64.decode_to_vec(&corrupted).ok(), expected);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_to_writer() {
+    use std::io::{self, Write};
+
+    /// A writer which records the size of each write.
+    #[derive(Default)]
+    struct Blocks {
+        data: Vec<u8>,
+        sizes: Vec<usize>,
+    }
+
+    impl Write for Blocks {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.data.extend_from_slice(buf);
+            self.sizes.push(buf.len());
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    for n in [0, 1, 2, 3, 4, 5, 6, 7, 100, 1000] {
+        let bytes = rand_bytes(n);
+        for base64 in [STANDARD, URL_SAFE_NO_PAD] {
+            let encoded = base64.encode_to_string(&bytes);
+            for block_size in [3, 6, 300, 3000] {
+                let mut w = Blocks::default();
+                let ans = base64.decode_to_writer(encoded.as_bytes(), &mut w, block_size).unwrap();
+                assert_eq!(ans, n);
+                assert_eq!(w.data, bytes);
+
+                let (last, init) = w.sizes.split_last().unwrap_or((&0, &[]));
+                assert!(init.iter().all(|&s| s == block_size));
+                assert!(*last <= block_size);
+            }
+        }
+    }
+
+    let mut w = Vec::new();
+    let err = STANDARD.decode_to_writer(b"Zm9vYmFy*mF6", &mut w, 3).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(w, b"foobar");
+
+    let err = STANDARD.decode_to_writer(b"Zm9vY", &mut w, 3).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn nul_policy() {
+    use base64_simd::NulPolicy;
+
+    let variants: [fn(NulPolicy) -> Base64; 2] = [|p| STANDARD.on_nul(p), |p| URL_SAFE_NO_PAD.on_nul(p)];
+
+    for n in [0, 1, 2, 3, 100, 1000] {
+        let bytes = rand_bytes(n);
+        for variant in variants {
+            let encoded = variant(NulPolicy::Error).encode_type::<Vec<u8>>(&bytes);
+
+            let mut field = encoded.clone();
+            field.resize(encoded.len() + 7, 0);
+
+            let mut sparse = Vec::new();
+            for (i, &x) in encoded.iter().enumerate() {
+                sparse.push(x);
+                if i % 3 == 0 {
+                    sparse.push(0);
+                }
+            }
+            sparse.insert(0, 0);
+
+            let mut buf = vec![0u8; sparse.len()];
+
+            {
+                let base64 = variant(NulPolicy::Error);
+                assert!(base64.check(&field).is_err());
+                assert!(base64.decode_to_vec(&field).is_err());
+                assert!(base64.decode(&sparse, buf.as_out()).is_err());
+            }
+
+            {
+                let base64 = variant(NulPolicy::Stop);
+                assert_eq!(base64.decoded_length(&field).unwrap(), n);
+                base64.check(&field).unwrap();
+                assert_eq!(base64.decode(&field, buf.as_out()).unwrap(), bytes);
+                assert_eq!(base64.decode_inplace(&mut field.clone()).unwrap(), bytes);
+                assert_eq!(base64.decode_to_vec(&field).unwrap(), bytes);
+
+                let mut truncated = field.clone();
+                truncated.insert(encoded.len() / 2, 0);
+                assert_eq!(
+                    base64.check(&truncated).is_ok(),
+                    base64.decode_to_vec(&truncated).is_ok()
+                );
+            }
+
+            {
+                let base64 = variant(NulPolicy::Skip);
+                for src in [&field, &sparse] {
+                    assert_eq!(base64.decoded_length(src).unwrap(), n);
+                    base64.check(src).unwrap();
+                    assert_eq!(base64.decode(src, buf.as_out()).unwrap(), bytes);
+                    assert_eq!(base64.decode_inplace(&mut src.clone()).unwrap(), bytes);
+                    assert_eq!(base64.decode_to_vec(src).unwrap(), bytes);
+
+                    let mut out = b"prefix".to_vec();
+                    base64.decode_append(src, &mut out).unwrap();
+                    assert_eq!(&out[6..], bytes);
+
+                    // `dst` only needs the decoded length.
+                    let mut exact = vec![0u8; n];
+                    assert_eq!(base64.decode(src, exact.as_out()).unwrap(), bytes);
+                    assert_eq!(base64.decode_chunked(src, exact.as_out(), 8).unwrap(), bytes);
+                    assert_eq!(base64.decode_any(src, exact.as_out()).unwrap(), bytes);
+                    assert!(base64.eq_decoded(src, &bytes));
+                    if n > 0 {
+                        let err = base64.decode(src, exact[..n - 1].as_out()).unwrap_err();
+                        assert_eq!(err.capacity().unwrap().required, n);
+
+                        let mut other = bytes.clone();
+                        other[n - 1] ^= 1;
+                        assert!(!base64.eq_decoded(src, &other));
+                    }
+                }
+
+                if !encoded.is_empty() {
+                    let mut bad = sparse.clone();
+                    bad.push(b'*');
+                    assert!(base64.check(&bad).is_err());
+                    assert!(base64.decoded_length(&bad).is_err() || base64.decode_to_vec(&bad).is_err());
+                    assert!(base64.decode_to_vec(&bad).is_err());
+                }
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn capacity_error() {
+    use base64_simd::{CapacityError, ErrorKind};
+
+    let mut buf = [0u8; 64];
+
+    let err = STANDARD.encode(b"hello", buf[..7].as_out()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InsufficientCapacity);
+    assert_eq!(
+        err.capacity(),
+        Some(CapacityError {
+            required: 8,
+            provided: 7
+        })
+    );
+
+    let err = STANDARD.decode(b"aGVsbG8=", buf[..4].as_out()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InsufficientCapacity);
+    assert_eq!(
+        err.capacity(),
+        Some(CapacityError {
+            required: 5,
+            provided: 4
+        })
+    );
+
+    let err = STANDARD
+        .decode_chained(b"aGVs", b"bG8=", buf[..4].as_out())
+        .unwrap_err();
+    assert_eq!(
+        err.capacity(),
+        Some(CapacityError {
+            required: 5,
+            provided: 4
+        })
+    );
+
+    let err = STANDARD.decode_any(b"aGVsbG8=", buf[..0].as_out()).unwrap_err();
+    assert_eq!(err.capacity().map(|c| c.required), Some(5));
+
+    let err = STANDARD.decode(b"aGVsbG8!", buf.as_mut_slice().as_out()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(err.capacity(), None);
+
+    assert_eq!(STANDARD.decode(b"aGVsbG8=", buf[..5].as_out()).unwrap(), b"hello");
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn url_decode() {
+    use base64_simd::url;
+
+    let cases: &[(&str, &[u8])] = &[
+        ("", b""),
+        ("aGVsbG8=", b"hello"),
+        ("aGVsbG8%3D", b"hello"),
+        ("aGVsbA%3d%3D", b"hell"),
+        ("%2B%2Fx%2B%2Fw%3D%3D", &[0xfb, 0xfc, 0x7e, 0xff]),
+        ("%2b%2fx%2b%2fw==", &[0xfb, 0xfc, 0x7e, 0xff]),
+    ];
+
+    let mut buf = [0u8; 64];
+    for &(src, expected) in cases {
+        let ans = url::decode(&STANDARD, src.as_bytes(), buf.as_mut_slice().as_out()).unwrap();
+        assert_eq!(ans, expected);
+        assert_eq!(url::decode_to_vec(&STANDARD, src).unwrap(), expected);
+    }
+
+    let bad = [
+        "aGVsbG8%",
+        "aGVsbG8%3",
+        "aGVsbG8%41",
+        "aGVsbG8%%3D",
+        "aGVsbA%3D%3D%3D",
+        "aGVs%20bG8=",
+    ];
+    for src in bad {
+        assert!(url::decode(&STANDARD, src.as_bytes(), buf.as_mut_slice().as_out()).is_err());
+        assert!(url::decode_to_vec(&STANDARD, src).is_err());
+    }
+
+    // percent sequences are still decoded with the URL-safe charset, which rejects `+` and `/`
+    assert!(url::decode_to_vec(&URL_SAFE, "%2B%2Fx%2B%2Fw%3D%3D").is_err());
+    assert_eq!(
+        url::decode_to_vec(&URL_SAFE, "-_x-_w%3D%3D").unwrap(),
+        [0xfb, 0xfc, 0x7e, 0xff]
+    );
+
+    let err = url::decode(&STANDARD, b"aGVsbG8%3D", buf[..5].as_out()).unwrap_err();
+    assert_eq!(err.capacity().map(|c| (c.required, c.provided)), Some((10, 5)));
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_iter() {
+    use base64_simd::ErrorKind;
+
+    let data = b"The quick brown fox jumps over the lazy dog";
+    for base64 in [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD] {
+        for n in 0..data.len() {
+            let encoded = base64.encode_type::<Vec<u8>>(&data[..n]);
+            let iter = base64.decode_iter(&encoded);
+            let (lower, upper) = iter.size_hint();
+            assert!(lower <= n && n <= upper.unwrap());
+            let decoded: Vec<u8> = iter.collect::<Result<_, _>>().unwrap();
+            assert_eq!(decoded, &data[..n]);
+        }
+    }
+
+    let cases: &[(&[u8], &[u8], usize)] = &[
+        (b"aGVs*G8=", b"hel", 4),
+        (b"*GVsbG8=", b"", 0),
+        (b"aGVsbG8", b"hel", 4),
+        (b"YQ==YQ==", b"", 0),
+        (b"aGVsbG8=aGVs", b"hel", 4),
+    ];
+    for &(src, prefix, pos) in cases {
+        let mut iter = STANDARD.decode_iter(src);
+        let mut ok = Vec::new();
+        let err = loop {
+            match iter.next().unwrap() {
+                Ok(x) => ok.push(x),
+                Err(e) => break e,
+            }
+        };
+        assert_eq!(ok, prefix);
+        assert_ne!(err.kind(), ErrorKind::InsufficientCapacity);
+        assert_eq!(iter.position(), pos);
+        assert!(iter.next().is_none());
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_with_progress() {
+    for base64 in [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD] {
+        for n in [0, 1, 2, 3, 47, 48, 49, 1000] {
+            let data = rand_bytes(n);
+            let encoded = base64.encode_type::<Vec<u8>>(&data);
+            for every in [1, 2, 3, 4, 10, 48, 2000] {
+                let mut calls = Vec::new();
+                let mut buf = vec![0u8; n];
+                let ans = base64
+                    .decode_with_progress(&encoded, buf.as_out(), every, &mut |k| calls.push(k))
+                    .unwrap();
+                assert_eq!(ans, data);
+
+                assert!(calls.windows(2).all(|w| w[0] < w[1]));
+                assert_eq!(calls.last().copied().unwrap_or(0), n);
+                if every % 3 == 0 {
+                    assert!(calls.iter().zip(1..).all(|(&k, i)| k == n.min(every * i)));
+                }
+            }
+        }
+    }
+
+    let mut calls = Vec::new();
+    let mut buf = [0u8; 8];
+    assert!(STANDARD
+        .decode_with_progress(b"aGVs*G8=", buf.as_mut_slice().as_out(), 3, &mut |k| calls.push(k))
+        .is_err());
+    assert_eq!(calls, [3]);
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_strided() {
+    use base64_simd::{ErrorKind, NulPolicy};
+
+    for base64 in [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD] {
+        for n in [0, 1, 2, 3, 47, 48, 49, 191, 192, 193, 1000] {
+            let data = rand_bytes(n);
+            let encoded = base64.encode_type::<Vec<u8>>(&data);
+            for (stride, offset) in [(1, 0), (1, 5), (2, 1), (3, 2), (7, 0)] {
+                let len = if n == 0 { 0 } else { offset + (n - 1) * stride + 1 };
+                let mut buf = vec![0xaa; len];
+                assert_eq!(base64.decode_strided(&encoded, &mut buf, stride, offset).unwrap(), n);
+                for (i, &x) in buf.iter().enumerate() {
+                    if i >= offset && (i - offset) % stride == 0 {
+                        assert_eq!(x, data[(i - offset) / stride]);
+                    } else {
+                        assert_eq!(x, 0xaa);
+                    }
+                }
+
+                if n > 0 {
+                    let err = base64
+                        .decode_strided(&encoded, &mut buf[..len - 1], stride, offset)
+                        .unwrap_err();
+                    assert_eq!(err.kind(), ErrorKind::InsufficientCapacity);
+                    assert_eq!(err.capacity().unwrap().required, len);
+                }
+            }
+        }
+    }
+
+    // NUL bytes spread over several scratch chunks.
+    let data = rand_bytes(1000);
+    let mut encoded = STANDARD.encode_type::<Vec<u8>>(&data);
+    for i in (0..encoded.len()).step_by(7).rev() {
+        encoded.insert(i, 0);
+    }
+    let mut buf = vec![0; 2000];
+    let base64 = STANDARD.on_nul(NulPolicy::Skip);
+    assert_eq!(base64.decode_strided(&encoded, &mut buf, 2, 0).unwrap(), 1000);
+    assert!(buf.iter().step_by(2).eq(&data));
+
+    // With `stride == 1`, the result is the same as `decode` on `dst[offset..]`.
+    for src in [&b"aGVs\0\0\0bG8="[..], &encoded] {
+        let mut buf = vec![0; 2000];
+        let mut expected = vec![0; 2000];
+        let n = base64.decode(src, expected[3..].as_out()).unwrap().len();
+        assert_eq!(base64.decode_strided(src, &mut buf, 1, 3).unwrap(), n);
+        assert_eq!(buf, expected);
+    }
+
+    let mut buf = [0u8; 16];
+    assert!(STANDARD.decode_strided(b"aGVs*G8=", &mut buf, 2, 0).is_err());
+    assert!(STANDARD.decode_strided(b"aGVsbG8", &mut buf, 2, 0).is_err());
+    let err = STANDARD
+        .decode_strided(b"aGVsbG8=", &mut buf, usize::MAX, 1)
+        .unwrap_err();
+    assert_eq!(err.capacity().unwrap().required, usize::MAX);
+}
+
+#[cfg(all(feature = "std", feature = "unstable"))]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn borrowed_buf() {
+    use std::io::{BorrowedBuf, Read};
+    use std::mem::MaybeUninit;
+
+    for n in [0, 1, 2, 3, 47, 48, 49, 1000] {
+        let data = rand_bytes(n);
+        let encoded = STANDARD.encode_type::<Vec<u8>>(&data);
+
+        let mut storage = vec![MaybeUninit::<u8>::uninit(); encoded.len() + 16];
+        let mut src = BorrowedBuf::from(storage.as_mut_slice());
+        encoded.as_slice().read_buf(src.unfilled()).unwrap();
+        assert_eq!(src.len(), encoded.len());
+
+        let mut buf = vec![0u8; n];
+        let ans = STANDARD.decode_from_borrowed(&src, buf.as_out()).unwrap();
+        assert_eq!(ans, data);
+
+        let mut storage = vec![MaybeUninit::<u8>::uninit(); n];
+        let mut src = BorrowedBuf::from(storage.as_mut_slice());
+        data.as_slice().read_buf(src.unfilled()).unwrap();
+
+        let mut buf = vec![0u8; encoded.len()];
+        let ans = STANDARD.encode_from_borrowed(&src, buf.as_out()).unwrap();
+        assert_eq!(ans, encoded);
+    }
+
+    // The initialized but unfilled part is ignored.
+    let mut storage = *b"AAAAAAAAAAAAAAAA";
+    let mut src = BorrowedBuf::from(storage.as_mut_slice());
+    b"aGk=".as_slice().read_buf(src.unfilled()).unwrap();
+    let mut buf = [0u8; 16];
+    assert_eq!(
+        STANDARD
+            .decode_from_borrowed(&src, buf.as_mut_slice().as_out())
+            .unwrap(),
+        b"hi"
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn encoded_str_is_utf8() {
+    use base64_simd::SORTABLE;
+
+    for base64 in [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD, SORTABLE] {
+        assert!(base64.charset().is_ascii());
+        assert!(base64.charset_str().is_ascii());
+
+        for n in 0..128 {
+            let data = rand_bytes(n);
+
+            let boxed: Box<str> = base64.encode_type(&data);
+            assert!(boxed.is_ascii());
+
+            let string = base64.encode_to_string(&data);
+            assert!(string.is_ascii());
+
+            let mut buf = vec![0u8; base64.encoded_length(n)];
+            let ans = base64.encode_as_str(&data, buf.as_out()).unwrap();
+            assert!(ans.is_ascii());
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_array() {
+    use base64_simd::{ErrorKind, SORTABLE};
+
+    const FOO: [u8; 3] = match URL_SAFE_NO_PAD.decode_array(b"Zm9v") {
+        Ok(bytes) => bytes,
+        Err(_) => panic!(),
+    };
+    assert_eq!(&FOO, b"foo");
+
+    fn check<const N: usize>(base64: &Base64) {
+        let data = rand_bytes(N);
+        let encoded = base64.encode_type::<Vec<u8>>(&data);
+        assert_eq!(base64.decode_array::<N>(&encoded).unwrap(), data.as_slice());
+        if N > 0 {
+            let err = base64.decode_array::<N>(&encoded[..encoded.len() - 1]);
+            assert!(err.is_err());
+        }
+        assert_eq!(base64.decode_array::<5>(&encoded).is_ok(), N == 5);
+    }
+
+    for base64 in [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD, SORTABLE] {
+        check::<0>(&base64);
+        check::<1>(&base64);
+        check::<2>(&base64);
+        check::<3>(&base64);
+        check::<5>(&base64);
+        check::<32>(&base64);
+        check::<100>(&base64);
+    }
+
+    // the same errors as `decode`
+    for src in ["aGVsbG8", "aGVsbG9=", "aGVs*G8=", "aGV=bG8=", "a"] {
+        let expected = STANDARD.decode_to_vec(src).map_err(|e| e.kind());
+        let ans = STANDARD.decode_array::<5>(src.as_bytes()).map_err(|e| e.kind());
+        assert_eq!(ans.is_ok(), expected.is_ok(), "{src}");
+        if let (Err(a), Err(b)) = (ans, expected) {
+            assert_eq!(a, b, "{src}");
+        }
+    }
+    assert_eq!(
+        STANDARD.decode_array::<4>(b"aGVsbG8=").unwrap_err().kind(),
+        ErrorKind::InvalidLength
+    );
+    assert!(STANDARD.decode_array::<5>(b"aGVs\0G8=").is_err());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn block_sizes() {
+    for base64 in [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD] {
+        assert_eq!(base64.encoded_length(Base64::ENCODE_BLOCK_IN), Base64::ENCODE_BLOCK_OUT);
+        assert_eq!(
+            base64.estimated_decoded_length(Base64::DECODE_BLOCK_IN),
+            Base64::DECODE_BLOCK_OUT
+        );
+    }
+    assert_eq!(Base64::ENCODE_BLOCK_IN % 3, 0);
+    assert_eq!(Base64::DECODE_BLOCK_IN % 4, 0);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_pasted() {
+    use base64_simd::ErrorKind;
+
+    let mut buf = [0u8; 16];
+    let cases: &[&[u8]] = &[
+        b"aGVsbG8=",
+        b"base64,aGVsbG8=",
+        b"BASE64,aGVsbG8=",
+        b",base64,aGVsbG8=",
+        b",,base64,,aGVsbG8=,",
+        b"aGVsbG8=,,",
+    ];
+    for &src in cases {
+        let ans = STANDARD.decode_pasted(src, buf.as_mut_slice().as_out()).unwrap();
+        assert_eq!(ans, b"hello");
+    }
+
+    let cases: &[(&[u8], Option<usize>)] = &[
+        (b"base64,aGV*bG8=", Some(10)),
+        (b",aGVsbG8", None),
+        (b"aGVs=G8=", Some(4)),
+        (b" aGVsbG8=", Some(0)),
+        (b"data:base64,aGVsbG8=", Some(4)),
+        (b"base64,aGVsbG8=,x", Some(14)),
+    ];
+    for &(src, index) in cases {
+        let err = STANDARD.decode_pasted(src, buf.as_mut_slice().as_out()).unwrap_err();
+        assert_eq!(err.index(), index, "{src:?}");
+    }
+
+    let err = STANDARD
+        .decode_pasted(b"base64,aGVsbG8=", buf[..4].as_out())
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InsufficientCapacity);
+    assert_eq!(err.index(), None);
+
+    assert!(STANDARD
+        .decode_pasted(b",,", buf.as_mut_slice().as_out())
+        .unwrap()
+        .is_empty());
+
+    #[cfg(feature = "alloc")]
+    assert_eq!(STANDARD.decode_pasted_to_vec("base64,aGVsbG8=").unwrap(), b"hello");
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn pem() {
+    use base64_simd::pem;
+
+    let blocks: Vec<(&str, Vec<u8>)> = vec![
+        ("CERTIFICATE", rand_bytes(100)),
+        ("CERTIFICATE", rand_bytes(0)),
+        ("RSA PRIVATE KEY", rand_bytes(48)),
+    ];
+
+    let mut chain = String::from("Subject: leaf\r\n\r\n");
+    for (label, bytes) in &blocks {
+        chain.push_str(&format!("-----BEGIN {label}-----\r\n"));
+        for line in STANDARD.encode_to_string(bytes).as_bytes().chunks(64) {
+            chain.push_str(core::str::from_utf8(line).unwrap());
+            chain.push_str("\r\n");
+        }
+        chain.push_str(&format!("-----END {label}-----\r\n\n# comment\n"));
+    }
+
+    let objects = pem::decode_all(&chain).unwrap();
+    assert_eq!(objects.len(), blocks.len());
+    for ((label, data), (expected_label, expected)) in objects.iter().zip(&blocks) {
+        assert_eq!(label, expected_label);
+        assert_eq!(&**data, expected.as_slice());
+    }
+
+    let (label, data) = pem::decode(&chain).unwrap();
+    assert_eq!((label.as_str(), &*data), (blocks[0].0, blocks[0].1.as_slice()));
+
+    assert!(pem::decode_all("").unwrap().is_empty());
+    assert!(pem::decode_all("no objects\n").unwrap().is_empty());
+    assert!(pem::decode("no objects\n").is_err());
+
+    let bad = [
+        "-----BEGIN A-----\naGk=\n",
+        "-----BEGIN A-----\naGk=\n-----END B-----\n",
+        "-----BEGIN A-----\naGk\n-----END A-----\n",
+        "-----BEGIN A-----\naGk=\n-----END A-----\n-----BEGIN B-----\n*\n-----END B-----\n",
+    ];
+    for data in bad {
+        assert!(pem::decode_all(data).is_err(), "{data:?}");
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn scan() {
+    use base64_simd::ScanResult;
+
+    fn reference(base64: &Base64, data: &[u8]) -> ScanResult {
+        match data.iter().position(|&x| base64.alphabet_index(x).is_none()) {
+            None => ScanResult::Valid,
+            Some(pos) if data[pos..].iter().all(|&x| x == b'=') => ScanResult::Padding(pos),
+            Some(pos) => ScanResult::Invalid(pos),
+        }
+    }
+
+    for base64 in [&STANDARD, &URL_SAFE] {
+        for n in 0..100 {
+            let mut buf = vec![0u8; base64.encoded_length(n)];
+            let encoded = base64
+                .encode(&rand_bytes(n), buf.as_mut_slice().as_out())
+                .unwrap()
+                .to_vec();
+            let ans = base64.scan(&encoded);
+            assert_eq!(ans, reference(base64, &encoded));
+            let expected = match encoded.iter().position(|&x| x == b'=') {
+                Some(pos) => ScanResult::Padding(pos),
+                None => ScanResult::Valid,
+            };
+            assert_eq!(ans, expected);
+
+            for i in 0..encoded.len() {
+                for x in [b'=', b' ', b'\n', b'.', b'+', b'_', 0x80, 0xff] {
+                    let mut data = encoded.clone();
+                    data[i] = x;
+                    assert_eq!(base64.scan(&data), reference(base64, &data), "{data:?}");
+                }
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_in_arena() {
+    use base64_simd::Arena;
+
+    use core::cell::{Cell, UnsafeCell};
+    use core::mem::MaybeUninit;
+
+    struct Bump {
+        buf: UnsafeCell<[MaybeUninit<u8>; 4096]>,
+        used: Cell<usize>,
+    }
+
+    impl Arena for Bump {
+        fn alloc_uninit(&self, len: usize) -> &mut [MaybeUninit<u8>] {
+            let start = self.used.get();
+            self.used.set(start + len);
+            unsafe { (*self.buf.get()).get_unchecked_mut(start..start + len) }
+        }
+    }
+
+    let arena = Bump {
+        buf: UnsafeCell::new([MaybeUninit::uninit(); 4096]),
+        used: Cell::new(0),
+    };
+
+    let mut decoded = Vec::new();
+    for n in 0..40 {
+        let bytes = rand_bytes(n);
+        let mut buf = vec![0u8; URL_SAFE_NO_PAD.encoded_length(n)];
+        let encoded = URL_SAFE_NO_PAD.encode(&bytes, buf.as_mut_slice().as_out()).unwrap();
+
+        let used = arena.used.get();
+        let ans = URL_SAFE_NO_PAD.decode_in_arena(encoded, &arena).unwrap();
+        assert_eq!(arena.used.get(), used + n);
+        decoded.push((ans, bytes));
+
+        assert!(URL_SAFE_NO_PAD.decode_in_arena(b"a", &arena).is_err());
+        assert_eq!(arena.used.get(), used + n);
+    }
+    for (ans, bytes) in decoded {
+        assert_eq!(&*ans, bytes.as_slice());
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_mime_strict() {
+    fn wrap(encoded: &str, line: usize, sep: &[u8]) -> Vec<u8> {
+        encoded.as_bytes().chunks(line).collect::<Vec<_>>().join(sep)
+    }
+
+    for n in (0..600).step_by(7) {
+        let bytes = rand_bytes(n);
+        let encoded = STANDARD.encode_to_string(&bytes);
+        let mut buf = vec![0u8; n];
+
+        for line in [76, 64, 4] {
+            let mut src = wrap(&encoded, line, b"\r\n");
+            let ans = STANDARD.decode_mime_strict(&src, buf.as_mut_slice().as_out()).unwrap();
+            assert_eq!(ans, bytes);
+
+            if n > 0 {
+                src.extend_from_slice(b"\r\n");
+                let ans = STANDARD.decode_mime_strict(&src, buf.as_mut_slice().as_out()).unwrap();
+                assert_eq!(ans, bytes);
+            }
+        }
+
+        if encoded.len() > 76 {
+            let src = wrap(&encoded, 77, b"\r\n");
+            let err = STANDARD
+                .decode_mime_strict(&src, buf.as_mut_slice().as_out())
+                .unwrap_err();
+            assert_eq!(err.index(), Some(76));
+
+            let src = wrap(&encoded, 76, b"\n");
+            let err = STANDARD
+                .decode_mime_strict(&src, buf.as_mut_slice().as_out())
+                .unwrap_err();
+            assert_eq!(err.index(), Some(76));
+        }
+    }
+
+    let mut buf = [0u8; 64];
+    let cases: &[(&[u8], usize)] = &[
+        (b"Zm9v\r\n\r\nYmFy", 6),
+        (b"\r\nZm9v", 0),
+        (b"Zm9v\r\nYmFy\r\n\r\n", 12),
+        (b"Zm9v\rYmFy", 4),
+        (b"Zm9v \r\nYmFy", 4),
+        (b"Zm9v\r\nYm Fy", 8),
+        (b"Zm9v\r\nYm*y", 8),
+        (b"Zm8=\r\nYmFy", 3),
+    ];
+    for &(src, index) in cases {
+        let err = STANDARD
+            .decode_mime_strict(src, buf.as_mut_slice().as_out())
+            .unwrap_err();
+        assert_eq!(err.index(), Some(index), "{src:?}");
+    }
+
+    let ans = STANDARD
+        .decode_mime_strict(b"Zm9v\r\nYmE=\r\n", buf.as_mut_slice().as_out())
+        .unwrap();
+    assert_eq!(ans, b"fooba");
+    assert!(STANDARD
+        .decode_mime_strict(b"Zm9v\r\nYmF", buf.as_mut_slice().as_out())
+        .is_err());
+    assert!(STANDARD.decode_mime_strict(b"Zm9v\r\nYmFy", buf[..5].as_out()).is_err());
+}
@@ -1,9 +1,11 @@
-use crate::Error;
+use crate::{AsciiCase, Error};
 
 use vsimd::hex::unhex;
 use vsimd::isa::{AVX2, WASM128};
 use vsimd::{matches_isa, SIMD256};
 
+use core::ops::Not;
+
 #[inline(always)]
 unsafe fn check_short(mut src: *const u8, len: usize) -> Result<(), Error> {
     let mut flag = 0;
@@ -81,3 +83,108 @@ pub unsafe fn check_simd<S: SIMD256>(s: S, mut src: *const u8, mut len: usize) -
         check_short(src, len)
     }
 }
+
+#[inline(always)]
+unsafe fn find_non_hex_short(mut src: *const u8, len: usize) -> usize {
+    let base = src;
+    let end = base.add(len);
+    while src < end {
+        if unhex(src.read()) == 0xff {
+            break;
+        }
+        src = src.add(1);
+    }
+    src.offset_from(base) as usize
+}
+
+#[inline(always)]
+pub unsafe fn find_non_hex_fallback(src: *const u8, len: usize) -> usize {
+    find_non_hex_short(src, len)
+}
+
+#[inline(always)]
+pub unsafe fn find_non_hex_simd<S: SIMD256>(s: S, mut src: *const u8, len: usize) -> usize {
+    let base = src;
+
+    if matches_isa!(S, AVX2) {
+        let end = src.add(len / 32 * 32);
+        while src < end {
+            let x = s.v256_load_unaligned(src);
+            if vsimd::hex::check_xn(s, x).not() {
+                break;
+            }
+            src = src.add(32);
+        }
+        if src == end && (len % 32) >= 16 {
+            let x = s.v128_load_unaligned(src);
+            if vsimd::hex::check_xn(s, x) {
+                src = src.add(16);
+            }
+        }
+    } else {
+        let end = src.add(len / 16 * 16);
+        while src < end {
+            let x = s.v128_load_unaligned(src);
+            if vsimd::hex::check_xn(s, x).not() {
+                break;
+            }
+            src = src.add(16);
+        }
+    }
+
+    let checked_len = src.offset_from(base) as usize;
+    let pos = find_non_hex_short(src, len - checked_len);
+    checked_len + pos
+}
+
+/// Returns `true` if `x` is a digit or a hex letter in `case`.
+#[inline(always)]
+pub const fn is_hex_in_case(x: u8, case: AsciiCase) -> bool {
+    match case {
+        AsciiCase::Lower => matches!(x, b'0'..=b'9' | b'a'..=b'f'),
+        AsciiCase::Upper => matches!(x, b'0'..=b'9' | b'A'..=b'F'),
+    }
+}
+
+#[inline(always)]
+unsafe fn check_case_short(mut src: *const u8, len: usize, case: AsciiCase) -> bool {
+    let mut ok = true;
+    let end = src.add(len);
+    while src < end {
+        ok &= is_hex_in_case(src.read(), case);
+        src = src.add(1);
+    }
+    ok
+}
+
+#[inline(always)]
+pub unsafe fn check_case_fallback(src: *const u8, len: usize, case: AsciiCase) -> bool {
+    check_case_short(src, len, case)
+}
+
+#[inline(always)]
+pub unsafe fn check_case_simd<S: SIMD256>(s: S, mut src: *const u8, mut len: usize, case: AsciiCase) -> bool {
+    if matches_isa!(S, AVX2) {
+        let end = src.add(len / 32 * 32);
+        while src < end {
+            let x = s.v256_load_unaligned(src);
+            if vsimd::hex::check_case_xn(s, x, case).not() {
+                return false;
+            }
+            src = src.add(32);
+        }
+        len %= 32;
+    }
+
+    let end = src.add(len / 16 * 16);
+    while src < end {
+        let x = s.v128_load_unaligned(src);
+        if vsimd::hex::check_case_xn(s, x, case).not() {
+            return false;
+        }
+        src = src.add(16);
+    }
+    len %= 16;
+
+    check_case_short(src, len, case)
+}
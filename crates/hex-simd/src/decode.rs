@@ -1,6 +1,6 @@
 use crate::Error;
 
-use vsimd::hex::unhex;
+use vsimd::hex::{unhex, UNHEX_TABLE};
 use vsimd::is_isa_type;
 use vsimd::isa::{Fallback, InstructionSet, AVX2, SSE2, WASM128};
 use vsimd::matches_isa;
@@ -29,13 +29,13 @@ where
 {
     // FIXME: work around for suboptimal auto-vectorization (AVX2, WASM128)
     if matches_isa!(S, AVX2 | WASM128) {
-        let end = src.add(len);
-        while src < end {
-            let flag = decode_bits(src, dst);
-            ensure!(flag != 0xff);
-            src = src.add(2);
-            dst = dst.add(1);
-        }
+        ensure!(vsimd::reference::decode_bits::<4, true>(
+            src,
+            len,
+            dst,
+            UNHEX_TABLE.as_ptr(),
+            false
+        ));
         Ok(())
     } else {
         let end = src.add(len);
@@ -202,3 +202,46 @@ pub unsafe fn decode_simd_sse2(s: SSE2, mut src: *const u8, mut len: usize, mut
 
     decode_short::<SSE2>(src, len, dst)
 }
+
+#[inline(always)]
+pub unsafe fn decode_nibbles_fallback(src: *const u8, len: usize, dst: *mut u8) -> Result<(), Error> {
+    let mut flag = 0;
+    let mut i = 0;
+    while i < len {
+        let y = unhex(read(src, i));
+        flag |= y;
+        dst.add(i).write(y);
+        i += 1;
+    }
+    ensure!(flag != 0xff);
+    Ok(())
+}
+
+#[inline(always)]
+pub unsafe fn decode_nibbles_simd<S: SIMD256>(
+    s: S,
+    mut src: *const u8,
+    mut len: usize,
+    mut dst: *mut u8,
+) -> Result<(), Error> {
+    let end = src.add(len / 32 * 32);
+    while src < end {
+        let x = s.v256_load_unaligned(src);
+        let y = try_!(vsimd::hex::unhex_ascii32(s, x));
+        s.v256_store_unaligned(dst, y);
+        src = src.add(32);
+        dst = dst.add(32);
+    }
+    len %= 32;
+
+    if len >= 16 {
+        let x = s.v128_load_unaligned(src);
+        let y = try_!(vsimd::hex::unhex_ascii16(s, x));
+        s.v128_store_unaligned(dst, y);
+        src = src.add(16);
+        dst = dst.add(16);
+        len -= 16;
+    }
+
+    decode_nibbles_fallback(src, len, dst)
+}
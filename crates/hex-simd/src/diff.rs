@@ -0,0 +1,149 @@
+use crate::{AsOut, AsciiCase};
+
+use core::fmt::Write;
+
+use alloc::string::String;
+
+/// The layout of a hex diff produced by [`DiffFormat::diff_to_string`].
+///
+/// Each line shows the offset and the hex strings of both inputs side by side.
+/// A line where the inputs differ starts with `!`.
+/// With markers enabled, it is followed by a line with `^^` under each differing byte.
+/// A byte which is missing from the shorter input counts as differing.
+///
+/// # Examples
+///
+/// ```
+/// let diff = hex_simd::DiffFormat::new().bytes_per_line(4).diff_to_string(b"hello", b"help");
+/// let expected = concat!(
+///     "! 00000000  68656c6c  68656c70\n",
+///     "                  ^^        ^^\n",
+///     "! 00000004  6f\n",
+///     "            ^^        ^^\n",
+/// );
+/// assert_eq!(diff, expected);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DiffFormat {
+    bytes_per_line: usize,
+    markers: bool,
+}
+
+impl Default for DiffFormat {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiffFormat {
+    /// Creates the default layout: 16 bytes per line, with markers.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bytes_per_line: 16,
+            markers: true,
+        }
+    }
+
+    /// Sets the number of bytes per line.
+    ///
+    /// # Panics
+    /// This function asserts that `n > 0`.
+    #[inline]
+    #[must_use]
+    pub const fn bytes_per_line(self, n: usize) -> Self {
+        assert!(n > 0);
+        Self {
+            bytes_per_line: n,
+            ..self
+        }
+    }
+
+    /// Sets whether to add a marker line under each line where the inputs differ.
+    #[inline]
+    #[must_use]
+    pub const fn markers(self, markers: bool) -> Self {
+        Self { markers, ..self }
+    }
+
+    /// Formats the hex diff of `a` and `b`.
+    ///
+    /// Returns an empty string if both inputs are empty.
+    #[inline]
+    #[must_use]
+    pub fn diff_to_string(&self, a: &[u8], b: &[u8]) -> String {
+        let n = self.bytes_per_line;
+        let width = n.saturating_mul(2);
+        let len = a.len().max(b.len());
+
+        let mut ans = String::new();
+        let mut buf = alloc::vec![0u8; width];
+        let mut offset = 0;
+        while offset < len {
+            let end = len.min(offset.saturating_add(n));
+            let line_a = &a[a.len().min(offset)..a.len().min(end)];
+            let line_b = &b[b.len().min(offset)..b.len().min(end)];
+            let differs = line_a != line_b;
+
+            ans.push_str(if differs { "! " } else { "  " });
+            write!(ans, "{offset:08x}  ").unwrap();
+            push_hex(&mut ans, &mut buf, line_a, width);
+            ans.push_str("  ");
+            push_hex(&mut ans, &mut buf, line_b, width);
+            trim_end(&mut ans);
+            ans.push('\n');
+
+            if differs && self.markers {
+                let mut marks = String::new();
+                for i in offset..end {
+                    let mark = if a.get(i) == b.get(i) { "  " } else { "^^" };
+                    marks.push_str(mark);
+                }
+                ans.push_str("            ");
+                ans.push_str(&marks);
+                ans.push_str(&" ".repeat(width - marks.len() + 2));
+                ans.push_str(&marks);
+                trim_end(&mut ans);
+                ans.push('\n');
+            }
+
+            offset = end;
+        }
+        ans
+    }
+}
+
+/// Formats the hex diff of `a` and `b` with the default [`DiffFormat`].
+///
+/// It is useful for the failure messages of assertions on byte slices.
+///
+/// # Examples
+///
+/// ```
+/// let a = [0x00, 0x11, 0x22, 0x33];
+/// let b = [0x00, 0x11, 0x2a, 0x33];
+/// let diff = hex_simd::diff_to_string(&a, &b);
+/// assert!(diff.starts_with("! 00000000  00112233 "));
+/// assert!(diff.ends_with(" 00112a33\n                ^^                                ^^\n"));
+/// ```
+#[inline]
+#[must_use]
+pub fn diff_to_string(a: &[u8], b: &[u8]) -> String {
+    DiffFormat::new().diff_to_string(a, b)
+}
+
+/// Appends the hex string of `data`, padded with spaces to `width` characters.
+fn push_hex(ans: &mut String, buf: &mut [u8], data: &[u8], width: usize) {
+    let hex = crate::encode_as_str(data, buf.as_out(), AsciiCase::Lower).unwrap();
+    ans.push_str(hex);
+    for _ in hex.len()..width {
+        ans.push(' ');
+    }
+}
+
+fn trim_end(s: &mut String) {
+    let len = s.trim_end_matches(' ').len();
+    s.truncate(len);
+}
@@ -0,0 +1,94 @@
+use crate::{encode_array, AsciiCase};
+
+use core::fmt;
+use core::ops::Deref;
+
+/// A hex string of `M` characters stored on the stack.
+///
+/// It is returned by [`encode_digest`] and dereferences to [`str`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexDigest<const M: usize>([u8; M]);
+
+impl<const M: usize> HexDigest<M> {
+    /// Returns the hex string.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: the bytes are produced by the hex encoder, which only outputs ascii characters.
+        unsafe { core::str::from_utf8_unchecked(&self.0) }
+    }
+
+    /// Returns the hex string as bytes.
+    #[inline]
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; M] {
+        &self.0
+    }
+}
+
+impl<const M: usize> Deref for HexDigest<M> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const M: usize> AsRef<str> for HexDigest<M> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const M: usize> AsRef<[u8]> for HexDigest<M> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<const M: usize> fmt::Debug for HexDigest<M> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const M: usize> fmt::Display for HexDigest<M> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+/// Encodes a digest of `N` bytes to a stack string of `M = 2N` characters.
+///
+/// This is [`encode_array`] with a string output. It needs no allocation,
+/// which makes it suitable for the `Display` implementations of hashes.
+///
+/// `case` specifies the ascii case of output.
+///
+/// # Examples
+///
+/// ```
+/// use hex_simd::{AsciiCase, HexDigest};
+///
+/// let hex: HexDigest<8> = hex_simd::encode_digest(&[0xde, 0xad, 0xbe, 0xef], AsciiCase::Lower);
+/// assert_eq!(hex.as_str(), "deadbeef");
+/// assert_eq!(hex.len(), 8);
+/// ```
+///
+/// A mismatched output length does not compile:
+///
+/// ```compile_fail
+/// use hex_simd::{AsciiCase, HexDigest};
+///
+/// let hex: HexDigest<7> = hex_simd::encode_digest(&[0xde, 0xad, 0xbe, 0xef], AsciiCase::Lower);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_digest<const N: usize, const M: usize>(digest: &[u8; N], case: AsciiCase) -> HexDigest<M> {
+    HexDigest(encode_array(digest, case))
+}
@@ -0,0 +1,92 @@
+use crate::{AsOut, AsciiCase};
+
+use core::fmt;
+
+/// A wrapper which formats bytes as a hex string by [`{:x}`](fmt::LowerHex) or [`{:X}`](fmt::UpperHex).
+///
+/// The bytes are encoded in chunks into a stack buffer and written to the formatter,
+/// so no allocation is needed.
+///
+/// The format spec is respected:
+/// + width and fill, right-aligned by default.
+/// + `0` flag, which pads with zeros on the left.
+/// + `#` flag, which adds the `0x` prefix.
+///
+/// # Examples
+///
+/// ```
+/// let bytes = [0xde, 0xad, 0xbe, 0xef];
+///
+/// assert_eq!(format!("{:x}", hex_simd::wrap(&bytes)), "deadbeef");
+/// assert_eq!(format!("{:X}", hex_simd::wrap(&bytes)), "DEADBEEF");
+/// assert_eq!(format!("{:#x}", hex_simd::wrap(&bytes)), "0xdeadbeef");
+/// assert_eq!(format!("{:>12x}", hex_simd::wrap(&bytes)), "    deadbeef");
+/// assert_eq!(format!("{:*<12x}", hex_simd::wrap(&bytes)), "deadbeef****");
+/// assert_eq!(format!("{:012x}", hex_simd::wrap(&bytes)), "0000deadbeef");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Hex<'a>(&'a [u8]);
+
+/// Wraps bytes to format them as a hex string.
+///
+/// See [`Hex`].
+#[inline]
+#[must_use]
+pub fn wrap(data: &[u8]) -> Hex<'_> {
+    Hex(data)
+}
+
+impl fmt::LowerHex for Hex<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(self.0, f, AsciiCase::Lower)
+    }
+}
+
+impl fmt::UpperHex for Hex<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(self.0, f, AsciiCase::Upper)
+    }
+}
+
+fn fmt_hex(data: &[u8], f: &mut fmt::Formatter<'_>, case: AsciiCase) -> fmt::Result {
+    let prefix = if f.alternate() { "0x" } else { "" };
+    let len = prefix.len() + data.len().saturating_mul(2);
+    let pad = f.width().map_or(0, |w| w.saturating_sub(len));
+
+    if f.sign_aware_zero_pad() {
+        f.write_str(prefix)?;
+        write_fill(f, '0', pad)?;
+        return write_hex(data, f, case);
+    }
+
+    let (pre, post) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, pad),
+        Some(fmt::Alignment::Center) => (pad / 2, pad - pad / 2),
+        Some(fmt::Alignment::Right) | None => (pad, 0),
+    };
+    let fill = f.fill();
+
+    write_fill(f, fill, pre)?;
+    f.write_str(prefix)?;
+    write_hex(data, f, case)?;
+    write_fill(f, fill, post)
+}
+
+fn write_fill(f: &mut fmt::Formatter<'_>, fill: char, n: usize) -> fmt::Result {
+    for _ in 0..n {
+        fmt::Write::write_char(f, fill)?;
+    }
+    Ok(())
+}
+
+fn write_hex(data: &[u8], f: &mut fmt::Formatter<'_>, case: AsciiCase) -> fmt::Result {
+    const CHUNK: usize = 256;
+    let mut buf = [0u8; CHUNK * 2];
+    for chunk in data.chunks(CHUNK) {
+        let ans = crate::encode_as_str(chunk, buf.as_mut_slice().as_out(), case).unwrap();
+        f.write_str(ans)?;
+    }
+    Ok(())
+}
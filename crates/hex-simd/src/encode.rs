@@ -23,13 +23,8 @@ unsafe fn encode_bits(src: *const u8, dst: *mut u8, charset: *const u8) {
 }
 
 #[inline(always)]
-unsafe fn encode_short(mut src: *const u8, len: usize, mut dst: *mut u8, charset: *const u8) {
-    let end = src.add(len);
-    while src < end {
-        encode_bits(src, dst, charset);
-        src = src.add(1);
-        dst = dst.add(2);
-    }
+unsafe fn encode_short(src: *const u8, len: usize, dst: *mut u8, charset: *const u8) {
+    vsimd::reference::encode_bits::<4>(src, len, dst, charset);
 }
 
 #[inline(always)]
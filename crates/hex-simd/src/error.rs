@@ -1,12 +1,69 @@
 use core::fmt;
 
 /// Hex Error
-pub struct Error(());
+pub struct Error {
+    kind: ErrorKind,
+    index: Option<usize>,
+}
+
+/// The kind of a [`Error`]
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The length of the input to decode is odd.
+    OddLength,
+
+    /// The input or the output is invalid for other reasons.
+    InvalidData,
+
+    /// The length of the input is not the expected length.
+    InvalidLength,
+
+    /// The input contains a hex letter in the unexpected case.
+    InvalidCase,
+}
 
 impl Error {
     #[inline(always)]
     pub(crate) const fn new() -> Self {
-        Error(())
+        Self::with_kind(ErrorKind::InvalidData)
+    }
+
+    #[inline(always)]
+    pub(crate) const fn with_kind(kind: ErrorKind) -> Self {
+        Error { kind, index: None }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn at(index: usize) -> Self {
+        Error {
+            kind: ErrorKind::InvalidData,
+            index: Some(index),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) const fn with_kind_at(kind: ErrorKind, index: usize) -> Self {
+        Error {
+            kind,
+            index: Some(index),
+        }
+    }
+
+    /// Returns the kind of the error.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the index of the first offending byte in the input.
+    ///
+    /// Only the functions which document it report the index. The others return `None`.
+    #[inline]
+    #[must_use]
+    pub const fn index(&self) -> Option<usize> {
+        self.index
     }
 }
 
@@ -34,6 +91,11 @@ macro_rules! ensure {
             return Err($crate::error::Error::new());
         }
     };
+    ($cond:expr, $kind:ident) => {
+        if !$cond {
+            return Err($crate::error::Error::with_kind($crate::error::ErrorKind::$kind));
+        }
+    };
 }
 
 #[allow(unused_macros)]
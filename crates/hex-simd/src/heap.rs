@@ -12,7 +12,7 @@ fn decode_to_boxed_bytes(src: &[u8]) -> Result<Box<[u8]>, Error> {
         return Ok(Box::from([]));
     }
 
-    ensure!(src.len() % 2 == 0);
+    ensure!(src.len() % 2 == 0, OddLength);
 
     unsafe {
         let mut buf = alloc_uninit_bytes(src.len() / 2);
@@ -33,7 +33,7 @@ fn decode_append_vec(src: &[u8], buf: &mut Vec<u8>) -> Result<(), Error> {
         return Ok(());
     }
 
-    ensure!(src.len() % 2 == 0);
+    ensure!(src.len() % 2 == 0, OddLength);
     let m = src.len() / 2;
 
     buf.reserve_exact(m);
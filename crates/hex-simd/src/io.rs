@@ -0,0 +1,189 @@
+use crate::{AsOut, AsciiCase, Error, ErrorKind};
+
+use core::fmt;
+use std::io;
+
+const BUF_SIZE: usize = 1024;
+
+#[inline]
+fn invalid_data(err: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// A reader that decodes hex characters from an inner reader.
+///
+/// A lone character at the end of a read is carried over to the next one.
+/// A lone character at the end of the input is reported as [`io::ErrorKind::InvalidData`],
+/// which is also the error kind of invalid characters.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Read;
+///
+/// let mut reader = hex_simd::DecoderReader::new(&b"48656c6c6f"[..]);
+/// let mut buf = Vec::new();
+/// reader.read_to_end(&mut buf).unwrap();
+/// assert_eq!(buf, b"Hello");
+/// ```
+pub struct DecoderReader<R> {
+    inner: R,
+    buf: [u8; BUF_SIZE],
+    pos: usize,
+    len: usize,
+    eof: bool,
+}
+
+impl<R: io::Read> DecoderReader<R> {
+    /// Creates a reader that decodes hex characters from `inner`.
+    #[inline]
+    #[must_use]
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: [0; BUF_SIZE],
+            pos: 0,
+            len: 0,
+            eof: false,
+        }
+    }
+}
+
+impl<R> DecoderReader<R> {
+    /// Returns a reference to the inner reader.
+    #[inline]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner reader.
+    ///
+    /// Reading from it directly skips the characters buffered by this reader.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the inner reader. The buffered characters are discarded.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: io::Read> io::Read for DecoderReader<R> {
+    #[inline]
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if out.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            let avail = self.len - self.pos;
+            if avail >= 2 {
+                let n = (avail / 2).min(out.len());
+                let src = &self.buf[self.pos..self.pos + n * 2];
+                crate::decode(src, out[..n].as_out()).map_err(invalid_data)?;
+                self.pos += n * 2;
+                return Ok(n);
+            }
+
+            if self.eof {
+                if avail == 1 {
+                    return Err(invalid_data(Error::with_kind(ErrorKind::OddLength)));
+                }
+                return Ok(0);
+            }
+
+            // move the residual character to the front
+            if avail == 1 {
+                self.buf[0] = self.buf[self.pos];
+            }
+            self.pos = 0;
+            self.len = avail;
+
+            let k = self.inner.read(&mut self.buf[avail..])?;
+            if k == 0 {
+                self.eof = true;
+            }
+            self.len += k;
+        }
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for DecoderReader<R> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecoderReader")
+            .field("inner", &self.inner)
+            .field("buffered", &(self.len - self.pos))
+            .field("eof", &self.eof)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A writer that encodes bytes to hex characters and writes them to an inner writer.
+///
+/// Each byte is encoded to two characters, so nothing is buffered.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write;
+///
+/// let mut writer = hex_simd::EncoderWriter::new(Vec::new(), hex_simd::AsciiCase::Lower);
+/// writer.write_all(b"Hello").unwrap();
+/// assert_eq!(writer.into_inner(), b"48656c6c6f");
+/// ```
+#[derive(Debug)]
+pub struct EncoderWriter<W> {
+    inner: W,
+    case: AsciiCase,
+}
+
+impl<W: io::Write> EncoderWriter<W> {
+    /// Creates a writer that encodes bytes to `inner`.
+    ///
+    /// `case` specifies the ascii case of output.
+    #[inline]
+    #[must_use]
+    pub fn new(inner: W, case: AsciiCase) -> Self {
+        Self { inner, case }
+    }
+}
+
+impl<W> EncoderWriter<W> {
+    /// Returns a reference to the inner writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the inner writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: io::Write> io::Write for EncoderWriter<W> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut out = [0u8; BUF_SIZE];
+        let n = buf.len().min(BUF_SIZE / 2);
+        let ans = crate::encode(&buf[..n], out.as_mut_slice().as_out(), self.case).map_err(invalid_data)?;
+        self.inner.write_all(ans)?;
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{AsciiCase, Error};
+
+use vsimd::hex::unhex;
+
+/// An iterator that encodes bytes to hex characters.
+///
+/// It yields two characters for each input byte.
+#[derive(Debug, Clone)]
+pub struct EncodeIter<I> {
+    iter: I,
+    charset: &'static [u8; 16],
+    pending: Option<u8>,
+}
+
+impl<I: Iterator<Item = u8>> EncodeIter<I> {
+    /// Creates an iterator that encodes bytes from `iter`.
+    ///
+    /// `case` specifies the ascii case of output.
+    #[inline]
+    #[must_use]
+    pub fn new(iter: impl IntoIterator<IntoIter = I>, case: AsciiCase) -> Self {
+        let charset = match case {
+            AsciiCase::Lower => vsimd::hex::LOWER_CHARSET,
+            AsciiCase::Upper => vsimd::hex::UPPER_CHARSET,
+        };
+        Self {
+            iter: iter.into_iter(),
+            charset,
+            pending: None,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for EncodeIter<I> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(lo) = self.pending.take() {
+            return Some(lo);
+        }
+        let x = self.iter.next()?;
+        self.pending = Some(self.charset[(x & 0x0f) as usize]);
+        Some(self.charset[(x >> 4) as usize])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let extra = usize::from(self.pending.is_some());
+        let (lo, hi) = self.iter.size_hint();
+        let lo = lo.saturating_mul(2).saturating_add(extra);
+        let hi = hi.and_then(|hi| hi.checked_mul(2)?.checked_add(extra));
+        (lo, hi)
+    }
+}
+
+/// An iterator that decodes hex characters to bytes case-insensitively.
+///
+/// It consumes two characters for each output byte.
+/// An invalid character or an odd number of characters is reported as an `Err` item,
+/// after which the iterator is exhausted.
+#[derive(Debug, Clone)]
+pub struct DecodeIter<I> {
+    iter: I,
+    done: bool,
+}
+
+impl<I: Iterator<Item = u8>> DecodeIter<I> {
+    /// Creates an iterator that decodes hex characters from `iter`.
+    #[inline]
+    #[must_use]
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            iter: iter.into_iter(),
+            done: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for DecodeIter<I> {
+    type Item = Result<u8, Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let hi = self.iter.next();
+        self.done = hi.is_none();
+        let hi = hi?;
+        let ans = match self.iter.next() {
+            Some(lo) => {
+                let (hi, lo) = (unhex(hi), unhex(lo));
+                if (hi | lo) == 0xff {
+                    Err(Error::new())
+                } else {
+                    Ok((hi << 4) | lo)
+                }
+            }
+            None => Err(Error::new()),
+        };
+        self.done = ans.is_err();
+        Some(ans)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        // decoding stops at the first error
+        let (lo, hi) = self.iter.size_hint();
+        (usize::from(lo > 0), hi.map(|hi| hi / 2 + hi % 2))
+    }
+}
@@ -47,12 +47,33 @@ extern crate alloc;
 
 #[macro_use]
 mod error;
-pub use self::error::Error;
+pub use self::error::{Error, ErrorKind};
 
 mod check;
 mod decode;
 mod encode;
 
+mod iter;
+pub use self::iter::{DecodeIter, EncodeIter};
+
+mod display;
+pub use self::display::{wrap, Hex};
+
+mod digest;
+pub use self::digest::{encode_digest, HexDigest};
+
+#[cfg(feature = "alloc")]
+mod diff;
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+pub use self::diff::{diff_to_string, DiffFormat};
+
+#[cfg(feature = "std")]
+mod io;
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use self::io::{DecoderReader, EncoderWriter};
+
 mod multiversion;
 
 #[cfg(feature = "alloc")]
@@ -63,10 +84,16 @@ pub use vsimd::ascii::AsciiCase;
 
 // -------------------------------------------------------------------------------------------------
 
+use vsimd::hex::unhex;
 use vsimd::tools::{slice_mut, slice_parts};
 
 #[cfg(feature = "alloc")]
-use alloc::{string::String, vec::Vec};
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+/// The lookup table used by the decoder.
+///
+/// Maps each byte to its nibble value (`0..16`) case-insensitively, or `0xff` if it is not a hex character.
+pub const HEX_DECODE_TABLE: [u8; 256] = *vsimd::hex::UNHEX_TABLE;
 
 /// Calculates the encoded length.
 ///
@@ -85,7 +112,7 @@ pub const fn encoded_length(n: usize) -> usize {
 /// This function returns `Err` if `n` is not even.
 #[inline]
 pub fn decoded_length(n: usize) -> Result<usize, Error> {
-    ensure!(n % 2 == 0);
+    ensure!(n % 2 == 0, OddLength);
     Ok(n / 2)
 }
 
@@ -130,7 +157,7 @@ pub unsafe fn encode_raw(src: *const u8, len: usize, dst: *mut u8, case: AsciiCa
 ///   In other words, the function supports either "copy mode" or "inplace mode".
 #[inline]
 pub unsafe fn decode_raw(src: *const u8, len: usize, dst: *mut u8) -> Result<usize, Error> {
-    ensure!(len % 2 == 0);
+    ensure!(len % 2 == 0, OddLength);
     crate::multiversion::decode::auto(src, len, dst)?;
     Ok(len / 2)
 }
@@ -147,6 +174,81 @@ pub fn check(data: &[u8]) -> Result<(), Error> {
     unsafe { crate::multiversion::check::auto(src, len) }
 }
 
+/// Checks whether `data` is a hex string of the expected length and case.
+///
+/// + `expected_len`: if it is `Some(n)`, `data` must have exactly `n` bytes.
+/// + `case`: if it is `Some(case)`, all hex letters in `data` must be in `case`.
+///
+/// The length is compared first. The bytes are then checked in a single SIMD pass,
+/// which verifies the characters and their case together.
+/// Only a failing input is scanned again to find the offending byte.
+///
+/// # Errors
+/// This function returns `Err` with
+/// + [`ErrorKind::InvalidLength`] if the length of `data` is not `expected_len`.
+/// + [`ErrorKind::InvalidCase`] if the first offending byte is a hex letter in the other case.
+/// + [`ErrorKind::InvalidData`] if the first offending byte is not a hex character.
+///
+/// The errors about the bytes report the index of the first offending byte.
+///
+/// # Examples
+///
+/// ```
+/// use hex_simd::{AsciiCase, ErrorKind};
+///
+/// let digest = b"d41d8cd98f00b204e9800998ecf8427e";
+/// assert!(hex_simd::check_strict(digest, Some(32), Some(AsciiCase::Lower)).is_ok());
+///
+/// let err = hex_simd::check_strict(digest, Some(40), None).unwrap_err();
+/// assert_eq!(err.kind(), ErrorKind::InvalidLength);
+///
+/// let err = hex_simd::check_strict(digest, Some(32), Some(AsciiCase::Upper)).unwrap_err();
+/// assert_eq!((err.kind(), err.index()), (ErrorKind::InvalidCase, Some(0)));
+/// ```
+#[inline]
+pub fn check_strict(data: &[u8], expected_len: Option<usize>, case: Option<AsciiCase>) -> Result<(), Error> {
+    if let Some(n) = expected_len {
+        ensure!(data.len() == n, InvalidLength);
+    }
+
+    let case = match case {
+        Some(case) => case,
+        None => {
+            return match find_first_non_hex(data) {
+                Some(pos) => Err(Error::at(pos)),
+                None => Ok(()),
+            }
+        }
+    };
+
+    let (src, len) = slice_parts(data);
+    if unsafe { crate::multiversion::check_case::auto(src, len, case) } {
+        return Ok(());
+    }
+
+    let pos = match data.iter().position(|&x| !check::is_hex_in_case(x, case)) {
+        Some(pos) => pos,
+        None => unreachable!(),
+    };
+    let kind = if unhex(data[pos]) == 0xff {
+        ErrorKind::InvalidData
+    } else {
+        ErrorKind::InvalidCase
+    };
+    Err(Error::with_kind_at(kind, pos))
+}
+
+/// Finds the index of the first byte in `data` which is not a hex character.
+///
+/// Returns `None` if all bytes in `data` are hex characters.
+#[inline]
+#[must_use]
+pub fn find_first_non_hex(data: &[u8]) -> Option<usize> {
+    let (src, len) = slice_parts(data);
+    let pos = unsafe { crate::multiversion::find_non_hex::auto(src, len) };
+    (pos < len).then_some(pos)
+}
+
 /// Encodes bytes to a hex string.
 ///
 /// `case` specifies the ascii case of output.
@@ -164,15 +266,32 @@ pub fn encode<'d>(src: &[u8], mut dst: Out<'d, [u8]>, case: AsciiCase) -> Result
     }
 }
 
+/// Encodes bytes to a hex string in a byte slice and returns the number of bytes written.
+///
+/// `case` specifies the ascii case of output.
+///
+/// # Errors
+/// This function returns `Err` if the length of `dst` is not enough.
+#[inline]
+pub fn encode_slice(src: &[u8], dst: &mut [u8], case: AsciiCase) -> Result<usize, Error> {
+    let ans = encode(src, dst.as_out(), case)?;
+    Ok(ans.len())
+}
+
 /// Decodes a hex string to bytes case-insensitively.
 ///
+/// The length of `src` is checked before any work,
+/// so an odd length is reported as [`ErrorKind::OddLength`] instead of invalid data.
+///
 /// # Errors
 /// This function returns `Err` if
+/// + the length of `src` is odd.
 /// + the length of `dst` is not enough.
 /// + the content of `src` is invalid.
 #[inline]
 pub fn decode<'d>(src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
-    ensure!(src.len() % 2 == 0 && dst.len() >= src.len() / 2);
+    ensure!(src.len() % 2 == 0, OddLength);
+    ensure!(dst.len() >= src.len() / 2);
 
     let len = src.len();
     let dst = dst.as_mut_ptr();
@@ -183,13 +302,28 @@ pub fn decode<'d>(src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Er
     }
 }
 
+/// Decodes a hex string to bytes case-insensitively in a byte slice and returns the number of bytes written.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + the length of `src` is odd.
+/// + the length of `dst` is not enough.
+/// + the content of `src` is invalid.
+#[inline]
+pub fn decode_slice(src: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
+    let ans = decode(src, dst.as_out())?;
+    Ok(ans.len())
+}
+
 /// Decodes a hex string to bytes case-insensitively and writes inplace.
 ///
 /// # Errors
-/// This function returns `Err` if the content of `data` is invalid.
+/// This function returns `Err` if
+/// + the length of `data` is odd.
+/// + the content of `data` is invalid.
 #[inline]
 pub fn decode_inplace(data: &mut [u8]) -> Result<&mut [u8], Error> {
-    ensure!(data.len() % 2 == 0);
+    ensure!(data.len() % 2 == 0, OddLength);
     unsafe {
         let len = data.len();
         let dst: *mut u8 = data.as_mut_ptr();
@@ -199,6 +333,133 @@ pub fn decode_inplace(data: &mut [u8]) -> Result<&mut [u8], Error> {
     }
 }
 
+/// Decodes a canonical content-addressing key of `N` bytes.
+///
+/// Content-addressed stores name objects by the lowercase hex of their digests,
+/// so each key has exactly one accepted form:
+/// + the length of `src` must be exactly `2N`.
+/// + every character must be a lowercase hex character. Uppercase `A-F` is rejected.
+///
+/// # Examples
+///
+/// ```
+/// let key: [u8; 4] = hex_simd::decode_canonical_key(b"deadbeef").unwrap();
+/// assert_eq!(key, [0xde, 0xad, 0xbe, 0xef]);
+///
+/// let err = hex_simd::decode_canonical_key::<4>(b"deadBEEF").unwrap_err();
+/// assert_eq!(err.index(), Some(4));
+/// ```
+///
+/// # Errors
+/// This function returns `Err` if
+/// + the length of `src` is not `2N`. The error has no index.
+/// + the content of `src` is not canonical. [`Error::index`] returns the index of the first offending character.
+#[inline]
+pub fn decode_canonical_key<const N: usize>(src: &[u8]) -> Result<[u8; N], Error> {
+    ensure!(src.len() % 2 == 0, OddLength);
+    ensure!(src.len() / 2 == N);
+
+    let mut key = [0u8; N];
+    let is_canonical = decode(src, key.as_mut_slice().as_out()).is_ok() && !src.iter().any(u8::is_ascii_uppercase);
+    if is_canonical {
+        return Ok(key);
+    }
+
+    // The scan for the offending character only runs on the error path.
+    let pos = src.iter().position(|&x| !matches!(x, b'0'..=b'9' | b'a'..=b'f'));
+    debug_assert!(pos.is_some());
+    Err(Error::at(pos.unwrap_or(0)))
+}
+
+/// Checks that an array of `M` characters holds the hex string of an array of `N` bytes.
+struct ArrayLength<const N: usize, const M: usize>;
+
+impl<const N: usize, const M: usize> ArrayLength<N, M> {
+    const ASSERT: () = assert!(
+        N <= usize::MAX / 2 && M == N * 2,
+        "the output length must be twice the input length"
+    );
+}
+
+/// Encodes an array of `N` bytes to a hex string of `M = 2N` characters.
+///
+/// The lengths are checked at compile time, so this function can not fail.
+/// It is convenient for fixed-size values such as addresses, keys and digests.
+///
+/// `case` specifies the ascii case of output.
+///
+/// # Examples
+///
+/// ```
+/// use hex_simd::AsciiCase;
+///
+/// let hex: [u8; 8] = hex_simd::encode_array(&[0xde, 0xad, 0xbe, 0xef], AsciiCase::Lower);
+/// assert_eq!(&hex, b"deadbeef");
+/// ```
+///
+/// A mismatched output length does not compile:
+///
+/// ```compile_fail
+/// use hex_simd::AsciiCase;
+///
+/// let hex: [u8; 7] = hex_simd::encode_array(&[0xde, 0xad, 0xbe, 0xef], AsciiCase::Lower);
+/// ```
+#[inline]
+#[must_use]
+pub fn encode_array<const N: usize, const M: usize>(src: &[u8; N], case: AsciiCase) -> [u8; M] {
+    let () = ArrayLength::<N, M>::ASSERT;
+
+    let mut ans = [0u8; M];
+    unsafe {
+        let (src, len) = slice_parts(src);
+        crate::multiversion::encode::auto(src, len, ans.as_mut_ptr(), case);
+    }
+    ans
+}
+
+/// Decodes a hex string of `M = 2N` characters to an array of `N` bytes case-insensitively.
+///
+/// The lengths are checked at compile time, so only the content of `src` can be invalid.
+///
+/// # Examples
+///
+/// ```
+/// let bytes: [u8; 4] = hex_simd::decode_array(b"DEADbeef").unwrap();
+/// assert_eq!(bytes, [0xde, 0xad, 0xbe, 0xef]);
+///
+/// assert!(hex_simd::decode_array::<4, 8>(b"deadbeeg").is_err());
+/// ```
+///
+/// # Errors
+/// This function returns `Err` if the content of `src` is invalid.
+#[inline]
+pub fn decode_array<const N: usize, const M: usize>(src: &[u8; M]) -> Result<[u8; N], Error> {
+    let () = ArrayLength::<N, M>::ASSERT;
+
+    let mut ans = [0u8; N];
+    unsafe { crate::multiversion::decode::auto(src.as_ptr(), M, ans.as_mut_ptr())? };
+    Ok(ans)
+}
+
+/// Decodes each hex character to its nibble value case-insensitively.
+///
+/// Every output byte is in the range `0..16`. The output length equals the input length.
+///
+/// # Errors
+/// This function returns `Err` if
+/// + the length of `dst` is not enough.
+/// + the content of `src` is invalid.
+#[inline]
+pub fn decode_nibbles<'d>(src: &[u8], mut dst: Out<'d, [u8]>) -> Result<&'d mut [u8], Error> {
+    ensure!(dst.len() >= src.len());
+    unsafe {
+        let (src, len) = slice_parts(src);
+        let dst = dst.as_mut_ptr();
+        crate::multiversion::decode_nibbles::auto(src, len, dst)?;
+        Ok(slice_mut(dst, len))
+    }
+}
+
 /// Encodes bytes to a hex string and returns [`&mut str`](str).
 ///
 /// `case` specifies the ascii case of output.
@@ -291,3 +552,89 @@ pub fn encode_to_string(data: impl AsRef<[u8]>, case: AsciiCase) -> String {
 pub fn decode_to_vec(data: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
     decode_type(data)
 }
+
+/// Decodes a hex string as a big-endian unsigned integer and returns its `u64` limbs.
+///
+/// The limbs are in little-endian order: the first limb holds the least significant 64 bits.
+///
+/// + The string is zero-padded on the left to a multiple of 16 characters,
+///   so a leading odd nibble becomes the high nibble of the integer.
+/// + The result has `src.len().div_ceil(16)` limbs. Leading zeros are kept, and an empty string has no limbs.
+///
+/// The decoding is case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// let limbs = hex_simd::decode_to_u64_le(b"123456789abcdef0fedcba9").unwrap();
+/// assert_eq!(limbs, [0x89ab_cdef_0fed_cba9, 0x0123_4567]);
+///
+/// let limbs = hex_simd::decode_to_u64_le(b"00000000000000000000000000000001").unwrap();
+/// assert_eq!(limbs, [1, 0]);
+/// ```
+///
+/// # Errors
+/// This function returns `Err` if the content of `src` is invalid.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn decode_to_u64_le(src: &[u8]) -> Result<Vec<u64>, Error> {
+    let limbs = (src.len() + 15) / 16;
+    let mut buf = alloc::vec![0u8; limbs * 8];
+
+    let (head, body) = src.split_at(src.len() % 2);
+    let start = buf.len() - body.len() / 2 - head.len();
+    if !head.is_empty() {
+        decode_nibbles(head, buf[start..=start].as_out())?;
+    }
+    decode(body, buf[start + head.len()..].as_out())?;
+
+    let limb = |c: &[u8]| c.iter().fold(0, |acc, &x| acc << 8 | u64::from(x));
+    Ok(buf.chunks_exact(8).rev().map(limb).collect())
+}
+
+/// Decodes a `\x`-escaped byte string, such as `\xde\xad\xbe\xef`, case-insensitively.
+///
+/// Every byte must be written as `\x` followed by exactly two hex characters.
+/// The escapes are removed by a scalar pass, and the hex characters are decoded by SIMD instructions.
+///
+/// # Examples
+///
+/// ```
+/// let bytes = hex_simd::decode_x_escaped(br"\xde\xad\xBE\xEF").unwrap();
+/// assert_eq!(&*bytes, [0xde, 0xad, 0xbe, 0xef]);
+///
+/// let err = hex_simd::decode_x_escaped(br"\xde\xag").unwrap_err();
+/// assert_eq!(err.index(), Some(7));
+/// ```
+///
+/// # Errors
+/// This function returns `Err` if the content of `src` is invalid.
+/// [`Error::index`] returns the index of the first offending character:
+/// + a character which should be `\` or `x`.
+/// + a character which should be a hex character.
+/// + the `\` of an escape which is cut off by the end of `src`.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn decode_x_escaped(src: &[u8]) -> Result<Box<[u8]>, Error> {
+    let mut nibbles = Vec::with_capacity(src.len() / 2);
+    for (i, escape) in src.chunks(4).enumerate() {
+        let pos = i * 4;
+        if escape.len() < 4 || escape[0] != b'\\' {
+            return Err(Error::at(pos));
+        }
+        if escape[1] != b'x' {
+            return Err(Error::at(pos + 1));
+        }
+        nibbles.extend_from_slice(&escape[2..]);
+    }
+
+    let mut buf = alloc::vec![0u8; nibbles.len() / 2];
+    if decode(&nibbles, buf.as_mut_slice().as_out()).is_err() {
+        // The scan for the offending character only runs on the error path.
+        let k = find_first_non_hex(&nibbles).unwrap_or(0);
+        return Err(Error::at(k / 2 * 4 + 2 + k % 2));
+    }
+    Ok(buf.into_boxed_slice())
+}
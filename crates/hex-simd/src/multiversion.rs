@@ -11,6 +11,24 @@ vsimd::dispatch!(
     fastest     = {"avx2", "neon", "simd128"},
 );
 
+vsimd::dispatch!(
+    name        = {check_case},
+    signature   = {pub unsafe fn(src: *const u8, len: usize, case: AsciiCase) -> bool},
+    fallback    = {crate::check::check_case_fallback},
+    simd        = {crate::check::check_case_simd},
+    targets     = {"avx2", "sse2", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+);
+
+vsimd::dispatch!(
+    name        = {find_non_hex},
+    signature   = {pub unsafe fn(src: *const u8, len: usize) -> usize},
+    fallback    = {crate::check::find_non_hex_fallback},
+    simd        = {crate::check::find_non_hex_simd},
+    targets     = {"avx2", "sse2", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+);
+
 vsimd::dispatch!(
     name        = {encode},
     signature   = {pub unsafe fn(src: *const u8, len: usize, dst: *mut u8, case: AsciiCase) -> () },
@@ -28,3 +46,12 @@ vsimd::dispatch!(
     targets     = {"avx2", "ssse3", "sse2", "neon", "simd128"},
     fastest     = {"avx2", "neon", "simd128"},
 );
+
+vsimd::dispatch!(
+    name        = {decode_nibbles},
+    signature   = {pub unsafe fn(src: *const u8, len: usize, dst: *mut u8) -> Result<(), Error>},
+    fallback    = {crate::decode::decode_nibbles_fallback},
+    simd        = {crate::decode::decode_nibbles_simd},
+    targets     = {"avx2", "ssse3", "neon", "simd128"},
+    fastest     = {"avx2", "neon", "simd128"},
+);
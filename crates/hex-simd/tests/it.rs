@@ -154,3 +154,514 @@ fn random() {
         test_encode_decode_inplace!(src, AsciiCase::Upper);
     }
 }
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_nibbles() {
+    for n in 0..128 {
+        let bytes = rand_bytes(n);
+        let case = if n % 2 == 0 { AsciiCase::Lower } else { AsciiCase::Upper };
+
+        let mut encoded = vec![0u8; n * 2];
+        let encoded = hex_simd::encode(&bytes, encoded.as_out(), case).unwrap();
+
+        let mut nibbles = vec![0u8; n * 2];
+        let nibbles = hex_simd::decode_nibbles(encoded, nibbles.as_out()).unwrap();
+
+        let expected: Vec<u8> = bytes.iter().flat_map(|&b| [b >> 4, b & 0x0f]).collect();
+        assert_eq!(nibbles, expected);
+
+        if n > 0 {
+            let mut invalid = encoded.to_vec();
+            invalid[n] = b'g';
+            let mut buf = vec![0u8; n * 2];
+            assert!(hex_simd::decode_nibbles(&invalid, buf.as_out()).is_err());
+        }
+    }
+
+    let mut buf = [0u8; 3];
+    let ans = hex_simd::decode_nibbles(b"0aF", buf.as_mut_slice().as_out()).unwrap();
+    assert_eq!(ans, &[0, 10, 15]);
+    assert!(hex_simd::decode_nibbles(b"0aF1", buf.as_mut_slice().as_out()).is_err());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn iter() {
+    use hex_simd::{DecodeIter, EncodeIter};
+
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for case in [AsciiCase::Lower, AsciiCase::Upper] {
+            let mut expected = vec![0u8; n * 2];
+            let expected = hex_simd::encode(&bytes, expected.as_out(), case).unwrap();
+
+            let iter = EncodeIter::new(bytes.iter().copied(), case);
+            assert_eq!(iter.size_hint(), (n * 2, Some(n * 2)));
+            let encoded: Vec<u8> = iter.collect();
+            assert_eq!(encoded, expected);
+
+            let decoded: Result<Vec<u8>, _> = DecodeIter::new(encoded.iter().copied()).collect();
+            assert_eq!(decoded.unwrap(), bytes);
+        }
+    }
+
+    let mut iter = DecodeIter::new(b"0aFg".iter().copied());
+    assert_eq!(iter.next().unwrap().unwrap(), 0x0a);
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+
+    let mut iter = DecodeIter::new(b"0aF".iter().copied());
+    assert_eq!(iter.next().unwrap().unwrap(), 0x0a);
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_table() {
+    let table = &hex_simd::HEX_DECODE_TABLE;
+    for c in 0..=255u8 {
+        let expected = match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            b'A'..=b'F' => c - b'A' + 10,
+            _ => 0xff,
+        };
+        assert_eq!(table[c as usize], expected);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn find_first_non_hex() {
+    for n in [0, 1, 15, 16, 17, 31, 32, 33, 47, 48, 63, 64, 65, 100] {
+        let encoded = hex_simd::encode_to_string(rand_bytes(n), AsciiCase::Lower).into_bytes();
+        assert_eq!(hex_simd::find_first_non_hex(&encoded), None);
+
+        for i in 0..encoded.len() {
+            let mut invalid = encoded.clone();
+            invalid[i] = b'g';
+            assert_eq!(hex_simd::find_first_non_hex(&invalid), Some(i));
+
+            if i + 3 < invalid.len() {
+                invalid[i + 3] = b'\0';
+                assert_eq!(hex_simd::find_first_non_hex(&invalid), Some(i));
+            }
+        }
+    }
+
+    assert_eq!(hex_simd::find_first_non_hex(b"0123456789abcdefABCDEF"), None);
+    assert_eq!(hex_simd::find_first_non_hex(b"0x12"), Some(1));
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn check_strict() {
+    use hex_simd::ErrorKind;
+
+    let check_strict = hex_simd::check_strict;
+    let kind_at = |e: hex_simd::Error| (e.kind(), e.index());
+
+    for n in [0, 1, 7, 8, 9, 15, 16, 17, 31, 32, 33, 50] {
+        for case in [AsciiCase::Lower, AsciiCase::Upper] {
+            let encoded = hex_simd::encode_to_string(rand_bytes(n), case).into_bytes();
+            let len = encoded.len();
+            assert!(check_strict(&encoded, Some(len), Some(case)).is_ok());
+            assert!(check_strict(&encoded, None, None).is_ok());
+
+            let err = check_strict(&encoded, Some(len + 1), Some(case)).unwrap_err();
+            assert_eq!(kind_at(err), (ErrorKind::InvalidLength, None));
+
+            for i in 0..len {
+                let mut invalid = encoded.clone();
+                invalid[i] = if case == AsciiCase::Lower { b'B' } else { b'b' };
+                let err = check_strict(&invalid, Some(len), Some(case)).unwrap_err();
+                assert_eq!(kind_at(err), (ErrorKind::InvalidCase, Some(i)));
+                assert!(check_strict(&invalid, Some(len), None).is_ok());
+
+                invalid[i] = b'g';
+                let err = check_strict(&invalid, Some(len), Some(case)).unwrap_err();
+                assert_eq!(kind_at(err), (ErrorKind::InvalidData, Some(i)));
+                let err = check_strict(&invalid, None, None).unwrap_err();
+                assert_eq!(kind_at(err), (ErrorKind::InvalidData, Some(i)));
+            }
+        }
+    }
+
+    let err = check_strict(b"0123456789abcdefABCDEF/:@G`g", None, Some(AsciiCase::Lower)).unwrap_err();
+    assert_eq!(kind_at(err), (ErrorKind::InvalidCase, Some(16)));
+    for x in [b'/', b':', b'@', b'G', b'`', b'g', 0x80, 0xc1, 0xe1, 0xff] {
+        for case in [AsciiCase::Lower, AsciiCase::Upper] {
+            let src = [b'0'; 40].iter().copied().chain([x]).collect::<Vec<u8>>();
+            let err = check_strict(&src, None, Some(case)).unwrap_err();
+            assert_eq!(kind_at(err), (ErrorKind::InvalidData, Some(40)));
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn odd_length() {
+    use hex_simd::ErrorKind;
+
+    let mut buf = [0u8; 4];
+
+    for src in [&b"a"[..], b"abc", b"g", b"xyz"] {
+        let err = hex_simd::decode(src, buf.as_mut_slice().as_out()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::OddLength);
+
+        let mut data = src.to_vec();
+        let err = hex_simd::decode_inplace(&mut data).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::OddLength);
+    }
+
+    assert_eq!(hex_simd::decoded_length(3).unwrap_err().kind(), ErrorKind::OddLength);
+
+    let err = hex_simd::decode(b"xy", buf.as_mut_slice().as_out()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    let err = hex_simd::decode(b"abcdef0123", buf.as_mut_slice().as_out()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn slice() {
+    for n in 0..64 {
+        let bytes = rand_bytes(n);
+        for case in [AsciiCase::Lower, AsciiCase::Upper] {
+            let mut encoded = vec![0u8; n * 2 + 1];
+            assert_eq!(hex_simd::encode_slice(&bytes, &mut encoded, case).unwrap(), n * 2);
+            encoded.truncate(n * 2);
+
+            let mut decoded = vec![0u8; n];
+            assert_eq!(hex_simd::decode_slice(&encoded, &mut decoded).unwrap(), n);
+            assert_eq!(decoded, bytes);
+
+            if n > 0 {
+                let mut buf = vec![0u8; n * 2 - 1];
+                assert!(hex_simd::encode_slice(&bytes, &mut buf, case).is_err());
+
+                let mut buf = vec![0u8; n - 1];
+                assert!(hex_simd::decode_slice(&encoded, &mut buf).is_err());
+            }
+        }
+    }
+
+    let mut buf = [0u8; 4];
+    assert!(hex_simd::decode_slice(b"0g", &mut buf).is_err());
+    assert!(hex_simd::decode_slice(b"012", &mut buf).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn format_hex() {
+    for n in [0, 1, 16, 255, 256, 257, 1000] {
+        let bytes = rand_bytes(n);
+        let lower = hex_simd::encode_to_string(&bytes, AsciiCase::Lower);
+        let upper = hex_simd::encode_to_string(&bytes, AsciiCase::Upper);
+
+        assert_eq!(format!("{:x}", hex_simd::wrap(&bytes)), lower);
+        assert_eq!(format!("{:X}", hex_simd::wrap(&bytes)), upper);
+        assert_eq!(format!("{:#x}", hex_simd::wrap(&bytes)), format!("0x{lower}"));
+
+        let width = n * 2 + 3;
+        assert_eq!(
+            format!("{:width$x}", hex_simd::wrap(&bytes)),
+            format!("{lower:>width$}")
+        );
+        assert_eq!(
+            format!("{:-^width$X}", hex_simd::wrap(&bytes)),
+            format!("{upper:-^width$}")
+        );
+        assert_eq!(format!("{:0width$x}", hex_simd::wrap(&bytes)), format!("000{lower}"));
+        assert_eq!(format!("{:w$x}", hex_simd::wrap(&bytes), w = n * 2), lower);
+    }
+
+    let x: u32 = 0x00c0_ffee;
+    let bytes = x.to_be_bytes();
+    assert_eq!(format!("{:#012x}", hex_simd::wrap(&bytes)), format!("{x:#012x}"));
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn streaming() {
+    use std::io::{Read, Write};
+
+    /// A reader which returns at most `step` bytes per read.
+    struct Trickle<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.step.min(buf.len()).min(self.data.len());
+            buf[..n].copy_from_slice(&self.data[..n]);
+            self.data = &self.data[n..];
+            Ok(n)
+        }
+    }
+
+    for n in [0, 1, 2, 3, 100, 511, 512, 513, 3000] {
+        let bytes = rand_bytes(n);
+        for case in [AsciiCase::Lower, AsciiCase::Upper] {
+            let encoded = hex_simd::encode_type::<Vec<u8>>(&bytes, case);
+
+            let mut writer = hex_simd::EncoderWriter::new(Vec::new(), case);
+            for chunk in bytes.chunks(7) {
+                writer.write_all(chunk).unwrap();
+            }
+            writer.flush().unwrap();
+            assert_eq!(writer.into_inner(), encoded);
+
+            for step in [1, 3, 4, 1000, 5000] {
+                let mut reader = hex_simd::DecoderReader::new(Trickle { data: &encoded, step });
+                let mut decoded = Vec::new();
+                let mut buf = [0u8; 5];
+                loop {
+                    match reader.read(&mut buf).unwrap() {
+                        0 => break,
+                        k => decoded.extend_from_slice(&buf[..k]),
+                    }
+                }
+                assert_eq!(decoded, bytes);
+            }
+        }
+    }
+
+    let mut buf = Vec::new();
+    let err = hex_simd::DecoderReader::new(&b"48656c6c6"[..])
+        .read_to_end(&mut buf)
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(buf, b"Hell");
+
+    let err = hex_simd::DecoderReader::new(&b"4865zz6c"[..])
+        .read_to_end(&mut Vec::new())
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn reference() {
+    use vsimd::reference::{decode, encode};
+
+    for n in 0..256 {
+        let bytes = rand_bytes(n);
+        for (case, charset) in [
+            (AsciiCase::Lower, vsimd::hex::LOWER_CHARSET),
+            (AsciiCase::Upper, vsimd::hex::UPPER_CHARSET),
+        ] {
+            let encoded = encode(&bytes, charset, None);
+            assert_eq!(hex_simd::encode_type::<Vec<u8>>(&bytes, case), encoded);
+            assert_eq!(hex_simd::decode_to_vec(&encoded).unwrap(), bytes);
+
+            if encoded.is_empty() {
+                continue;
+            }
+            let pos = rand::random::<usize>() % encoded.len();
+            for x in [b'0', b'f', b'F', b'g', b'G', b' ', 0x80] {
+                let mut corrupted = encoded.clone();
+                corrupted[pos] = x;
+                // hex decoding is case-insensitive
+                let expected = decode(&corrupted.to_ascii_lowercase(), vsimd::hex::LOWER_CHARSET, None);
+                assert_eq!(hex_simd::decode_to_vec(&corrupted).ok(), expected);
+            }
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn canonical_key() {
+    use hex_simd::ErrorKind;
+
+    let mut bytes = [0u8; 32];
+    bytes.iter_mut().enumerate().for_each(|(i, x)| *x = (i * 37) as u8);
+
+    let mut buf = [0u8; 64];
+    let lower = hex_simd::encode(&bytes, buf.as_mut_slice().as_out(), AsciiCase::Lower).unwrap();
+    let key: [u8; 32] = hex_simd::decode_canonical_key(lower).unwrap();
+    assert_eq!(key, bytes);
+
+    for i in 0..lower.len() {
+        let mut src = lower.to_vec();
+
+        if src[i].is_ascii_alphabetic() {
+            src[i].make_ascii_uppercase();
+            let err = hex_simd::decode_canonical_key::<32>(&src).unwrap_err();
+            assert_eq!(err.index(), Some(i));
+        }
+
+        src[i] = b'g';
+        let err = hex_simd::decode_canonical_key::<32>(&src).unwrap_err();
+        assert_eq!(err.index(), Some(i));
+    }
+
+    let err = hex_simd::decode_canonical_key::<32>(&lower[..62]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(err.index(), None);
+
+    let err = hex_simd::decode_canonical_key::<32>(&lower[..63]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::OddLength);
+    assert_eq!(err.index(), None);
+
+    let key: [u8; 0] = hex_simd::decode_canonical_key(b"").unwrap();
+    assert_eq!(key, []);
+
+    assert!(hex_simd::decode("zz".as_bytes(), buf.as_mut_slice().as_out())
+        .unwrap_err()
+        .index()
+        .is_none());
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn array() {
+    let mut bytes = [0u8; 16];
+    bytes.iter_mut().enumerate().for_each(|(i, x)| *x = (i * 37) as u8);
+
+    for case in [AsciiCase::Lower, AsciiCase::Upper] {
+        let mut buf = [0u8; 32];
+        let expected = hex_simd::encode(&bytes, buf.as_mut_slice().as_out(), case).unwrap();
+
+        let hex: [u8; 32] = hex_simd::encode_array(&bytes, case);
+        assert_eq!(hex, *expected);
+
+        let decoded: [u8; 16] = hex_simd::decode_array(&hex).unwrap();
+        assert_eq!(decoded, bytes);
+
+        for i in 0..hex.len() {
+            let mut src = hex;
+            src[i] = b'g';
+            assert!(hex_simd::decode_array::<16, 32>(&src).is_err());
+        }
+    }
+
+    let hex: [u8; 0] = hex_simd::encode_array(&[], AsciiCase::Lower);
+    assert_eq!(hex_simd::decode_array::<0, 0>(&hex).unwrap(), []);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn digest() {
+    use hex_simd::HexDigest;
+
+    let mut bytes = [0u8; 32];
+    bytes.iter_mut().enumerate().for_each(|(i, x)| *x = (i * 37) as u8);
+
+    for case in [AsciiCase::Lower, AsciiCase::Upper] {
+        let hex: HexDigest<64> = hex_simd::encode_digest(&bytes, case);
+        let expected: [u8; 64] = hex_simd::encode_array(&bytes, case);
+        assert_eq!(hex.as_bytes(), &expected);
+        assert_eq!(hex.as_str().as_bytes(), expected);
+    }
+
+    let hex: HexDigest<4> = hex_simd::encode_digest(&[0xab, 0x01], AsciiCase::Upper);
+    assert_eq!(&*hex, "AB01");
+
+    let hex: HexDigest<0> = hex_simd::encode_digest(&[], AsciiCase::Lower);
+    assert!(hex.is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn diff() {
+    use hex_simd::DiffFormat;
+
+    assert_eq!(hex_simd::diff_to_string(b"", b""), "");
+
+    let data = rand_bytes(100);
+    let diff = hex_simd::diff_to_string(&data, &data);
+    assert_eq!(diff.lines().count(), 7);
+    assert!(diff.lines().all(|line| line.starts_with("  ")));
+
+    let format = DiffFormat::new().bytes_per_line(2);
+    let expected = concat!(
+        "  00000000  0102  0102\n",
+        "! 00000002  0304  03ff\n",
+        "              ^^    ^^\n",
+        "! 00000004        05\n",
+        "            ^^    ^^\n",
+    );
+    assert_eq!(format.diff_to_string(&[1, 2, 3, 4], &[1, 2, 3, 0xff, 5]), expected);
+
+    let expected = concat!(
+        "  00000000  0102  0102\n",
+        "! 00000002  0304  03ff\n",
+        "! 00000004        05\n",
+    );
+    let format = format.markers(false);
+    assert_eq!(format.diff_to_string(&[1, 2, 3, 4], &[1, 2, 3, 0xff, 5]), expected);
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_to_u64_le() {
+    assert!(hex_simd::decode_to_u64_le(b"").unwrap().is_empty());
+    assert_eq!(hex_simd::decode_to_u64_le(b"f").unwrap(), [0xf]);
+    assert_eq!(hex_simd::decode_to_u64_le(b"FFFFFFFFFFFFFFFF").unwrap(), [u64::MAX]);
+    assert_eq!(hex_simd::decode_to_u64_le(b"1FFFFFFFFFFFFFFFF").unwrap(), [u64::MAX, 1]);
+
+    for n in 0..80 {
+        let src: String = (0..n)
+            .map(|_| b"0123456789abcdefABCDEF"[rand::random::<usize>() % 22] as char)
+            .collect();
+        let limbs = hex_simd::decode_to_u64_le(src.as_bytes()).unwrap();
+        assert_eq!(limbs.len(), (n + 15) / 16);
+        for (i, limb) in limbs.iter().enumerate() {
+            let end = n - (i * 16).min(n);
+            let start = end.saturating_sub(16);
+            assert_eq!(*limb, u64::from_str_radix(&src[start..end], 16).unwrap());
+        }
+    }
+
+    assert!(hex_simd::decode_to_u64_le(b"g").is_err());
+    assert!(hex_simd::decode_to_u64_le(b"12345678901234567g").is_err());
+    assert!(hex_simd::decode_to_u64_le(b"x2345678901234567").is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn decode_x_escaped() {
+    assert!(hex_simd::decode_x_escaped(b"").unwrap().is_empty());
+
+    for n in 0..100 {
+        let bytes = rand_bytes(n);
+        let escaped: String = bytes.iter().map(|x| format!("\\x{x:02x}")).collect();
+        assert_eq!(&*hex_simd::decode_x_escaped(escaped.as_bytes()).unwrap(), bytes);
+        let escaped = escaped.to_ascii_uppercase().replace('X', "x");
+        assert_eq!(&*hex_simd::decode_x_escaped(escaped.as_bytes()).unwrap(), bytes);
+    }
+
+    let cases: &[(&[u8], usize)] = &[
+        (br"\x", 0),
+        (br"\x0", 0),
+        (br"\x00\", 4),
+        (br"\x00\x1", 4),
+        (br"x00", 0),
+        (br"\X00", 1),
+        (br"\x00/x00", 4),
+        (br"\x0g", 3),
+        (br"\xg0", 2),
+        (
+            br"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x0z",
+            75,
+        ),
+    ];
+    for &(src, index) in cases {
+        let err = hex_simd::decode_x_escaped(src).unwrap_err();
+        assert_eq!(err.index(), Some(index), "{}", String::from_utf8_lossy(src));
+    }
+}
@@ -64,6 +64,153 @@ impl AlswLut<V128> {
     }
 }
 
+/// The valid characters which fall into each hash bucket.
+#[derive(Clone, Copy)]
+struct Buckets {
+    max_invalid: [i16; 16],
+    min_valid: [i16; 16],
+}
+
+/// Places the characters with low nibble `lo` into the buckets `(c >> 4) + x`.
+///
+/// [`check`] accepts `c` iff `c` is not less than the smallest valid character in its bucket,
+/// so every invalid character in a bucket must be less than all the valid ones.
+const fn place(valid: &[bool; 128], mut b: Buckets, lo: usize, x: usize) -> Option<Buckets> {
+    let mut hi = 0;
+    while hi < 8 {
+        let c = (hi * 16 + lo) as i16;
+        let h = hi + x;
+        if valid[c as usize] {
+            if c < b.min_valid[h] {
+                b.min_valid[h] = c;
+            }
+        } else if c > b.max_invalid[h] {
+            b.max_invalid[h] = c;
+        }
+        if b.max_invalid[h] >= b.min_valid[h] {
+            return None;
+        }
+        hi += 1;
+    }
+    Some(b)
+}
+
+/// Searches the hash values of the unassigned low nibbles, the most constrained nibble first.
+const fn solve(valid: &[bool; 128], mut xs: [u8; 16], b: Buckets) -> Option<[u8; 16]> {
+    let mut best = 16;
+    let mut best_count = 9;
+    let mut lo = 0;
+    while lo < 16 {
+        if xs[lo] == 0 {
+            let mut count = 0;
+            let mut x = 1;
+            while x <= 8 {
+                if place(valid, b, lo, x).is_some() {
+                    count += 1;
+                }
+                x += 1;
+            }
+            if count < best_count {
+                best = lo;
+                best_count = count;
+            }
+        }
+        lo += 1;
+    }
+
+    if best == 16 {
+        return Some(xs);
+    }
+
+    // `x <= 8` keeps the hash below 0x80, so that the offset lookup never returns zero for ASCII.
+    let mut x = 1;
+    while x <= 8 {
+        if let Some(next) = place(valid, b, best, x) {
+            xs[best] = x as u8;
+            if let Some(ans) = solve(valid, xs, next) {
+                return Some(ans);
+            }
+        }
+        x += 1;
+    }
+    None
+}
+
+/// Builds the check table of a charset from its valid ASCII ranges.
+///
+/// Each range `(lo, hi)` is inclusive. The table can be used by [`check`] and [`check_ascii_xn`].
+///
+/// Returns `None` if a range is not ASCII, or if the charset can not be checked by ALSW.
+/// A charset whose valid characters alternate too often in the ASCII table may have no solution.
+///
+/// # Examples
+///
+/// ```
+/// use vsimd::alsw::{self, AlswLut};
+/// use vsimd::vector::V128;
+///
+/// const BASE32_CHECK: AlswLut<V128> = match alsw::build_check(&[(b'A', b'Z'), (b'2', b'7')]) {
+///     Some(lut) => lut,
+///     None => panic!("unsupported charset"),
+/// };
+///
+/// let (hash, offset) = (BASE32_CHECK.hash.as_bytes(), BASE32_CHECK.offset.as_bytes());
+/// assert!(alsw::check(hash, offset, b'Q') < 0x80);
+/// assert!(alsw::check(hash, offset, b'8') >= 0x80);
+/// ```
+#[inline]
+#[must_use]
+pub const fn build_check(ranges: &[(u8, u8)]) -> Option<AlswLut<V128>> {
+    let mut valid = [false; 128];
+    let mut i = 0;
+    while i < ranges.len() {
+        let (lo, hi) = ranges[i];
+        if hi >= 0x80 {
+            return None;
+        }
+        let mut c = lo;
+        while c <= hi {
+            valid[c as usize] = true;
+            c += 1;
+        }
+        i += 1;
+    }
+
+    let empty = Buckets {
+        max_invalid: [-1; 16],
+        min_valid: [0x80; 16],
+    };
+    let xs = match solve(&valid, [0; 16], empty) {
+        Some(xs) => xs,
+        None => return None,
+    };
+
+    let mut hash = [0; 16];
+    let mut i = 0;
+    while i < 16 {
+        hash[i] = (xs[i] << 1) - 1;
+        i += 1;
+    }
+
+    let mut offset = [0x80; 16];
+    let mut c: u8 = 0x7f;
+    loop {
+        if valid[c as usize] {
+            let h = self::hash(&hash, c);
+            offset[(h & 0x0f) as usize] = 0u8.wrapping_sub(c);
+        }
+        if c == 0 {
+            break;
+        }
+        c -= 1;
+    }
+
+    Some(AlswLut {
+        hash: V128::from_bytes(hash),
+        offset: V128::from_bytes(offset),
+    })
+}
+
 #[inline(always)]
 pub fn check_ascii_xn<S: Scalable<V>, V: POD>(s: S, x: V, check: AlswLut<V>) -> bool {
     let shr3 = s.u32xn_shr::<3>(x);
@@ -89,6 +236,17 @@ pub fn decode_ascii_xn<S: Scalable<V>, V: POD>(s: S, x: V, check: AlswLut<V>, de
     (c1, c2)
 }
 
+/// Decodes `x` without validation.
+///
+/// The result is unspecified for invalid characters.
+#[inline(always)]
+pub fn decode_ascii_xn_unchecked<S: Scalable<V>, V: POD>(s: S, x: V, decode: AlswLut<V>) -> V {
+    let shr3 = s.u32xn_shr::<3>(x);
+    let h2 = s.u8xn_avgr(shr3, u8x16xn_lookup(s, decode.hash, x));
+    let o2 = u8x16xn_lookup(s, decode.offset, h2);
+    s.u8xn_add(x, o2)
+}
+
 #[macro_export]
 macro_rules! impl_alsw {
     ($spec:ty) => {
@@ -0,0 +1,192 @@
+//! Radix-85 conversion between 32-bit words and 5 digits.
+//!
+//! `n = d0 * 85^4 + d1 * 85^3 + d2 * 85^2 + d3 * 85 + d4`
+
+use crate::vector::V128;
+use crate::SIMD128;
+
+/// `floor((2^32 - 1) / 85)`
+const FOLD_LIMIT: u32 = u32::MAX / 85;
+
+/// Folds 5 digits into a 32-bit word.
+///
+/// Returns `None` if the result overflows `u32`.
+/// Every digit must be less than 85.
+#[inline]
+#[must_use]
+pub const fn fold(d: [u8; 5]) -> Option<u32> {
+    let hi = (((d[0] as u32 * 85 + d[1] as u32) * 85 + d[2] as u32) * 85) + d[3] as u32;
+    if hi > FOLD_LIMIT {
+        return None;
+    }
+    (hi * 85).checked_add(d[4] as u32)
+}
+
+/// Splits a 32-bit word into 5 digits.
+#[inline]
+#[must_use]
+pub const fn unfold(mut n: u32) -> [u8; 5] {
+    let mut d = [0; 5];
+    let mut i = 5;
+    while i > 0 {
+        i -= 1;
+        // n / 85 == (n * ceil(2^38 / 85)) >> 38 for all u32
+        let q = ((n as u64 * 0xC0C0_C0C1) >> 38) as u32;
+        d[i] = (n - q * 85) as u8;
+        n = q;
+    }
+    d
+}
+
+/// Folds 5 digit vectors into 4 32-bit words.
+///
+/// Lane `i` of `d[k]` holds the `k`-th digit of the `i`-th word as `u32`.
+/// Every digit must be less than 85.
+///
+/// Returns the wrapping results and a mask whose lanes are all ones when the result overflows `u32`.
+///
+/// This function requires a 32-bit lane multiplication, which is available on SSE41, NEON and WASM128.
+/// It is unreachable with the SSE2 and SSSE3 tokens.
+#[inline(always)]
+pub fn fold_u32x4<S: SIMD128>(s: S, d: [V128; 5]) -> (V128, V128) {
+    let k = s.u32x4_splat(85);
+
+    let mut hi = d[0];
+    hi = s.u32x4_add(s.i32x4_mul_lo(hi, k), d[1]);
+    hi = s.u32x4_add(s.i32x4_mul_lo(hi, k), d[2]);
+    hi = s.u32x4_add(s.i32x4_mul_lo(hi, k), d[3]);
+    // hi <= 84 * (85^3 + 85^2 + 85 + 1), no overflow
+
+    let n = s.u32x4_add(s.i32x4_mul_lo(hi, k), d[4]);
+
+    // The result overflows iff `hi * 85 + d4 > 2^32 - 1`.
+    // If `hi < FOLD_LIMIT`, there is no overflow.
+    // Otherwise, an overflowed result wraps around to a value less than `(FOLD_LIMIT - 1) * 85`.
+    let m1 = s.u32x4_lt(s.u32x4_splat(FOLD_LIMIT - 1), hi);
+    let m2 = s.u32x4_lt(n, s.u32x4_splat((FOLD_LIMIT - 1) * 85));
+    (n, s.v128_and(m1, m2))
+}
+
+/// Splits 4 32-bit words into 5 digit vectors.
+///
+/// Lane `i` of the `k`-th result holds the `k`-th digit of the `i`-th word as `u32`.
+///
+/// There is no 32-bit multiply-high on SIMD128, so each digit is found by a restoring division
+/// of 7 steps, which only compares and subtracts. It works with every SIMD128 token.
+#[inline(always)]
+pub fn unfold_u32x4<S: SIMD128>(s: S, n: V128) -> [V128; 5] {
+    const POW: [u32; 4] = [85 * 85 * 85 * 85, 85 * 85 * 85, 85 * 85, 85];
+
+    let mut r = n;
+    let mut d = [s.v128_create_zero(); 5];
+    let mut k = 0;
+    while k < 4 {
+        // The leading digit is at most 82, the others are at most 84.
+        let mut b = 64;
+        while b > 0 {
+            // 64 * 85^4 < 2^32
+            let t = s.u32x4_splat(b * POW[k]);
+            let m = s.v128_not(s.u32x4_lt(r, t));
+            r = s.u32x4_sub(r, s.v128_and(m, t));
+            d[k] = s.v128_or(d[k], s.v128_and(m, s.u32x4_splat(b)));
+            b /= 2;
+        }
+        k += 1;
+    }
+    d[4] = r;
+    d
+}
+
+#[cfg(test)]
+mod algorithm {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let cases = [0, 1, 84, 85, 0x1234_5678, FOLD_LIMIT * 85, u32::MAX - 1, u32::MAX];
+        for n in cases {
+            assert_eq!(fold(unfold(n)), Some(n));
+        }
+        for n in (0..=u32::MAX).step_by(9973) {
+            assert_eq!(fold(unfold(n)), Some(n));
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(fold([82, 23, 54, 12, 0]), Some(u32::MAX));
+        assert_eq!(fold([82, 23, 54, 12, 1]), None);
+        assert_eq!(fold([84, 84, 84, 84, 84]), None);
+    }
+
+    #[cfg(all(feature = "detect", any(target_arch = "x86", target_arch = "x86_64")))]
+    #[test]
+    fn simd_fold() {
+        use crate::isa::SSE41;
+
+        let s = match crate::isa::detect::<SSE41>() {
+            Some(s) => s,
+            None => return,
+        };
+
+        let words = [
+            [82, 23, 54, 12, 0],
+            [82, 23, 54, 12, 1],
+            [84, 84, 84, 84, 84],
+            [1, 2, 3, 4, 5],
+        ];
+
+        let mut d = [[0u32; 4]; 5];
+        for (i, w) in words.iter().enumerate() {
+            for k in 0..5 {
+                d[k][i] = u32::from(w[k]);
+            }
+        }
+        let d = d.map(|x| V128::from_bytes(unsafe { core::mem::transmute(x) }));
+
+        let (n, overflow) = fold_u32x4(s, d);
+        let n: [u32; 4] = unsafe { core::mem::transmute(*n.as_bytes()) };
+        let overflow: [u32; 4] = unsafe { core::mem::transmute(*overflow.as_bytes()) };
+
+        for (i, w) in words.iter().enumerate() {
+            match fold(*w) {
+                Some(x) => {
+                    assert_eq!(n[i], x);
+                    assert_eq!(overflow[i], 0);
+                }
+                None => assert_eq!(overflow[i], u32::MAX),
+            }
+        }
+    }
+
+    #[cfg(all(feature = "detect", any(target_arch = "x86", target_arch = "x86_64")))]
+    #[test]
+    fn simd_unfold() {
+        use crate::isa::SSE2;
+
+        let s = match crate::isa::detect::<SSE2>() {
+            Some(s) => s,
+            None => return,
+        };
+
+        let cases = [0, 84, 85, 0x1234_5678, FOLD_LIMIT * 85, u32::MAX - 1, u32::MAX];
+        for words in cases
+            .windows(4)
+            .chain((0..=u32::MAX).step_by(99991).collect::<Vec<_>>().chunks(4))
+        {
+            let mut words4 = [0u32; 4];
+            words4[..words.len()].copy_from_slice(words);
+
+            let n = V128::from_bytes(unsafe { core::mem::transmute(words4) });
+            let d = unfold_u32x4(s, n);
+            let d: [[u32; 4]; 5] = d.map(|x| unsafe { core::mem::transmute(*x.as_bytes()) });
+
+            for (i, &w) in words4.iter().enumerate() {
+                let expected = unfold(w);
+                for k in 0..5 {
+                    assert_eq!(d[k][i], u32::from(expected[k]));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,294 @@
+//! CRC32 and CRC32C checksums.
+//!
+//! The one-shot functions compute the checksum of a byte slice.
+//! The `*_update` functions continue a checksum with more bytes:
+//! `crc32_update(crc32(a), b) == crc32(a ++ b)`.
+//!
+//! + CRC32 (IEEE 802.3, used by zlib and PNG) uses PCLMULQDQ on x86 and the CRC instructions on aarch64.
+//! + CRC32C (Castagnoli, used by iSCSI and ext4) uses SSE4.2 on x86 and the CRC instructions on aarch64.
+//!
+//! The fallback is table-driven (slicing-by-8).
+
+/// The reversed polynomial of CRC32.
+const CRC32_POLY: u32 = 0xedb8_8320;
+
+/// The reversed polynomial of CRC32C.
+const CRC32C_POLY: u32 = 0x82f6_3b78;
+
+const fn crc_tables(poly: u32) -> [[u32; 256]; 8] {
+    let mut tables = [[0; 256]; 8];
+
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ poly } else { crc >> 1 };
+            j += 1;
+        }
+        tables[0][i] = crc;
+        i += 1;
+    }
+
+    let mut k = 1;
+    while k < 8 {
+        let mut i = 0;
+        while i < 256 {
+            let prev = tables[k - 1][i];
+            tables[k][i] = (prev >> 8) ^ tables[0][(prev & 0xff) as usize];
+            i += 1;
+        }
+        k += 1;
+    }
+
+    tables
+}
+
+const CRC32_TABLES: &[[u32; 256]; 8] = &crc_tables(CRC32_POLY);
+const CRC32C_TABLES: &[[u32; 256]; 8] = &crc_tables(CRC32C_POLY);
+
+#[inline]
+fn update_fallback(crc: u32, data: &[u8], t: &[[u32; 256]; 8]) -> u32 {
+    let mut crc = !crc;
+
+    let mut chunks = data.chunks_exact(8);
+    for chunk in &mut chunks {
+        let lo = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) ^ crc;
+        let hi = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]);
+        crc = t[7][(lo & 0xff) as usize]
+            ^ t[6][((lo >> 8) & 0xff) as usize]
+            ^ t[5][((lo >> 16) & 0xff) as usize]
+            ^ t[4][(lo >> 24) as usize]
+            ^ t[3][(hi & 0xff) as usize]
+            ^ t[2][((hi >> 8) & 0xff) as usize]
+            ^ t[1][((hi >> 16) & 0xff) as usize]
+            ^ t[0][(hi >> 24) as usize];
+    }
+
+    for &x in chunks.remainder() {
+        crc = (crc >> 8) ^ t[0][((crc ^ x as u32) & 0xff) as usize];
+    }
+
+    !crc
+}
+
+/// Computes the CRC32 checksum of `data`.
+#[inline]
+#[must_use]
+pub fn crc32(data: &[u8]) -> u32 {
+    crc32_update(0, data)
+}
+
+/// Continues the CRC32 checksum `crc` with `data`.
+#[inline]
+#[must_use]
+pub fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if data.len() >= x86::FOLD_MIN_LEN && x86::is_pclmul_enabled() {
+            return unsafe { x86::crc32_pclmul(crc, data) };
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if is_feature_detected!("crc") {
+            return unsafe { aarch64::crc32(crc, data) };
+        }
+    }
+    update_fallback(crc, data, CRC32_TABLES)
+}
+
+/// Computes the CRC32C checksum of `data`.
+#[inline]
+#[must_use]
+pub fn crc32c(data: &[u8]) -> u32 {
+    crc32c_update(0, data)
+}
+
+/// Continues the CRC32C checksum `crc` with `data`.
+#[inline]
+#[must_use]
+pub fn crc32c_update(crc: u32, data: &[u8]) -> u32 {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_feature_detected!("sse4.2") {
+            return unsafe { x86::crc32c_sse42(crc, data) };
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if is_feature_detected!("crc") {
+            return unsafe { aarch64::crc32c(crc, data) };
+        }
+    }
+    update_fallback(crc, data, CRC32C_TABLES)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    /// Shorter inputs are not worth the setup of the folding.
+    pub const FOLD_MIN_LEN: usize = 128;
+
+    #[inline]
+    pub fn is_pclmul_enabled() -> bool {
+        if !is_feature_detected!("pclmulqdq") {
+            return false;
+        }
+        is_feature_detected!("sse4.1")
+    }
+
+    // Folding constants for the reversed CRC32 polynomial.
+    // See "Fast CRC Computation for Generic Polynomials Using PCLMULQDQ Instruction" (Intel, 2009).
+    const K1: i64 = 0x1_5444_2bd4;
+    const K2: i64 = 0x1_c6e4_1596;
+    const K3: i64 = 0x1_7519_97d0;
+    const K4: i64 = 0x0_ccaa_009e;
+    const K5: i64 = 0x1_63cd_6124;
+    const P_X: i64 = 0x1_db71_0641;
+    const U_PRIME: i64 = 0x1_f701_1641;
+
+    #[inline(always)]
+    unsafe fn load(src: *const u8) -> __m128i {
+        _mm_loadu_si128(src.cast())
+    }
+
+    #[inline(always)]
+    unsafe fn fold(a: __m128i, b: __m128i, keys: __m128i) -> __m128i {
+        let t1 = _mm_clmulepi64_si128(a, keys, 0x00);
+        let t2 = _mm_clmulepi64_si128(a, keys, 0x11);
+        _mm_xor_si128(_mm_xor_si128(b, t1), t2)
+    }
+
+    #[target_feature(enable = "pclmulqdq,sse4.1")]
+    pub unsafe fn crc32_pclmul(crc: u32, data: &[u8]) -> u32 {
+        debug_assert!(data.len() >= FOLD_MIN_LEN);
+
+        let mut src = data.as_ptr();
+        let mut len = data.len();
+
+        let mut x3 = load(src);
+        let mut x2 = load(src.add(16));
+        let mut x1 = load(src.add(32));
+        let mut x0 = load(src.add(48));
+        src = src.add(64);
+        len -= 64;
+
+        x3 = _mm_xor_si128(x3, _mm_cvtsi32_si128(!crc as i32));
+
+        let k1k2 = _mm_set_epi64x(K2, K1);
+        while len >= 64 {
+            x3 = fold(x3, load(src), k1k2);
+            x2 = fold(x2, load(src.add(16)), k1k2);
+            x1 = fold(x1, load(src.add(32)), k1k2);
+            x0 = fold(x0, load(src.add(48)), k1k2);
+            src = src.add(64);
+            len -= 64;
+        }
+
+        let k3k4 = _mm_set_epi64x(K4, K3);
+        let mut x = fold(x3, x2, k3k4);
+        x = fold(x, x1, k3k4);
+        x = fold(x, x0, k3k4);
+
+        while len >= 16 {
+            x = fold(x, load(src), k3k4);
+            src = src.add(16);
+            len -= 16;
+        }
+
+        // 128 bits to 64 bits
+        let low32 = _mm_set_epi32(0, 0, 0, !0);
+        let x = _mm_xor_si128(_mm_clmulepi64_si128(x, k3k4, 0x10), _mm_srli_si128(x, 8));
+        let x = _mm_xor_si128(
+            _mm_clmulepi64_si128(_mm_and_si128(x, low32), _mm_set_epi64x(0, K5), 0x00),
+            _mm_srli_si128(x, 4),
+        );
+
+        // Barrett reduction from 64 bits to 32 bits
+        let pu = _mm_set_epi64x(U_PRIME, P_X);
+        let t1 = _mm_clmulepi64_si128(_mm_and_si128(x, low32), pu, 0x10);
+        let t2 = _mm_clmulepi64_si128(_mm_and_si128(t1, low32), pu, 0x00);
+        let crc = !(_mm_extract_epi32(_mm_xor_si128(x, t2), 1) as u32);
+
+        let rest = core::slice::from_raw_parts(src, len);
+        super::update_fallback(crc, rest, super::CRC32_TABLES)
+    }
+
+    #[target_feature(enable = "sse4.2")]
+    pub unsafe fn crc32c_sse42(crc: u32, data: &[u8]) -> u32 {
+        let mut crc = !crc;
+
+        #[cfg(target_arch = "x86_64")]
+        let rest = {
+            let mut chunks = data.chunks_exact(8);
+            for chunk in &mut chunks {
+                let x = u64::from_le_bytes(chunk.try_into().unwrap());
+                crc = _mm_crc32_u64(crc as u64, x) as u32;
+            }
+            chunks.remainder()
+        };
+
+        #[cfg(target_arch = "x86")]
+        let rest = {
+            let mut chunks = data.chunks_exact(4);
+            for chunk in &mut chunks {
+                let x = u32::from_le_bytes(chunk.try_into().unwrap());
+                crc = _mm_crc32_u32(crc, x);
+            }
+            chunks.remainder()
+        };
+
+        for &x in rest {
+            crc = _mm_crc32_u8(crc, x);
+        }
+
+        !crc
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use core::arch::asm;
+
+    // The CRC intrinsics in `core::arch::aarch64` require a newer compiler than the MSRV,
+    // so the instructions are written in inline assembly.
+    macro_rules! define_crc {
+        ($name:ident, $insn_x:literal, $insn_b:literal) => {
+            #[target_feature(enable = "crc")]
+            pub unsafe fn $name(crc: u32, data: &[u8]) -> u32 {
+                let mut crc = !crc;
+
+                let mut chunks = data.chunks_exact(8);
+                for chunk in &mut chunks {
+                    let x = u64::from_le_bytes(chunk.try_into().unwrap());
+                    asm!(
+                        concat!($insn_x, " {crc:w}, {crc:w}, {x:x}"),
+                        crc = inout(reg) crc,
+                        x = in(reg) x,
+                        options(pure, nomem, nostack)
+                    );
+                }
+
+                for &x in chunks.remainder() {
+                    asm!(
+                        concat!($insn_b, " {crc:w}, {crc:w}, {x:w}"),
+                        crc = inout(reg) crc,
+                        x = in(reg) u32::from(x),
+                        options(pure, nomem, nostack)
+                    );
+                }
+
+                !crc
+            }
+        };
+    }
+
+    define_crc!(crc32, "crc32x", "crc32b");
+    define_crc!(crc32c, "crc32cx", "crc32cb");
+}
@@ -1,5 +1,6 @@
 use crate::alsw::{self, AlswLut};
-use crate::isa::{AVX2, NEON, SSSE3, WASM128};
+use crate::ascii::AsciiCase;
+use crate::isa::{Fallback, AVX2, NEON, SSSE3, WASM128};
 use crate::mask::{u8x16_highbit_any, u8x32_highbit_any};
 use crate::pod::POD;
 use crate::vector::{V128, V256, V64};
@@ -17,18 +18,19 @@ const fn parse_hex(x: u8) -> u8 {
     }
 }
 
+pub const UNHEX_TABLE: &[u8; 256] = &{
+    let mut arr = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        arr[i] = parse_hex(i as u8);
+        i += 1;
+    }
+    arr
+};
+
 #[inline(always)]
 #[must_use]
 pub const fn unhex(x: u8) -> u8 {
-    const UNHEX_TABLE: &[u8; 256] = &{
-        let mut arr = [0; 256];
-        let mut i = 0;
-        while i < 256 {
-            arr[i] = parse_hex(i as u8);
-            i += 1;
-        }
-        arr
-    };
     UNHEX_TABLE[x as usize]
 }
 
@@ -45,6 +47,24 @@ where
     s.mask8xn_all(s.or(m1, m2))
 }
 
+/// Checks whether all bytes of `x` are digits or hex letters in `case`.
+#[inline(always)]
+pub fn check_case_xn<S, V>(s: S, x: V, case: AsciiCase) -> bool
+where
+    S: Scalable<V>,
+    V: POD,
+{
+    let letter = match case {
+        AsciiCase::Lower => b'a',
+        AsciiCase::Upper => b'A',
+    };
+    let x1 = s.u8xn_sub(x, s.u8xn_splat(0x30 + 0x80));
+    let x2 = s.u8xn_sub(x, s.u8xn_splat(letter + 0x80));
+    let m1 = s.i8xn_lt(x1, s.i8xn_splat(-118));
+    let m2 = s.i8xn_lt(x2, s.i8xn_splat(-122));
+    s.mask8xn_all(s.or(m1, m2))
+}
+
 pub const ENCODE_UPPER_LUT: V256 = V256::double_bytes(*UPPER_CHARSET);
 pub const ENCODE_LOWER_LUT: V256 = V256::double_bytes(*LOWER_CHARSET);
 
@@ -146,7 +166,7 @@ fn decode32<S: SIMD256>(s: S, x: V256) -> (V256, V256) {
 pub fn decode_ascii16<S: SIMD128>(s: S, x: V128) -> Result<V64, ()> {
     let (y, is_invalid) = decode16(s, x);
 
-    let ans = if matches_isa!(S, SSSE3 | WASM128) {
+    let ans = if matches_isa!(S, SSSE3 | WASM128) || is_isa_type!(S, Fallback) {
         const UZP1: V128 = DECODE_UZP1.to_v128x2().0;
         s.u8x16_swizzle(y, UZP1).to_v64x2().0
     } else if matches_isa!(S, NEON) {
@@ -168,7 +188,7 @@ pub fn decode_ascii16<S: SIMD128>(s: S, x: V128) -> Result<V64, ()> {
 pub fn decode_ascii32<S: SIMD256>(s: S, x: V256) -> Result<V128, ()> {
     let (y, is_invalid) = decode32(s, x);
 
-    let ans = if matches_isa!(S, SSSE3 | WASM128) {
+    let ans = if matches_isa!(S, SSSE3 | WASM128) || is_isa_type!(S, Fallback) {
         let (a, b) = s.u8x16x2_swizzle(y, DECODE_UZP1).to_v128x2();
         s.u64x2_zip_lo(a, b)
     } else if matches_isa!(S, NEON) {
@@ -197,7 +217,7 @@ pub fn decode_ascii32x2<S: SIMD256>(s: S, x: (V256, V256)) -> Result<V256, ()> {
         let cd = s.u8x16x2_swizzle(y2, DECODE_UZP2);
         let acbd = s.v256_or(ab, cd);
         s.u64x4_permute::<0b_1101_1000>(acbd) // 0213
-    } else if matches_isa!(S, SSSE3 | WASM128) {
+    } else if matches_isa!(S, SSSE3 | WASM128) || is_isa_type!(S, Fallback) {
         let ab = s.u8x16x2_swizzle(y1, DECODE_UZP1);
         let cd = s.u8x16x2_swizzle(y2, DECODE_UZP1);
         s.u64x4_unzip_even(ab, cd)
@@ -214,6 +234,30 @@ pub fn decode_ascii32x2<S: SIMD256>(s: S, x: (V256, V256)) -> Result<V256, ()> {
     }
 }
 
+/// Decodes each hex character to its nibble value (`0..16`).
+#[allow(clippy::result_unit_err)]
+#[inline(always)]
+pub fn unhex_ascii16<S: SIMD128>(s: S, x: V128) -> Result<V128, ()> {
+    let (c1, c2) = alsw::decode_ascii_xn(s, x, HEX_ALSW_CHECK, HEX_ALSW_DECODE);
+    if u8x16_highbit_any(s, c1) {
+        Err(())
+    } else {
+        Ok(c2)
+    }
+}
+
+/// Decodes each hex character to its nibble value (`0..16`).
+#[allow(clippy::result_unit_err)]
+#[inline(always)]
+pub fn unhex_ascii32<S: SIMD256>(s: S, x: V256) -> Result<V256, ()> {
+    let (c1, c2) = alsw::decode_ascii_xn(s, x, HEX_ALSW_CHECK_X2, HEX_ALSW_DECODE_X2);
+    if u8x32_highbit_any(s, c1) {
+        Err(())
+    } else {
+        Ok(c2)
+    }
+}
+
 pub mod sse2 {
     use crate::isa::SSE2;
     use crate::vector::{V128, V64};
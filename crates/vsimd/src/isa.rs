@@ -92,6 +92,10 @@ unsafe impl InstructionSet for Fallback {
     }
 }
 
+unsafe impl SIMD64 for Fallback {}
+unsafe impl SIMD128 for Fallback {}
+unsafe impl SIMD256 for Fallback {}
+
 #[allow(unused_macros)]
 macro_rules! is_feature_detected {
     ($feature:tt) => {{
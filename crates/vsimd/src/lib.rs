@@ -1,4 +1,87 @@
-//! ⚠️ This crate contains shared implementation details. Do not directly depend on it.
+//! SIMD utilities shared by the `*-simd` crates.
+//!
+//! ⚠️ Most of this crate is shared implementation details, which may change in any release.
+//! Only the extension API below is supported for third-party codecs.
+//!
+//! # Extension API
+//!
+//! The following items follow semver. Breaking changes to them only happen in a new `0.x` version.
+//!
+//! + [`dispatch!`]: generates a module with an `auto` function which calls the best implementation.
+//! + [`isa`]: the instruction set tokens ([`isa::AVX2`], [`isa::SSE2`], [`isa::NEON`], [`isa::WASM128`], ...)
+//!   and [`matches_isa!`] to specialize code for them.
+//!   [`isa::Fallback`] implements the vector operations with portable scalar code,
+//!   which is useful for testing SIMD algorithms on any target.
+//! + [`SIMD256`], [`SIMD128`] and [`Scalable`]: the vector operations, including
+//!   + load/store: `v128_load_unaligned`, `v128_store_unaligned`, `v256_load_unaligned`, `v256_store_unaligned`.
+//!   + shuffle: `u8x16_swizzle`, `u8x16x2_swizzle`, `u8x32_swizzle` and [`table::u8x16xn_lookup`].
+//!   + cross-lane moves of 256-bit vectors: `permute_cross_lane`, `shuffle_bytes_x2`, `u64x4_permute`,
+//!     `v128x2_zip_lo` and `v128x2_zip_hi`.
+//! + [`vector`] and [`POD`]: the vector types.
+//! + [`alsw`]: [`AlswLut`](alsw::AlswLut), [`check_ascii_xn`](alsw::check_ascii_xn)
+//!   and [`decode_ascii_xn`](alsw::decode_ascii_xn) for validating and decoding ASCII charsets.
+//!   [`build_check`](alsw::build_check) derives the check table from the valid ranges of a charset.
+//! + [`crc`]: CRC32 and CRC32C checksums for fusing integrity checks into codecs.
+//!
+//! All functions taking raw pointers are `unsafe`.
+//! The caller must ensure that the pointers are valid for the given lengths.
+//!
+//! # Examples
+//!
+//! A toy codec which adds 1 to each byte.
+//!
+//! ```
+//! use vsimd::isa::AVX2;
+//! use vsimd::{matches_isa, Scalable, POD, SIMD256};
+//!
+//! unsafe fn add_one_fallback(src: *const u8, len: usize, dst: *mut u8) {
+//!     for i in 0..len {
+//!         dst.add(i).write(src.add(i).read().wrapping_add(1));
+//!     }
+//! }
+//!
+//! #[inline(always)]
+//! fn add_one_xn<S: Scalable<V>, V: POD>(s: S, x: V) -> V {
+//!     s.u8xn_add(x, s.u8xn_splat(1))
+//! }
+//!
+//! #[inline(always)]
+//! unsafe fn add_one_simd<S: SIMD256>(s: S, mut src: *const u8, mut len: usize, mut dst: *mut u8) {
+//!     if matches_isa!(S, AVX2) {
+//!         while len >= 32 {
+//!             let x = s.v256_load_unaligned(src);
+//!             s.v256_store_unaligned(dst, add_one_xn(s, x));
+//!             src = src.add(32);
+//!             dst = dst.add(32);
+//!             len -= 32;
+//!         }
+//!     }
+//!     while len >= 16 {
+//!         let x = s.v128_load_unaligned(src);
+//!         s.v128_store_unaligned(dst, add_one_xn(s, x));
+//!         src = src.add(16);
+//!         dst = dst.add(16);
+//!         len -= 16;
+//!     }
+//!     add_one_fallback(src, len, dst);
+//! }
+//!
+//! vsimd::dispatch!(
+//!     name        = {add_one},
+//!     signature   = {pub(crate) unsafe fn(src: *const u8, len: usize, dst: *mut u8) -> ()},
+//!     fallback    = {add_one_fallback},
+//!     simd        = {add_one_simd},
+//!     targets     = {"avx2", "sse2", "neon", "simd128"},
+//!     fastest     = {"avx2", "neon", "simd128"},
+//! );
+//!
+//! fn main() {
+//!     let src: Vec<u8> = (0..=255).collect();
+//!     let mut dst = vec![0; src.len()];
+//!     unsafe { add_one::auto(src.as_ptr(), src.len(), dst.as_mut_ptr()) };
+//!     assert!(src.iter().zip(&dst).all(|(&x, &y)| y == x.wrapping_add(1)));
+//! }
+//! ```
 #![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![cfg_attr(
     feature = "unstable",
@@ -76,10 +159,13 @@ pub mod tools;
 pub mod alsw;
 
 pub mod ascii;
+pub mod base85;
 pub mod bswap;
+pub mod crc;
 pub mod hex;
 pub mod mask;
 pub mod native;
+pub mod reference;
 pub mod table;
 
 #[cfg(feature = "unstable")]
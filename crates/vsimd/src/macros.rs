@@ -60,6 +60,7 @@ macro_rules! dispatch {
         simd        = {$simd_fn:path},
         targets     = {$($target:tt),+},
         fastest     = {$($fastest:tt),*},
+        $(forced      = {$($forced:tt)*},)?
     ) => {
         $vis mod $name {
             #![allow(
@@ -88,6 +89,18 @@ macro_rules! dispatch {
             #[cfg(not(all(feature = "detect", not(target_arch = "wasm32"))))] // auto_direct
             #[inline]
             $vis unsafe fn auto($($arg_name: $arg_type),*) -> $ret {
+                $crate::dispatch!(
+                    @iter_resolve_forced,
+                    forced      = {$($($forced)*)?},
+                    targets     = {$($target),+},
+                    args        = {$($arg_name),*},
+                );
+                $crate::dispatch!(
+                    @forced_fallback,
+                    forced      = {$($($forced)*)?},
+                    fallback    = {$fallback_fn},
+                    args        = {$($arg_name),*},
+                );
                 $crate::dispatch!(
                     @iter_resolve_static,
                     targets     = {$($target),+},
@@ -119,6 +132,18 @@ macro_rules! dispatch {
                 #[allow(unreachable_code)]
                 #[inline]
                 $vis unsafe fn auto($($arg_name: $arg_type),*) -> $ret {
+                    $crate::dispatch!(
+                        @iter_resolve_forced,
+                        forced      = {$($($forced)*)?},
+                        targets     = {$($target),+},
+                        args        = {$($arg_name),*},
+                    );
+                    $crate::dispatch!(
+                        @forced_fallback,
+                        forced      = {$($($forced)*)?},
+                        fallback    = {$fallback_fn},
+                        args        = {$($arg_name),*},
+                    );
                     $crate::dispatch!(
                         @iter_resolve_static,
                         targets     = {$($fastest),+},
@@ -209,6 +234,118 @@ macro_rules! dispatch {
         }
     };
 
+    // The crates which pass `forced = {features}` accept the mutually exclusive feature flags
+    // `force-avx2`, `force-sse41`, `force-neon` and `force-scalar`.
+    // A forced backend is called directly, without runtime detection.
+    // The best target not above the forced one is selected, or the fallback if there is none.
+    // The flags for other architectures are ignored.
+    (
+        @iter_resolve_forced,
+        forced      = {},
+        targets     = {$($x:tt),+},
+        args        = {$($arg_name: ident),*},
+    ) => {};
+
+    (
+        @iter_resolve_forced,
+        forced      = {features},
+        targets     = {$x:tt, $($xs:tt),+},
+        args        = {$($arg_name: ident),*},
+    ) => {
+        $crate::dispatch!(@resolve_forced, $x, $($arg_name),*);
+        $crate::dispatch!(@iter_resolve_forced, forced = {features}, targets = {$($xs),+}, args = {$($arg_name),*},);
+    };
+
+    (
+        @iter_resolve_forced,
+        forced      = {features},
+        targets     = {$x:tt},
+        args        = {$($arg_name: ident),*},
+    ) => {
+        $crate::dispatch!(@resolve_forced, $x, $($arg_name),*);
+    };
+
+    (@resolve_forced, "avx2", $($arg_name: ident),*) => {
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            feature = "force-avx2"
+        ))]
+        {
+            return unsafe { avx2($($arg_name),*) }
+        }
+    };
+
+    (@resolve_forced, "sse4.1", $($arg_name: ident),*) => {
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            any(feature = "force-avx2", feature = "force-sse41")
+        ))]
+        {
+            return unsafe { sse41($($arg_name),*) }
+        }
+    };
+
+    (@resolve_forced, "ssse3", $($arg_name: ident),*) => {
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            any(feature = "force-avx2", feature = "force-sse41")
+        ))]
+        {
+            return unsafe { ssse3($($arg_name),*) }
+        }
+    };
+
+    (@resolve_forced, "sse2", $($arg_name: ident),*) => {
+        #[cfg(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            any(feature = "force-avx2", feature = "force-sse41")
+        ))]
+        {
+            return unsafe { sse2($($arg_name),*) }
+        }
+    };
+
+    (@resolve_forced, "neon", $($arg_name: ident),*) => {
+        #[cfg(all(
+            any(all(feature = "unstable", target_arch = "arm"), target_arch = "aarch64"),
+            feature = "force-neon"
+        ))]
+        {
+            return unsafe { neon($($arg_name),*) }
+        }
+    };
+
+    (@resolve_forced, "simd128", $($arg_name: ident),*) => {};
+
+    (
+        @forced_fallback,
+        forced      = {},
+        fallback    = {$fallback_fn:path},
+        args        = {$($arg_name: ident),*},
+    ) => {};
+
+    (
+        @forced_fallback,
+        forced      = {features},
+        fallback    = {$fallback_fn:path},
+        args        = {$($arg_name: ident),*},
+    ) => {
+        #[cfg(any(
+            feature = "force-scalar",
+            all(
+                any(target_arch = "x86", target_arch = "x86_64"),
+                any(feature = "force-avx2", feature = "force-sse41")
+            ),
+            all(
+                any(all(feature = "unstable", target_arch = "arm"), target_arch = "aarch64"),
+                feature = "force-neon"
+            ),
+        ))]
+        {
+            return $fallback_fn($($arg_name),*);
+        }
+    };
+
     (
         @iter_resolve_dynamic,
         targets     = {$x:tt, $($xs:tt),+},
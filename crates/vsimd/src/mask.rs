@@ -1,4 +1,4 @@
-use crate::isa::{AVX2, NEON, SSE2, WASM128};
+use crate::isa::{Fallback, AVX2, NEON, SSE2, WASM128};
 use crate::vector::{V128, V256};
 use crate::{SIMD128, SIMD256};
 
@@ -6,7 +6,7 @@ use core::ops::Not;
 
 #[inline(always)]
 pub fn mask8x16_all<S: SIMD128>(s: S, x: V128) -> bool {
-    if matches_isa!(S, SSE2 | WASM128) {
+    if matches_isa!(S, SSE2 | WASM128) || is_isa_type!(S, Fallback) {
         return s.u8x16_bitmask(x) == u16::MAX;
     }
     if matches_isa!(S, NEON) {
@@ -25,7 +25,7 @@ pub fn mask8x32_all<S: SIMD256>(s: S, x: V256) -> bool {
     if matches_isa!(S, AVX2) {
         return s.u8x32_bitmask(x) == u32::MAX;
     }
-    if matches_isa!(S, SSE2 | WASM128 | NEON) {
+    if matches_isa!(S, SSE2 | WASM128 | NEON) || is_isa_type!(S, Fallback) {
         let x = x.to_v128x2();
         let x = s.v128_and(x.0, x.1);
         return mask8x16_all(s, x);
@@ -35,7 +35,7 @@ pub fn mask8x32_all<S: SIMD256>(s: S, x: V256) -> bool {
 
 #[inline(always)]
 pub fn mask8x16_any<S: SIMD128>(s: S, x: V128) -> bool {
-    if matches_isa!(S, SSE2 | WASM128) {
+    if matches_isa!(S, SSE2 | WASM128) || is_isa_type!(S, Fallback) {
         return s.u8x16_bitmask(x) != 0;
     }
     if matches_isa!(S, NEON) {
@@ -49,7 +49,7 @@ pub fn mask8x32_any<S: SIMD256>(s: S, x: V256) -> bool {
     if matches_isa!(S, AVX2) {
         return s.u8x32_bitmask(x) != 0;
     }
-    if matches_isa!(S, SSE2 | WASM128 | NEON) {
+    if matches_isa!(S, SSE2 | WASM128 | NEON) || is_isa_type!(S, Fallback) {
         let x = x.to_v128x2();
         let x = s.v128_or(x.0, x.1);
         return mask8x16_any(s, x);
@@ -59,7 +59,7 @@ pub fn mask8x32_any<S: SIMD256>(s: S, x: V256) -> bool {
 
 #[inline(always)]
 pub fn u8x16_highbit_all<S: SIMD128>(s: S, x: V128) -> bool {
-    if matches_isa!(S, SSE2 | WASM128) {
+    if matches_isa!(S, SSE2 | WASM128) || is_isa_type!(S, Fallback) {
         return s.u8x16_bitmask(x) == u16::MAX;
     }
     if matches_isa!(S, NEON) {
@@ -78,7 +78,7 @@ pub fn u8x32_highbit_all<S: SIMD256>(s: S, x: V256) -> bool {
     if matches_isa!(S, AVX2) {
         return s.u8x32_bitmask(x) == u32::MAX;
     }
-    if matches_isa!(S, SSE2 | WASM128 | NEON) {
+    if matches_isa!(S, SSE2 | WASM128 | NEON) || is_isa_type!(S, Fallback) {
         let x = x.to_v128x2();
         let x = s.v128_and(x.0, x.1);
         return u8x16_highbit_all(s, x);
@@ -88,7 +88,7 @@ pub fn u8x32_highbit_all<S: SIMD256>(s: S, x: V256) -> bool {
 
 #[inline(always)]
 pub fn u8x16_highbit_any<S: SIMD128>(s: S, x: V128) -> bool {
-    if matches_isa!(S, SSE2 | WASM128) {
+    if matches_isa!(S, SSE2 | WASM128) || is_isa_type!(S, Fallback) {
         return s.u8x16_bitmask(x) != 0;
     }
     if matches_isa!(S, NEON) {
@@ -107,7 +107,7 @@ pub fn u8x32_highbit_any<S: SIMD256>(s: S, x: V256) -> bool {
     if matches_isa!(S, AVX2) {
         return s.u8x32_bitmask(x) != 0;
     }
-    if matches_isa!(S, SSE2 | WASM128 | NEON) {
+    if matches_isa!(S, SSE2 | WASM128 | NEON) || is_isa_type!(S, Fallback) {
         let x = x.to_v128x2();
         let x = s.v128_or(x.0, x.1);
         return u8x16_highbit_any(s, x);
@@ -0,0 +1,196 @@
+//! Scalar reference implementations of the codecs.
+//!
+//! The codecs are described by a single spec: a power-of-two charset and an optional padding character.
+//! + hex: 16 characters, 2 characters per group, no padding.
+//! + base32: 32 characters, 8 characters per group.
+//! + base64: 64 characters, 4 characters per group.
+//!
+//! [`encode_bits`] and [`decode_bits`] follow the spec bit by bit.
+//! The scalar fallbacks of the codec crates use them for the partial groups,
+//! and [`encode`] and [`decode`] build the test oracle of the fast paths on top of them.
+//!
+//! Decoding is strict: the padding must be present if and only if `pad` is `Some`,
+//! the length must be valid, and the unused bits of the last character must be zero.
+
+use crate::tools::{read, write};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Encodes `len` bytes with a charset of `2^BITS` characters, without padding.
+///
+/// Writes `(len * 8 + BITS - 1) / BITS` characters to `dst` and returns the number of them.
+///
+/// # Safety
+/// This function requires:
+///
+/// + `src` is valid for reads of `len` bytes.
+/// + `dst` is valid for writes of the returned number of bytes.
+/// + `charset` is valid for reads of `2^BITS` bytes.
+#[inline(always)]
+pub unsafe fn encode_bits<const BITS: u32>(src: *const u8, len: usize, dst: *mut u8, charset: *const u8) -> usize {
+    debug_assert!(matches!(BITS, 4..=6));
+    let mask = (1u32 << BITS) - 1;
+
+    let mut k = 0;
+    let mut acc: u32 = 0;
+    let mut nbits: u32 = 0;
+    let mut i = 0;
+    while i < len {
+        acc = (acc << 8) | u32::from(read(src, i));
+        nbits += 8;
+        while nbits >= BITS {
+            nbits -= BITS;
+            write(dst, k, read(charset, ((acc >> nbits) & mask) as usize));
+            k += 1;
+        }
+        acc &= (1 << nbits) - 1;
+        i += 1;
+    }
+    if nbits > 0 {
+        write(dst, k, read(charset, ((acc << (BITS - nbits)) & mask) as usize));
+        k += 1;
+    }
+    k
+}
+
+/// Decodes `n` characters with `table`, which maps a character to its value or `0xff`.
+///
+/// Writes `n * BITS / 8` bytes to `dst` if `WRITE` is `true`.
+///
+/// Returns `false` if
+/// + a character is invalid.
+/// + the last character does not start a new byte.
+/// + the unused bits of the last character are not zero and `forgiving` is `false`.
+///
+/// # Safety
+/// This function requires:
+///
+/// + `src` is valid for reads of `n` bytes.
+/// + `dst` is valid for writes of `n * BITS / 8` bytes if `WRITE` is `true`.
+/// + `table` is valid for reads of 256 bytes.
+#[inline(always)]
+pub unsafe fn decode_bits<const BITS: u32, const WRITE: bool>(
+    src: *const u8,
+    n: usize,
+    dst: *mut u8,
+    table: *const u8,
+    forgiving: bool,
+) -> bool {
+    debug_assert!(matches!(BITS, 4..=6));
+
+    let mut k = 0;
+    let mut acc: u32 = 0;
+    let mut nbits: u32 = 0;
+    let mut i = 0;
+    while i < n {
+        let value = read(table, read(src, i) as usize);
+        if value == 0xff {
+            return false;
+        }
+        acc = (acc << BITS) | u32::from(value);
+        nbits += BITS;
+        if nbits >= 8 {
+            nbits -= 8;
+            if WRITE {
+                write(dst, k, (acc >> nbits) as u8);
+            }
+            k += 1;
+        }
+        acc &= (1 << nbits) - 1;
+        i += 1;
+    }
+    nbits < BITS && (forgiving || acc == 0)
+}
+
+/// Returns `(bits per character, characters per group)`.
+#[cfg(feature = "alloc")]
+fn spec(charset: &[u8]) -> (u32, usize) {
+    assert!(matches!(charset.len(), 16 | 32 | 64));
+    let bits = charset.len().trailing_zeros();
+    let group = match bits {
+        4 => 2,
+        5 => 8,
+        6 => 4,
+        _ => unreachable!(),
+    };
+    (bits, group)
+}
+
+/// Encodes `src` with `charset` and pads the last group with `pad` if it is `Some`.
+///
+/// # Panics
+/// This function asserts that the length of `charset` is 16, 32 or 64.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn encode(src: &[u8], charset: &[u8], pad: Option<u8>) -> Vec<u8> {
+    let (bits, group) = spec(charset);
+
+    let mut dst = alloc::vec![0; (src.len() * 8 + bits as usize - 1) / bits as usize];
+    let k = unsafe {
+        let (len, charset) = (src.len(), charset.as_ptr());
+        let (src, dst) = (src.as_ptr(), dst.as_mut_ptr());
+        match bits {
+            4 => encode_bits::<4>(src, len, dst, charset),
+            5 => encode_bits::<5>(src, len, dst, charset),
+            _ => encode_bits::<6>(src, len, dst, charset),
+        }
+    };
+    debug_assert_eq!(k, dst.len());
+
+    if let Some(pad) = pad {
+        while dst.len() % group != 0 {
+            dst.push(pad);
+        }
+    }
+    dst
+}
+
+/// Decodes `src` with `charset`, expecting the padding `pad` if it is `Some`.
+///
+/// Returns `None` if `src` is invalid.
+///
+/// # Panics
+/// This function asserts that the length of `charset` is 16, 32 or 64.
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn decode(src: &[u8], charset: &[u8], pad: Option<u8>) -> Option<Vec<u8>> {
+    let (bits, group) = spec(charset);
+
+    let mut n = src.len();
+    if let Some(pad) = pad {
+        if n % group != 0 {
+            return None;
+        }
+        while n > 0 && src[n - 1] == pad {
+            n -= 1;
+        }
+        if src.len() - n >= group {
+            return None;
+        }
+    }
+
+    if pad.is_some() && n % group == 0 && n != src.len() {
+        return None;
+    }
+
+    let mut table = [0xff; 256];
+    for (i, &c) in charset.iter().enumerate() {
+        table[c as usize] = i as u8;
+    }
+
+    let mut dst = alloc::vec![0; n * bits as usize / 8];
+    let ok = unsafe {
+        let (src, dst, table) = (src.as_ptr(), dst.as_mut_ptr(), table.as_ptr());
+        match bits {
+            4 => decode_bits::<4, true>(src, n, dst, table, false),
+            5 => decode_bits::<5, true>(src, n, dst, table, false),
+            _ => decode_bits::<6, true>(src, n, dst, table, false),
+        }
+    };
+    ok.then_some(dst)
+}
@@ -1,4 +1,4 @@
-use crate::isa::{NEON, SSE2, SSE41, WASM128};
+use crate::isa::{Fallback, NEON, SSE2, SSE41, WASM128};
 use crate::unified;
 use crate::vector::V128;
 use crate::SIMD64;
@@ -46,6 +46,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return self.v128_load_unaligned(addr);
         }
+        if is_isa_type!(Self, Fallback) {
+            return self.v128_load_unaligned(addr);
+        }
         {
             let _ = addr;
             unreachable!()
@@ -67,6 +70,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return t(v128_load(addr.cast()));
         }
+        if is_isa_type!(Self, Fallback) {
+            return addr.cast::<V128>().read_unaligned();
+        }
         {
             let _ = addr;
             unreachable!()
@@ -90,6 +96,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return self.v128_store_unaligned(addr, a);
         }
+        if is_isa_type!(Self, Fallback) {
+            return self.v128_store_unaligned(addr, a);
+        }
         {
             let _ = (addr, a);
             unreachable!()
@@ -114,12 +123,37 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return v128_store(addr.cast(), t(a));
         }
+        if is_isa_type!(Self, Fallback) {
+            return addr.cast::<V128>().write_unaligned(a);
+        }
         {
             let _ = (addr, a);
             unreachable!()
         }
     }
 
+    /// Loads `len` bytes from `addr` and fills the remaining lanes with zeros.
+    ///
+    /// Only `len` bytes are read from `addr`.
+    #[inline(always)]
+    unsafe fn v128_load_partial(self, addr: *const u8, len: usize) -> V128 {
+        debug_assert!(len <= 16);
+        let mut buf = [0u8; 16];
+        core::ptr::copy_nonoverlapping(addr, buf.as_mut_ptr(), len);
+        self.v128_load_unaligned(buf.as_ptr())
+    }
+
+    /// Stores the first `len` bytes of `a` to `addr`.
+    ///
+    /// Only `len` bytes are written to `addr`.
+    #[inline(always)]
+    unsafe fn v128_store_partial(self, addr: *mut u8, a: V128, len: usize) {
+        debug_assert!(len <= 16);
+        let mut buf = [0u8; 16];
+        self.v128_store_unaligned(buf.as_mut_ptr(), a);
+        core::ptr::copy_nonoverlapping(buf.as_ptr(), addr, len);
+    }
+
     /// T1: SSE2, NEON, WASM128
     #[inline(always)]
     fn v128_create_zero(self) -> V128 {
@@ -135,6 +169,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { t(u8x16_splat(0)) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return V128::from_bytes([0; 16]);
+        }
         {
             unreachable!()
         }
@@ -160,6 +197,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { t(v128_not(t(a))) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::not(a);
+        }
         {
             let _ = a;
             unreachable!()
@@ -218,6 +258,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { !v128_any_true(t(a)) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::all_zero(a);
+        }
         {
             let _ = a;
             unreachable!()
@@ -359,6 +402,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { t(i16x8_mul(t(a), t(b))) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::mul_lo::<i16>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -380,6 +426,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { t(i32x4_mul(t(a), t(b))) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::mul_lo::<i32>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -404,6 +453,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { t(u16x8_shl(t(a), IMM8 as u32)) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::shl::<u16>(a, IMM8 as u32);
+        }
         {
             let _ = a;
             unreachable!()
@@ -425,6 +477,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { t(u32x4_shl(t(a), IMM8 as u32)) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::shl::<u32>(a, IMM8 as u32);
+        }
         {
             let _ = a;
             unreachable!()
@@ -449,6 +504,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { t(u16x8_shr(t(a), IMM8 as u32)) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::shr::<u16>(a, IMM8 as u32);
+        }
         {
             let _ = a;
             unreachable!()
@@ -470,6 +528,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { t(u32x4_shr(t(a), IMM8 as u32)) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::shr::<u32>(a, IMM8 as u32);
+        }
         {
             let _ = a;
             unreachable!()
@@ -635,6 +696,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { t(u8x16_swizzle(t(a), t(b))) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x16_swizzle(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -653,6 +717,9 @@ pub unsafe trait SIMD128: SIMD64 {
             return unsafe { t(vrev16q_u8(t(a))) };
         }
 
+        if is_isa_type!(Self, Fallback) {
+            return self.u8x16_swizzle(a, crate::bswap::SHUFFLE_U16X8);
+        }
         {
             let _ = a;
             unreachable!()
@@ -671,6 +738,9 @@ pub unsafe trait SIMD128: SIMD64 {
             return unsafe { t(vrev32q_u8(t(a))) };
         }
 
+        if is_isa_type!(Self, Fallback) {
+            return self.u8x16_swizzle(a, crate::bswap::SHUFFLE_U32X4);
+        }
         {
             let _ = a;
             unreachable!()
@@ -689,6 +759,9 @@ pub unsafe trait SIMD128: SIMD64 {
             return unsafe { t(vrev64q_u8(t(a))) };
         }
 
+        if is_isa_type!(Self, Fallback) {
+            return self.u8x16_swizzle(a, crate::bswap::SHUFFLE_U64X2);
+        }
         {
             let _ = a;
             unreachable!()
@@ -722,6 +795,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { !u8x16_all_true(t(a)) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x16_any_zero(a);
+        }
         {
             let _ = a;
             unreachable!()
@@ -746,6 +822,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { u8x16_bitmask(t(a)) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x16_bitmask(a);
+        }
         {
             let _ = a;
             unreachable!()
@@ -766,6 +845,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, NEON) {
             return unsafe { vmaxvq_u8(t(a)) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x16_reduce_max(a);
+        }
         {
             let _ = a;
             unreachable!()
@@ -786,6 +868,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, NEON) {
             return unsafe { vminvq_u8(t(a)) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x16_reduce_min(a);
+        }
         {
             let _ = a;
             unreachable!()
@@ -804,6 +889,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, NEON) {
             return unsafe { t(vbslq_u8(t(a), t(b), t(c))) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return self.v128_xor(self.v128_and(self.v128_xor(b, c), a), c);
+        }
         {
             let _ = (a, b, c);
             unreachable!()
@@ -831,6 +919,9 @@ pub unsafe trait SIMD128: SIMD64 {
             let ans = u8x16_shuffle::<0, 16, 1, 17, 2, 18, 3, 19, 4, 20, 5, 21, 6, 22, 7, 23>(a, b);
             return unsafe { t(ans) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::zip::<1, false>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -858,6 +949,9 @@ pub unsafe trait SIMD128: SIMD64 {
             let ans = u8x16_shuffle::<8, 24, 9, 25, 10, 26, 11, 27, 12, 28, 13, 29, 14, 30, 15, 31>(a, b);
             return unsafe { t(ans) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::zip::<1, true>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -885,6 +979,9 @@ pub unsafe trait SIMD128: SIMD64 {
             let ans = u16x8_shuffle::<0, 8, 1, 9, 2, 10, 3, 11>(a, b);
             return unsafe { t(ans) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::zip::<2, false>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -912,6 +1009,9 @@ pub unsafe trait SIMD128: SIMD64 {
             let ans = u16x8_shuffle::<4, 12, 5, 13, 6, 14, 7, 15>(a, b);
             return unsafe { t(ans) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::zip::<2, true>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -939,6 +1039,9 @@ pub unsafe trait SIMD128: SIMD64 {
             let ans = u32x4_shuffle::<0, 4, 1, 5>(a, b);
             return unsafe { t(ans) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::zip::<4, false>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -966,6 +1069,9 @@ pub unsafe trait SIMD128: SIMD64 {
             let ans = u32x4_shuffle::<2, 6, 3, 7>(a, b);
             return unsafe { t(ans) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::zip::<4, true>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -992,6 +1098,9 @@ pub unsafe trait SIMD128: SIMD64 {
             let ans = u64x2_shuffle::<0, 2>(a, b);
             return unsafe { t(ans) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::zip::<8, false>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1018,6 +1127,9 @@ pub unsafe trait SIMD128: SIMD64 {
             let ans = u64x2_shuffle::<1, 3>(a, b);
             return unsafe { t(ans) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::zip::<8, true>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1045,6 +1157,9 @@ pub unsafe trait SIMD128: SIMD64 {
             let ans = u8x16_shuffle::<0, 2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24, 26, 28, 30>(a, b);
             return unsafe { t(ans) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x16_unzip::<false>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1072,6 +1187,9 @@ pub unsafe trait SIMD128: SIMD64 {
             let ans = u8x16_shuffle::<1, 3, 5, 7, 9, 11, 13, 15, 17, 19, 21, 23, 25, 27, 29, 31>(a, b);
             return unsafe { t(ans) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x16_unzip::<true>(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1088,6 +1206,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, NEON | WASM128) {
             unimplemented!()
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u16x8_mul_hi(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1104,6 +1225,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, NEON | WASM128) {
             unimplemented!()
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::i16x8_mul_hi(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1120,6 +1244,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, NEON | WASM128) {
             unimplemented!()
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::i16x8_maddubs(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1136,6 +1263,13 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, NEON | WASM128) {
             unimplemented!()
         }
+        if is_isa_type!(Self, Fallback) {
+            return V128::from_bytes(crate::simulation::blend::<2, 16>(
+                *a.as_bytes(),
+                *b.as_bytes(),
+                IMM8 as u32,
+            ));
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1154,6 +1288,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, NEON | WASM128) {
             unimplemented!()
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x16_blendv(a, b, c);
+        }
         {
             let _ = (a, b, c);
             unreachable!()
@@ -1170,6 +1307,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, NEON | WASM128) {
             unimplemented!()
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::i16x8_madd(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1191,6 +1331,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, WASM128) {
             return unsafe { t(u8x16_avgr(t(a), t(b))) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x16_avgr(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1219,6 +1362,9 @@ pub unsafe trait SIMD128: SIMD64 {
         if matches_isa!(Self, SSE2) {
             return unsafe { t(_mm_packus_epi16(t(a), t(b))) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::i16x8_packus(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1,4 +1,4 @@
-use crate::isa::{AVX2, NEON, SSE2, WASM128};
+use crate::isa::{Fallback, AVX2, NEON, SSE2, SSE41, WASM128};
 use crate::vector::{V128, V256};
 use crate::{unified, SIMD128};
 
@@ -125,6 +125,28 @@ pub unsafe trait SIMD256: SIMD128 {
         }
     }
 
+    /// Loads `len` bytes from `addr` and fills the remaining lanes with zeros.
+    ///
+    /// Only `len` bytes are read from `addr`.
+    #[inline(always)]
+    unsafe fn v256_load_partial(self, addr: *const u8, len: usize) -> V256 {
+        debug_assert!(len <= 32);
+        let mut buf = [0u8; 32];
+        core::ptr::copy_nonoverlapping(addr, buf.as_mut_ptr(), len);
+        self.v256_load_unaligned(buf.as_ptr())
+    }
+
+    /// Stores the first `len` bytes of `a` to `addr`.
+    ///
+    /// Only `len` bytes are written to `addr`.
+    #[inline(always)]
+    unsafe fn v256_store_partial(self, addr: *mut u8, a: V256, len: usize) {
+        debug_assert!(len <= 32);
+        let mut buf = [0u8; 32];
+        self.v256_store_unaligned(buf.as_mut_ptr(), a);
+        core::ptr::copy_nonoverlapping(buf.as_ptr(), addr, len);
+    }
+
     #[inline(always)]
     fn v256_create_zero(self) -> V256 {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -458,6 +480,9 @@ pub unsafe trait SIMD256: SIMD128 {
         unified::min::<_, i32, _>(self, a, b)
     }
 
+    /// Shuffles the bytes of each 128-bit lane of `a` by the indices in the same lane of `b`.
+    ///
+    /// An index with the highest bit set selects zero. No byte crosses the lane boundary.
     #[inline(always)]
     fn u8x16x2_swizzle(self, a: V256, b: V256) -> V256 {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -499,6 +524,9 @@ pub unsafe trait SIMD256: SIMD128 {
         }
     }
 
+    /// Looks up the 32-byte table `a` by the indices in `b`, crossing the lane boundary.
+    ///
+    /// Only available on aarch64.
     #[inline(always)]
     fn u8x32_swizzle(self, a: V256, b: V256) -> V256 {
         if matches_isa!(Self, SSE2 | WASM128) {
@@ -518,6 +546,9 @@ pub unsafe trait SIMD256: SIMD128 {
                 t(uint8x16x2_t(c.0, c.1))
             };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x32_swizzle(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -606,6 +637,10 @@ pub unsafe trait SIMD256: SIMD128 {
                 V256::from_v128x2((low, high))
             };
         }
+        if is_isa_type!(Self, Fallback) {
+            let zero = self.v128_create_zero();
+            return V256::from_v128x2((self.u8x16_zip_lo(a, zero), self.u8x16_zip_hi(a, zero)));
+        }
         {
             let _ = a;
             unreachable!()
@@ -700,13 +735,14 @@ pub unsafe trait SIMD256: SIMD128 {
         }
     }
 
+    /// Concatenates the low 128-bit lanes of `a` and `b`.
     #[inline(always)]
     fn v128x2_zip_lo(self, a: V256, b: V256) -> V256 {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         if matches_isa!(Self, AVX2) {
             return unsafe { t(_mm256_permute2x128_si256::<0b0010_0000>(t(a), t(b))) };
         }
-        if matches_isa!(Self, SSE2 | NEON | WASM128) {
+        if matches_isa!(Self, SSE2 | NEON | WASM128) || is_isa_type!(Self, Fallback) {
             let ((a, _), (c, _)) = (a.to_v128x2(), b.to_v128x2());
             return V256::from_v128x2((a, c));
         }
@@ -715,13 +751,14 @@ pub unsafe trait SIMD256: SIMD128 {
         }
     }
 
+    /// Concatenates the high 128-bit lanes of `a` and `b`.
     #[inline(always)]
     fn v128x2_zip_hi(self, a: V256, b: V256) -> V256 {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         if matches_isa!(Self, AVX2) {
             return unsafe { t(_mm256_permute2x128_si256::<0b0011_0001>(t(a), t(b))) };
         }
-        if matches_isa!(Self, SSE2 | NEON | WASM128) {
+        if matches_isa!(Self, SSE2 | NEON | WASM128) || is_isa_type!(Self, Fallback) {
             let ((_, b), (_, d)) = (a.to_v128x2(), b.to_v128x2());
             return V256::from_v128x2((b, d));
         }
@@ -730,15 +767,24 @@ pub unsafe trait SIMD256: SIMD128 {
         }
     }
 
+    /// Moves the 64-bit elements of `a` across the lane boundary.
+    ///
+    /// The element `i` of the result is the element `(IMM8 >> (2 * i)) & 3` of `a`.
+    /// For example, `0b_1101_1000` swaps the middle two elements (0213).
     #[inline(always)]
     fn u64x4_permute<const IMM8: i32>(self, a: V256) -> V256 {
         #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
         if matches_isa!(Self, AVX2) {
             return unsafe { t(_mm256_permute4x64_epi64::<IMM8>(t(a))) };
         }
-        if matches_isa!(Self, SSE2 | NEON | WASM128) {
-            let _ = a;
-            unimplemented!()
+        if matches_isa!(Self, SSE2 | NEON | WASM128) || is_isa_type!(Self, Fallback) {
+            let a = a.as_bytes();
+            let mut b = [0u8; 32];
+            for (i, dst) in b.chunks_exact_mut(8).enumerate() {
+                let j = ((IMM8 >> (2 * i)) & 3) as usize;
+                dst.copy_from_slice(&a[j * 8..j * 8 + 8]);
+            }
+            return V256::from_bytes(b);
         }
         {
             let _ = a;
@@ -746,6 +792,60 @@ pub unsafe trait SIMD256: SIMD128 {
         }
     }
 
+    /// Moves the 32-bit elements of `a` across the lane boundary.
+    ///
+    /// The element `i` of the result is the element `b[i] & 7` of `a`.
+    ///
+    /// + AVX2: `vpermd`.
+    /// + others: [`shuffle_bytes_x2`](Self::shuffle_bytes_x2) by the indices of the bytes,
+    ///   which requires SSSE3 on x86.
+    #[inline(always)]
+    fn permute_cross_lane(self, a: V256, b: V256) -> V256 {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        if matches_isa!(Self, AVX2) {
+            return unsafe { t(_mm256_permutevar8x32_epi32(t(a), t(b))) };
+        }
+        {
+            const SPREAD: V256 = V256::double_bytes([0, 0, 0, 0, 4, 4, 4, 4, 8, 8, 8, 8, 12, 12, 12, 12]);
+            const OFFSET: u32 = u32::from_le_bytes([0, 1, 2, 3]);
+
+            let i = self.u32x8_shl::<2>(self.v256_and(b, self.u32x8_splat(7)));
+            let i = self.u8x32_add(self.u8x16x2_swizzle(i, SPREAD), self.u32x8_splat(OFFSET));
+            // the byte `4 * k + j` selects the byte `4 * (b[k] & 7) + j`
+            self.shuffle_bytes_x2(a, i)
+        }
+    }
+
+    /// Shuffles the bytes of `a` by the indices in `b`, crossing the lane boundary.
+    ///
+    /// The byte `i` of the result is the byte `b[i]` of `a`.
+    /// An index with the highest bit set selects zero. The other indices must be less than 32.
+    ///
+    /// + AVX2: `vpshufb` on each lane of `a` broadcast by `vperm2i128`, merged by `vpblendvb`.
+    /// + aarch64 NEON: `tbl` with a table of two registers.
+    /// + others: the same shuffles and merge on the 128-bit halves, which requires SSSE3 on x86.
+    #[inline(always)]
+    fn shuffle_bytes_x2(self, a: V256, b: V256) -> V256 {
+        #[cfg(target_arch = "aarch64")]
+        if matches_isa!(Self, NEON) {
+            return self.u8x32_swizzle(a, b);
+        }
+        if is_isa_type!(Self, Fallback) {
+            return self.u8x32_swizzle(a, b);
+        }
+        {
+            let lo = self.v128x2_zip_lo(a, a);
+            let hi = self.v128x2_zip_hi(a, a);
+            let c1 = self.u8x16x2_swizzle(lo, b);
+            let c2 = self.u8x16x2_swizzle(hi, self.u8x32_sub(b, self.u8x32_splat(16)));
+            let m = self.i8x32_lt(self.i8x32_splat(15), b);
+            if matches_isa!(Self, SSE41) {
+                return self.u8x32_blendv(c1, c2, m);
+            }
+            self.v256_bsl(m, c2, c1)
+        }
+    }
+
     #[inline(always)]
     fn u8x32_unzip_even(self, a: V256, b: V256) -> V256 {
         if matches_isa!(Self, SSE2) {
@@ -844,6 +944,13 @@ pub unsafe trait SIMD256: SIMD128 {
         if matches_isa!(Self, NEON | WASM128) {
             unimplemented!()
         }
+        if is_isa_type!(Self, Fallback) {
+            return V256::from_bytes(crate::simulation::blend::<4, 32>(
+                *a.as_bytes(),
+                *b.as_bytes(),
+                IMM8 as u32,
+            ));
+        }
         {
             let _ = (a, b);
             unreachable!()
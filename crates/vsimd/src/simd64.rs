@@ -1,4 +1,4 @@
-use crate::isa::InstructionSet;
+use crate::isa::{Fallback, InstructionSet};
 use crate::vector::V64;
 
 #[cfg(any(all(feature = "unstable", target_arch = "arm"), target_arch = "aarch64"))]
@@ -25,6 +25,9 @@ pub unsafe trait SIMD64: InstructionSet {
         if matches_isa!(Self, NEON) {
             return unsafe { t(vuzp1_u8(t(a), t(b))) };
         }
+        if is_isa_type!(Self, Fallback) {
+            return crate::simulation::u8x8_unzip_even(a, b);
+        }
         {
             let _ = (a, b);
             unreachable!()
@@ -1,6 +1,9 @@
-use crate::vector::V128;
+use crate::pod::POD;
+use crate::tools::transmute_copy as tc;
+use crate::vector::{V128, V256, V64};
 
 use core::mem::transmute as t;
+use core::ops::Not;
 
 #[cfg(miri)]
 use core::cmp::{max, min};
@@ -88,3 +91,356 @@ pub fn i16x8_packus(a: V128, b: V128) -> V128 {
     }
     V128::from_bytes(c)
 }
+
+// -----------------------------------------------------------------------------
+// Scalar lowering of the vector operations for the `Fallback` instruction set.
+//
+// The lanes are read and written in little-endian order, like the vector registers
+// on all the supported architectures, so the results do not depend on the target.
+
+trait Lane: Copy + Ord + Not<Output = Self> {
+    const ZERO: Self;
+    const WIDTH: usize;
+
+    fn read(bytes: &[u8]) -> Self;
+    fn write(self, bytes: &mut [u8]);
+}
+
+macro_rules! impl_lane {
+    ($($ty:ident),*) => {
+        $(
+            impl Lane for $ty {
+                const ZERO: Self = 0;
+                const WIDTH: usize = core::mem::size_of::<$ty>();
+
+                #[inline(always)]
+                fn read(bytes: &[u8]) -> Self {
+                    let mut buf = [0; core::mem::size_of::<$ty>()];
+                    buf.copy_from_slice(&bytes[..Self::WIDTH]);
+                    $ty::from_le_bytes(buf)
+                }
+
+                #[inline(always)]
+                fn write(self, bytes: &mut [u8]) {
+                    bytes[..Self::WIDTH].copy_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_lane!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Evaluates `$e` with `$L` bound to the integer type of the POD type `$T`.
+macro_rules! with_lane {
+    ($T:ident, |$L:ident| $e:expr) => {
+        with_lane!(@ $T, $L, $e, u8 u16 u32 u64 i8 i16 i32 i64)
+    };
+    (@ $T:ident, $L:ident, $e:expr, $($ty:ident)*) => {{
+        $(
+            if is_pod_type!($T, $ty) {
+                type $L = $ty;
+                return $e;
+            }
+        )*
+        unreachable!()
+    }};
+}
+
+#[inline(always)]
+fn mask<L: Lane>(cond: bool) -> L {
+    if cond {
+        !L::ZERO
+    } else {
+        L::ZERO
+    }
+}
+
+#[inline(always)]
+fn map1<L: Lane>(a: V128, f: impl Fn(L) -> L) -> V128 {
+    let a = a.as_bytes();
+    let mut c = [0; 16];
+    for i in (0..16).step_by(L::WIDTH) {
+        f(L::read(&a[i..])).write(&mut c[i..]);
+    }
+    V128::from_bytes(c)
+}
+
+#[inline(always)]
+fn map2<L: Lane>(a: V128, b: V128, f: impl Fn(L, L) -> L) -> V128 {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut c = [0; 16];
+    for i in (0..16).step_by(L::WIDTH) {
+        f(L::read(&a[i..]), L::read(&b[i..])).write(&mut c[i..]);
+    }
+    V128::from_bytes(c)
+}
+
+#[inline(always)]
+fn bytes2(a: V128, b: V128, f: impl Fn(u8, u8) -> u8) -> V128 {
+    map2::<u8>(a, b, f)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn splat<T: POD>(x: T) -> V128 {
+    with_lane!(T, |L| {
+        let x: L = unsafe { tc(&x) };
+        map1::<L>(V128::from_bytes([0; 16]), |_| x)
+    })
+}
+
+#[inline(always)]
+#[must_use]
+pub fn add<T: POD>(a: V128, b: V128) -> V128 {
+    with_lane!(T, |L| map2::<L>(a, b, L::wrapping_add))
+}
+
+#[inline(always)]
+#[must_use]
+pub fn sub<T: POD>(a: V128, b: V128) -> V128 {
+    with_lane!(T, |L| map2::<L>(a, b, L::wrapping_sub))
+}
+
+#[inline(always)]
+#[must_use]
+pub fn mul_lo<T: POD>(a: V128, b: V128) -> V128 {
+    with_lane!(T, |L| map2::<L>(a, b, L::wrapping_mul))
+}
+
+#[inline(always)]
+#[must_use]
+pub fn add_sat<T: POD>(a: V128, b: V128) -> V128 {
+    with_lane!(T, |L| map2::<L>(a, b, L::saturating_add))
+}
+
+#[inline(always)]
+#[must_use]
+pub fn sub_sat<T: POD>(a: V128, b: V128) -> V128 {
+    with_lane!(T, |L| map2::<L>(a, b, L::saturating_sub))
+}
+
+#[inline(always)]
+#[must_use]
+pub fn eq<T: POD>(a: V128, b: V128) -> V128 {
+    with_lane!(T, |L| map2::<L>(a, b, |x, y| mask(x == y)))
+}
+
+#[inline(always)]
+#[must_use]
+pub fn lt<T: POD>(a: V128, b: V128) -> V128 {
+    with_lane!(T, |L| map2::<L>(a, b, |x, y| mask(x < y)))
+}
+
+#[inline(always)]
+#[must_use]
+pub fn max<T: POD>(a: V128, b: V128) -> V128 {
+    with_lane!(T, |L| map2::<L>(a, b, Ord::max))
+}
+
+#[inline(always)]
+#[must_use]
+pub fn min<T: POD>(a: V128, b: V128) -> V128 {
+    with_lane!(T, |L| map2::<L>(a, b, Ord::min))
+}
+
+/// Shifts each lane left. The lanes are zero if `imm` is not less than the lane width.
+#[inline(always)]
+#[must_use]
+pub fn shl<T: POD>(a: V128, imm: u32) -> V128 {
+    with_lane!(T, |L| map1::<L>(a, |x| x.checked_shl(imm).unwrap_or(0)))
+}
+
+/// Shifts each lane right logically. The lanes are zero if `imm` is not less than the lane width.
+#[inline(always)]
+#[must_use]
+pub fn shr<T: POD>(a: V128, imm: u32) -> V128 {
+    with_lane!(T, |L| map1::<L>(a, |x| x.checked_shr(imm).unwrap_or(0)))
+}
+
+#[inline(always)]
+#[must_use]
+pub fn and(a: V128, b: V128) -> V128 {
+    bytes2(a, b, |x, y| x & y)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn or(a: V128, b: V128) -> V128 {
+    bytes2(a, b, |x, y| x | y)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn xor(a: V128, b: V128) -> V128 {
+    bytes2(a, b, |x, y| x ^ y)
+}
+
+/// `a & !b`
+#[inline(always)]
+#[must_use]
+pub fn andnot(a: V128, b: V128) -> V128 {
+    bytes2(a, b, |x, y| x & !y)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn not(a: V128) -> V128 {
+    map1::<u8>(a, |x| !x)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn all_zero(a: V128) -> bool {
+    a.as_bytes().iter().all(|&x| x == 0)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn u8x16_any_zero(a: V128) -> bool {
+    a.as_bytes().contains(&0)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn u8x16_reduce_max(a: V128) -> u8 {
+    a.as_bytes().iter().copied().fold(u8::MIN, Ord::max)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn u8x16_reduce_min(a: V128) -> u8 {
+    a.as_bytes().iter().copied().fold(u8::MAX, Ord::min)
+}
+
+/// Looks up the table `a` by the indices in `b`. The lanes are zero where the index is out of range.
+#[inline(always)]
+#[must_use]
+pub fn u8x16_swizzle(a: V128, b: V128) -> V128 {
+    let a = a.as_bytes();
+    map1::<u8>(b, |i| a.get(i as usize).copied().unwrap_or(0))
+}
+
+/// Looks up the table `a` by the indices in `b`. The lanes are zero where the index is out of range.
+#[inline(always)]
+#[must_use]
+pub fn u8x32_swizzle(a: V256, b: V256) -> V256 {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut c = [0; 32];
+    for (c, &i) in c.iter_mut().zip(b) {
+        *c = a.get(i as usize).copied().unwrap_or(0);
+    }
+    V256::from_bytes(c)
+}
+
+/// Interleaves the lanes of `W` bytes from the low (`HI = false`) or high (`HI = true`) halves of `a` and `b`.
+#[inline(always)]
+#[must_use]
+pub fn zip<const W: usize, const HI: bool>(a: V128, b: V128) -> V128 {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let base = if HI { 8 } else { 0 };
+    let mut c = [0; 16];
+    for (i, pair) in c.chunks_exact_mut(W * 2).enumerate() {
+        let src = base + i * W;
+        pair[..W].copy_from_slice(&a[src..src + W]);
+        pair[W..].copy_from_slice(&b[src..src + W]);
+    }
+    V128::from_bytes(c)
+}
+
+/// Takes the even (`ODD = false`) or odd (`ODD = true`) bytes of `a` followed by those of `b`.
+#[inline(always)]
+#[must_use]
+pub fn u8x16_unzip<const ODD: bool>(a: V128, b: V128) -> V128 {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut c = [0; 16];
+    for i in 0..8 {
+        c[i] = a[i * 2 + ODD as usize];
+        c[i + 8] = b[i * 2 + ODD as usize];
+    }
+    V128::from_bytes(c)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn u8x8_unzip_even(a: V64, b: V64) -> V64 {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut c = [0; 8];
+    for i in 0..4 {
+        c[i] = a[i * 2];
+        c[i + 4] = b[i * 2];
+    }
+    V64::from_bytes(c)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn u16x8_mul_hi(a: V128, b: V128) -> V128 {
+    map2::<u16>(a, b, |x, y| ((u32::from(x) * u32::from(y)) >> 16) as u16)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn i16x8_mul_hi(a: V128, b: V128) -> V128 {
+    map2::<i16>(a, b, |x, y| ((i32::from(x) * i32::from(y)) >> 16) as i16)
+}
+
+/// Multiplies the unsigned bytes of `a` by the signed bytes of `b`
+/// and adds the adjacent pairs of products with saturation.
+#[inline(always)]
+#[must_use]
+pub fn i16x8_maddubs(a: V128, b: V128) -> V128 {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut c = [0; 16];
+    for i in (0..16).step_by(2) {
+        let p0 = i16::from(a[i]) * i16::from(b[i] as i8);
+        let p1 = i16::from(a[i + 1]) * i16::from(b[i + 1] as i8);
+        p0.saturating_add(p1).write(&mut c[i..]);
+    }
+    V128::from_bytes(c)
+}
+
+/// Multiplies the signed 16-bit lanes and adds the adjacent pairs of products to 32-bit lanes.
+#[inline(always)]
+#[must_use]
+pub fn i16x8_madd(a: V128, b: V128) -> V128 {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut c = [0; 16];
+    for i in (0..16).step_by(4) {
+        let p0 = i32::from(i16::read(&a[i..])) * i32::from(i16::read(&b[i..]));
+        let p1 = i32::from(i16::read(&a[i + 2..])) * i32::from(i16::read(&b[i + 2..]));
+        p0.wrapping_add(p1).write(&mut c[i..]);
+    }
+    V128::from_bytes(c)
+}
+
+/// Takes the lane `i` of `b` if the bit `i` of `imm` is set, otherwise the lane `i` of `a`.
+#[inline(always)]
+#[must_use]
+pub fn blend<const W: usize, const N: usize>(a: [u8; N], b: [u8; N], imm: u32) -> [u8; N] {
+    let mut c = a;
+    for (i, lane) in c.chunks_exact_mut(W).enumerate() {
+        if imm & (1 << i) != 0 {
+            lane.copy_from_slice(&b[i * W..i * W + W]);
+        }
+    }
+    c
+}
+
+/// `if highbit(c) { b } else { a }` for each byte.
+#[inline(always)]
+#[must_use]
+pub fn u8x16_blendv(a: V128, b: V128, c: V128) -> V128 {
+    let (a, b, c) = (a.as_bytes(), b.as_bytes(), c.as_bytes());
+    let mut d = [0; 16];
+    for i in 0..16 {
+        d[i] = if c[i] & 0x80 != 0 { b[i] } else { a[i] };
+    }
+    V128::from_bytes(d)
+}
+
+#[inline(always)]
+#[must_use]
+pub fn u8x16_avgr(a: V128, b: V128) -> V128 {
+    bytes2(a, b, |x, y| ((u16::from(x) + u16::from(y) + 1) >> 1) as u8)
+}
@@ -1,4 +1,4 @@
-use crate::isa::{NEON, SSSE3, WASM128};
+use crate::isa::{Fallback, NEON, SSSE3, WASM128};
 use crate::pod::POD;
 use crate::Scalable;
 
@@ -12,7 +12,7 @@ where
         return s.u8x16xn_swizzle(lut, x);
     }
 
-    if matches_isa!(S, NEON | WASM128) {
+    if matches_isa!(S, NEON | WASM128) || is_isa_type!(S, Fallback) {
         let idx = s.and(x, s.u8xn_splat(0x8f));
         return s.u8x16xn_swizzle(lut, idx);
     }
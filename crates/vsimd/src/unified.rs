@@ -1,7 +1,8 @@
 #![allow(clippy::collapsible_if, clippy::too_many_lines)]
 
-use crate::isa::InstructionSet;
+use crate::isa::{Fallback, InstructionSet};
 use crate::pod::POD;
+use crate::simulation;
 use crate::tools::transmute_copy as tc;
 use crate::vector::{V128, V256};
 
@@ -89,6 +90,9 @@ pub fn splat<S: InstructionSet, T: POD, V: POD>(s: S, x: T) -> V {
                 return unsafe { tc(&u64x2_splat(tc(&x))) };
             }
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::splat::<T>(x)) };
+        }
     }
     {
         let _ = (s, x);
@@ -168,6 +172,9 @@ pub fn add<S: InstructionSet, T: POD, V: POD>(s: S, a: V, b: V) -> V {
                 return unsafe { tc(&u64x2_add(tc(&a), tc(&b))) };
             }
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::add::<T>(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -247,6 +254,9 @@ pub fn sub<S: InstructionSet, T: POD, V: POD>(s: S, a: V, b: V) -> V {
                 return unsafe { tc(&u64x2_sub(tc(&a), tc(&b))) };
             }
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::sub::<T>(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -324,6 +334,9 @@ pub fn eq<S: InstructionSet, T: POD, V: POD>(s: S, a: V, b: V) -> V {
                 return unsafe { tc(&u64x2_eq(tc(&a), tc(&b))) };
             }
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::eq::<T>(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -470,6 +483,9 @@ pub fn lt<S: InstructionSet, T: POD, V: POD>(s: S, a: V, b: V) -> V {
             //     return unsafe { tc(&u64x2_lt(tc(&a), tc(&b))) };
             // }
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::lt::<T>(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -555,6 +571,9 @@ pub fn add_sat<S: InstructionSet, T: POD, V: POD>(s: S, a: V, b: V) -> V {
                 return unsafe { tc(&u16x8_add_sat(tc(&a), tc(&b))) };
             }
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::add_sat::<T>(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -640,6 +659,9 @@ pub fn sub_sat<S: InstructionSet, T: POD, V: POD>(s: S, a: V, b: V) -> V {
                 return unsafe { tc(&u16x8_sub_sat(tc(&a), tc(&b))) };
             }
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::sub_sat::<T>(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -769,6 +791,9 @@ pub fn max<S: InstructionSet, T: POD, V: POD>(s: S, a: V, b: V) -> V {
                 return unsafe { tc(&f32x4_max(tc(&a), tc(&b))) };
             }
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::max::<T>(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -900,6 +925,9 @@ pub fn min<S: InstructionSet, T: POD, V: POD>(s: S, a: V, b: V) -> V {
                 return unsafe { tc(&f32x4_min(tc(&a), tc(&b))) };
             }
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::min::<T>(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -939,6 +967,9 @@ where
         if matches_isa!(S, WASM128) {
             return unsafe { tc(&v128_and(tc(&a), tc(&b))) };
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::and(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -978,6 +1009,9 @@ where
         if matches_isa!(S, WASM128) {
             return unsafe { tc(&v128_or(tc(&a), tc(&b))) };
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::or(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -1017,6 +1051,9 @@ where
         if matches_isa!(S, WASM128) {
             return unsafe { tc(&v128_xor(tc(&a), tc(&b))) };
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::xor(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
@@ -1058,6 +1095,9 @@ where
         if matches_isa!(S, WASM128) {
             return unsafe { tc(&v128_andnot(tc(&a), tc(&b))) };
         }
+        if is_isa_type!(S, Fallback) {
+            return unsafe { tc(&simulation::andnot(tc(&a), tc(&b))) };
+        }
     }
     {
         let _ = (s, a, b);
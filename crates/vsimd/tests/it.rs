@@ -1,7 +1,7 @@
 use vsimd::isa::detect;
-use vsimd::isa::{NEON, SSE2, WASM128};
-use vsimd::vector::V128;
-use vsimd::SIMD128;
+use vsimd::isa::{Fallback, AVX2, NEON, SSE2, SSE41, SSSE3, WASM128};
+use vsimd::vector::{V128, V256};
+use vsimd::{SIMD128, SIMD256};
 
 use const_str::hex;
 
@@ -55,3 +55,337 @@ fn u8x16_any_zero() {
     test(hex!("00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F"), true);
     test(hex!("10 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F"), false);
 }
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn crc() {
+    use vsimd::crc::{crc32, crc32_update, crc32c, crc32c_update};
+
+    fn bitwise(poly: u32, data: &[u8]) -> u32 {
+        let mut crc = !0u32;
+        for &x in data {
+            crc ^= u32::from(x);
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ poly } else { crc >> 1 };
+            }
+        }
+        !crc
+    }
+
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    assert_eq!(crc32c(b""), 0);
+    assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+
+    let data: Vec<u8> = (0..1000).map(|_| rand::random::<u8>()).collect();
+    for n in (0..200).chain([255, 256, 257, 511, 512, 999, 1000]) {
+        let data = &data[..n];
+        assert_eq!(crc32(data), bitwise(0xedb8_8320, data));
+        assert_eq!(crc32c(data), bitwise(0x82f6_3b78, data));
+
+        let (a, b) = data.split_at(n / 3);
+        assert_eq!(crc32_update(crc32(a), b), crc32(data));
+        assert_eq!(crc32c_update(crc32c(a), b), crc32c(data));
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn reference() {
+    use vsimd::reference::{decode, encode};
+
+    const HEX: &[u8] = b"0123456789abcdef";
+    const BASE32: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let cases: &[(&[u8], Option<u8>, &str, &str)] = &[
+        (HEX, None, "", ""),
+        (HEX, None, "foobar", "666f6f626172"),
+        (BASE32, Some(b'='), "", ""),
+        (BASE32, Some(b'='), "f", "MY======"),
+        (BASE32, Some(b'='), "fooba", "MZXW6YTB"),
+        (BASE32, Some(b'='), "foobar", "MZXW6YTBOI======"),
+        (BASE32, None, "foobar", "MZXW6YTBOI"),
+        (BASE64, Some(b'='), "", ""),
+        (BASE64, Some(b'='), "f", "Zg=="),
+        (BASE64, Some(b'='), "fo", "Zm8="),
+        (BASE64, Some(b'='), "foo", "Zm9v"),
+        (BASE64, None, "foob", "Zm9vYg"),
+    ];
+    for &(charset, pad, input, output) in cases {
+        assert_eq!(encode(input.as_bytes(), charset, pad), output.as_bytes());
+        assert_eq!(decode(output.as_bytes(), charset, pad).unwrap(), input.as_bytes());
+    }
+
+    let invalid: &[(&[u8], Option<u8>, &str)] = &[
+        (HEX, None, "666"),
+        (HEX, None, "66zz"),
+        (BASE32, Some(b'='), "MZXW6YTBOI"),
+        (BASE32, Some(b'='), "MZXW6YTB========"),
+        (BASE32, Some(b'='), "MZXW6Y=="),
+        (BASE32, None, "MZXW6YTBOJ"),
+        (BASE64, Some(b'='), "Zg="),
+        (BASE64, Some(b'='), "Zh=="),
+        (BASE64, Some(b'='), "Z==="),
+        (BASE64, None, "Zg=="),
+        (BASE64, None, "Zm9vY"),
+    ];
+    for &(charset, pad, input) in invalid {
+        assert!(decode(input.as_bytes(), charset, pad).is_none(), "{input}");
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn reference_bits() {
+    use vsimd::reference::{decode_bits, encode_bits};
+
+    const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut table = [0xff; 256];
+    for (i, &c) in BASE64.iter().enumerate() {
+        table[c as usize] = i as u8;
+    }
+
+    let mut buf = [0u8; 4];
+    let k = unsafe { encode_bits::<6>(b"fo".as_ptr(), 2, buf.as_mut_ptr(), BASE64.as_ptr()) };
+    assert_eq!(&buf[..k], b"Zm8");
+
+    let decode = |src: &[u8], forgiving: bool| -> Option<[u8; 4]> {
+        let mut dst = [0u8; 4];
+        let ok =
+            unsafe { decode_bits::<6, true>(src.as_ptr(), src.len(), dst.as_mut_ptr(), table.as_ptr(), forgiving) };
+        ok.then_some(dst)
+    };
+    assert_eq!(decode(b"Zm8", false), Some([b'f', b'o', 0, 0]));
+    assert_eq!(decode(b"Zh", false), None);
+    assert_eq!(decode(b"Zh", true), Some([b'f', 0, 0, 0]));
+    assert_eq!(decode(b"Z", true), None);
+    assert_eq!(decode(b"Z=", true), None);
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn cross_lane() {
+    fn check<S: SIMD256>(s: S) {
+        let a = V256::from_bytes(core::array::from_fn(|i| i as u8));
+        let b = V256::from_bytes(core::array::from_fn(|i| i as u8 + 32));
+
+        let expected: [u8; 32] = core::array::from_fn(|i| [0, 2, 1, 3][i / 8] * 8 + (i % 8) as u8);
+        assert_eq!(s.u64x4_permute::<0b_1101_1000>(a).as_bytes(), &expected);
+
+        let expected: [u8; 32] = core::array::from_fn(|i| [3, 3, 0, 1][i / 8] * 8 + (i % 8) as u8);
+        assert_eq!(s.u64x4_permute::<0b_0100_1111>(a).as_bytes(), &expected);
+
+        let expected: [u8; 32] = core::array::from_fn(|i| if i < 16 { i as u8 } else { i as u8 + 16 });
+        assert_eq!(s.v128x2_zip_lo(a, b).as_bytes(), &expected);
+
+        let expected: [u8; 32] = core::array::from_fn(|i| if i < 16 { i as u8 + 16 } else { i as u8 + 32 });
+        assert_eq!(s.v128x2_zip_hi(a, b).as_bytes(), &expected);
+    }
+
+    if let Some(s) = detect::<AVX2>() {
+        check(s);
+    }
+    if let Some(s) = detect::<SSE2>() {
+        check(s);
+    }
+    if let Some(s) = detect::<NEON>() {
+        check(s);
+    }
+    if let Some(s) = detect::<WASM128>() {
+        check(s);
+    }
+    if let Some(s) = detect::<Fallback>() {
+        check(s);
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn shuffle_cross_lane() {
+    fn check<S: SIMD256>(s: S) {
+        let a = V256::from_bytes(core::array::from_fn(|i| i as u8 + 100));
+
+        let idx: [u32; 8] = [0, 1, 2, 4, 5, 6, 3, 15];
+        let b = V256::from_bytes(core::array::from_fn(|i| idx[i / 4].to_le_bytes()[i % 4]));
+        let expected: [u8; 32] = core::array::from_fn(|i| (idx[i / 4] & 7) as u8 * 4 + (i % 4) as u8 + 100);
+        assert_eq!(s.permute_cross_lane(a, b).as_bytes(), &expected);
+
+        let idx: [u8; 32] = core::array::from_fn(|i| match i % 3 {
+            0 => 31 - i as u8,
+            1 => 0x80,
+            _ => i as u8,
+        });
+        let b = V256::from_bytes(idx);
+        let expected: [u8; 32] = core::array::from_fn(|i| if idx[i] < 32 { idx[i] + 100 } else { 0 });
+        assert_eq!(s.shuffle_bytes_x2(a, b).as_bytes(), &expected);
+    }
+
+    if let Some(s) = detect::<AVX2>() {
+        check(s);
+    }
+    if let Some(s) = detect::<SSSE3>() {
+        check(s);
+    }
+    if let Some(s) = detect::<NEON>() {
+        check(s);
+    }
+    if let Some(s) = detect::<WASM128>() {
+        check(s);
+    }
+    if let Some(s) = detect::<Fallback>() {
+        check(s);
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn fallback() {
+    fn ops<S: SIMD256>(s: S, a: V256, b: V256) -> [[u8; 32]; 33] {
+        let idx = s.v256_and(b, s.u8x32_splat(0x8f));
+        let (a0, a1) = a.to_v128x2();
+        let (b0, b1) = b.to_v128x2();
+        [
+            s.u8x32_add(a, b),
+            s.u16x16_sub(a, b),
+            s.u64x4_add(a, b),
+            s.u8x32_add_sat(a, b),
+            s.i16x16_sub_sat(a, b),
+            s.u8x32_eq(a, b),
+            s.u16x16_lt(a, b),
+            s.i8x32_lt(a, b),
+            s.i32x8_max(a, b),
+            s.u8x32_min(a, b),
+            s.i16x16_mul_lo(a, b),
+            s.i32x8_mul_lo(a, b),
+            s.u16x16_shl::<3>(a),
+            s.u32x8_shr::<5>(a),
+            s.v256_andnot(a, b),
+            s.v256_not(a),
+            s.u8x16x2_swizzle(a, idx),
+            s.u32x8_bswap(a),
+            s.u8x16x2_zip_lo(a, b),
+            s.u16x8x2_zip_hi(a, b),
+            s.u64x2x2_zip_lo(a, b),
+            s.u64x4_unzip_odd(a, b),
+            s.u16x16_mul_hi(a, b),
+            s.i16x16_mul_hi(a, b),
+            s.i16x16_maddubs(a, b),
+            s.i16x16_madd(a, b),
+            s.u8x32_avgr(a, b),
+            s.u32x8_blend::<0b_1010_0110>(a, b),
+            s.u8x32_blendv(a, b, idx),
+            s.u16x16_from_u8x16(a0),
+            V256::from_v128x2((s.u32x4_zip_lo(a0, a1), s.i16x8_packus(b0, b1))),
+            V256::from_v128x2((s.v128_bsl(a0, a1, b0), s.u8x16_zip_hi(b0, b1))),
+            s.u32x8_splat(s.u8x32_bitmask(a).count_ones() << 16 | u32::from(s.u8x32_any_zero(a))),
+        ]
+        .map(|v| *v.as_bytes())
+    }
+
+    let f = detect::<Fallback>().unwrap();
+    for _ in 0..100 {
+        let a = V256::from_bytes(rand::random());
+        let b = V256::from_bytes(rand::random());
+        let expected = if let Some(s) = detect::<AVX2>() {
+            ops(s, a, b)
+        } else if let Some(s) = detect::<SSE41>() {
+            ops(s, a, b)
+        } else {
+            return;
+        };
+        for (i, (x, y)) in ops(f, a, b).iter().zip(expected.iter()).enumerate() {
+            assert_eq!(x, y, "op {i}");
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+fn alsw_build_check() {
+    use vsimd::alsw::{self, AlswLut};
+
+    fn check<S: SIMD256>(s: S, lut: AlswLut<V256>, valid: &[bool; 256]) {
+        let filler = (0..=255u8).find(|&c| valid[c as usize]).unwrap();
+        for c in 0..=255u8 {
+            for pos in [0, 7, 16, 31] {
+                let mut bytes = [filler; 32];
+                bytes[pos] = c;
+                let ans = alsw::check_ascii_xn(s, V256::from_bytes(bytes), lut);
+                assert_eq!(ans, valid[c as usize], "c = {c:#04x}, pos = {pos}");
+            }
+        }
+    }
+
+    let charsets: [&[(u8, u8)]; 7] = [
+        &[(b'A', b'Z'), (b'a', b'z'), (b'0', b'9'), (b'+', b'+'), (b'/', b'/')],
+        &[(b'A', b'Z'), (b'a', b'z'), (b'0', b'9'), (b'-', b'-'), (b'_', b'_')],
+        &[(b'A', b'Z'), (b'2', b'7')],
+        &[(b'0', b'9'), (b'a', b'f'), (b'A', b'F')],
+        &[
+            (b'0', b'9'),
+            (b'A', b'H'),
+            (b'J', b'K'),
+            (b'M', b'N'),
+            (b'P', b'T'),
+            (b'V', b'Z'),
+            (b'a', b'h'),
+            (b'j', b'k'),
+            (b'm', b'n'),
+            (b'p', b't'),
+            (b'v', b'z'),
+        ],
+        &[
+            (b'1', b'9'),
+            (b'A', b'H'),
+            (b'J', b'N'),
+            (b'P', b'Z'),
+            (b'a', b'k'),
+            (b'm', b'z'),
+        ],
+        &[(b' ', b'~')],
+    ];
+
+    for ranges in charsets {
+        let mut valid = [false; 256];
+        for &(lo, hi) in ranges {
+            (lo..=hi).for_each(|c| valid[c as usize] = true);
+        }
+
+        let lut = alsw::build_check(ranges).unwrap();
+        for c in 0..=255u8 {
+            let ans = alsw::check(lut.hash.as_bytes(), lut.offset.as_bytes(), c) < 0x80;
+            assert_eq!(ans, valid[c as usize], "c = {c:#04x}");
+        }
+
+        let lut = lut.x2();
+        if let Some(s) = detect::<AVX2>() {
+            check(s, lut, &valid);
+        }
+        if let Some(s) = detect::<SSE41>() {
+            check(s, lut, &valid);
+        }
+        if let Some(s) = detect::<NEON>() {
+            check(s, lut, &valid);
+        }
+        if let Some(s) = detect::<WASM128>() {
+            check(s, lut, &valid);
+        }
+        if let Some(s) = detect::<Fallback>() {
+            check(s, lut, &valid);
+        }
+    }
+
+    // Crockford's base32 without the lowercase letters can not be checked by ALSW.
+    let crockford: &[(u8, u8)] = &[
+        (b'0', b'9'),
+        (b'A', b'H'),
+        (b'J', b'K'),
+        (b'M', b'N'),
+        (b'P', b'T'),
+        (b'V', b'Z'),
+    ];
+    assert!(alsw::build_check(crockford).is_none());
+    assert!(alsw::build_check(&[(b'a', 0x80)]).is_none());
+}
@@ -6,10 +6,13 @@ SYMBOLS = {
         "check": ["avx2", "sse2", "neon", "simd128"],
         "encode": ["avx2", "ssse3", "sse2", "neon", "simd128"],
         "decode": ["avx2", "ssse3", "sse2", "neon", "simd128"],
+        "decode_nibbles": ["avx2", "ssse3", "neon", "simd128"],
     },
     "base64-simd": {
         "encode": ["avx2", "ssse3", "neon", "simd128"],
         "decode": ["avx2", "ssse3", "neon", "simd128"],
+        "decode_any": ["avx2", "ssse3", "neon", "simd128"],
+        "decode_trusted": ["avx2", "ssse3", "neon", "simd128"],
         "check": ["avx2", "ssse3", "neon", "simd128"],
         "find_non_ascii_whitespace": ["avx2", "sse2", "neon", "simd128"],
     },